@@ -71,7 +71,8 @@ fn build_wamr() {
         )
         .define("WAMR_BUILD_AOT", "0")
         //.define("WAMR_BUILD_TAIL_CALL", "1")
-        //.define("WAMR_BUILD_DUMP_CALL_STACK", "1")
+        // Records the frames of traps, see `wasm_trap_trace`.
+        .define("WAMR_BUILD_DUMP_CALL_STACK", "1")
         // .define("WAMR_BUILD_CUSTOM_NAME_SECTION", "1")
         // .define("WAMR_BUILD_LOAD_CUSTOM_SECTION", "1")
         .define("WAMR_BUILD_BULK_MEMORY", "1")
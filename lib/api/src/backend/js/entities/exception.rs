@@ -44,6 +44,20 @@ impl ExceptionRef {
         unimplemented!("ExceptionRef is not yet supported in js");
     }
 
+    pub fn downcast_mut<'a, T>(&self, _store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        unimplemented!("ExceptionRef is not yet supported in js");
+    }
+
+    pub fn try_unwrap<T>(self, _store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        unimplemented!("ExceptionRef is not yet supported in js");
+    }
+
     pub(crate) fn vm_exceptionref(&self) -> VMExceptionRef {
         unimplemented!("ExceptionRef is not yet supported in js");
     }
@@ -394,10 +394,10 @@ macro_rules! impl_host_function {
                     Ok(Ok(result)) => return result.into_c_struct(&mut store),
                     #[cfg(feature = "std")]
                     #[allow(deprecated)]
-                    Ok(Err(trap)) => crate::backend::js::error::raise(Box::new(trap)),
+                    Ok(Err(trap)) => crate::backend::js::error::raise(crate::error::box_user_error(trap)),
                     #[cfg(feature = "core")]
                     #[allow(deprecated)]
-                    Ok(Err(trap)) => crate::backend::js::error::raise(Box::new(trap)),
+                    Ok(Err(trap)) => crate::backend::js::error::raise(crate::error::box_user_error(trap)),
                     Err(_panic) => unimplemented!(),
                 }
             }
@@ -441,10 +441,10 @@ macro_rules! impl_host_function {
                     Ok(Ok(result)) => return result.into_c_struct(&mut store),
                     #[allow(deprecated)]
                     #[cfg(feature = "std")]
-                    Ok(Err(trap)) => crate::js::error::raise(Box::new(trap)),
+                    Ok(Err(trap)) => crate::js::error::raise(crate::error::box_user_error(trap)),
                     #[cfg(feature = "core")]
                     #[allow(deprecated)]
-                    Ok(Err(trap)) => crate::js::error::raise(Box::new(trap)),
+                    Ok(Err(trap)) => crate::js::error::raise(crate::error::box_user_error(trap)),
                     Err(_panic) => unimplemented!(),
                 }
             }
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use bytes::Bytes;
 use js_sys::{Reflect, Uint8Array, WebAssembly};
@@ -6,7 +6,7 @@ use tracing::{debug, warn};
 use wasm_bindgen::{prelude::*, JsValue};
 use wasmer_types::{
     CompileError, DeserializeError, ExportType, ExportsIterator, ExternType, FunctionType,
-    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleInfo, Mutability, Pages,
+    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleHash, ModuleInfo, Mutability, Pages,
     SerializeError, TableType, Type,
 };
 
@@ -44,6 +44,11 @@ pub struct Module {
     type_hints: Option<ModuleTypeHints>,
     #[cfg(feature = "js-serializable-module")]
     raw_bytes: Option<Bytes>,
+    /// The hash of the Wasm binary, unknown for modules created from a
+    /// JavaScript module alone.
+    hash: Option<ModuleHash>,
+    /// The renamings of import modules, in the order they were applied.
+    import_remappings: Vec<HashMap<String, String>>,
 }
 
 // XXX
@@ -112,8 +117,10 @@ impl Module {
             module: JsHandle::new(module),
             type_hints,
             name,
+            hash: Some(ModuleHash::xxhash(&binary)),
             #[cfg(feature = "js-serializable-module")]
             raw_bytes: Some(binary),
+            import_remappings: vec![],
         }
     }
 
@@ -146,7 +153,8 @@ impl Module {
 
         let imports_object = js_sys::Object::new();
         let mut import_externs: Vec<Extern> = vec![];
-        for import_type in self.imports() {
+        let declared_modules = self.declared_import_modules();
+        for (import_type, declared_module) in self.imports().zip(declared_modules) {
             let resolved_import = imports.get_export(import_type.module(), import_type.name());
             // Annotation is here to prevent spurious IDE warnings.
             #[allow(unused_variables)]
@@ -166,7 +174,9 @@ impl Module {
             #[allow(unused_unsafe)]
             unsafe {
                 if let Some(import) = resolved_import {
-                    let val = js_sys::Reflect::get(&imports_object, &import_type.module().into())?;
+                    // The Wasm binary still imports from its declared module.
+                    let val =
+                        js_sys::Reflect::get(&imports_object, &declared_module.as_str().into())?;
                     if !val.is_undefined() {
                         // If the namespace is already set
                         js_sys::Reflect::set(
@@ -184,7 +194,7 @@ impl Module {
                         )?;
                         js_sys::Reflect::set(
                             &imports_object,
-                            &import_type.module().into(),
+                            &declared_module.as_str().into(),
                             &import_namespace.into(),
                         )?;
                     }
@@ -210,12 +220,15 @@ impl Module {
 
     pub fn serialize(&self) -> Result<Bytes, SerializeError> {
         #[cfg(feature = "js-serializable-module")]
-        return self.raw_bytes.clone().ok_or(SerializeError::Generic(
-            "Not able to serialize module".to_string(),
-        ));
+        return self
+            .raw_bytes
+            .clone()
+            .ok_or(SerializeError::UnsupportedByRuntime(
+                "the original bytes of the module are not available".to_string(),
+            ));
 
         #[cfg(not(feature = "js-serializable-module"))]
-        return Err(SerializeError::Generic(
+        return Err(SerializeError::UnsupportedByRuntime(
             "You need to enable the `js-serializable-module` feature flag to serialize a `Module`"
                 .to_string(),
         ));
@@ -279,6 +292,44 @@ impl Module {
         //     .unwrap_or(false)
     }
 
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.hash
+    }
+
+    pub fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        self.import_remappings.push(remapping.clone());
+        self
+    }
+
+    /// Returns the name `module` is imported under, once renamed by
+    /// [`Module::with_imports_override`].
+    fn import_module(&self, mut module: String) -> String {
+        for remapping in &self.import_remappings {
+            if let Some(renamed) = remapping.get(&module) {
+                module.clone_from(renamed);
+            }
+        }
+        module
+    }
+
+    /// Returns the module names of the imports as the Wasm binary declares
+    /// them, before any renaming.
+    fn declared_import_modules(&self) -> Vec<String> {
+        WebAssembly::Module::imports(&self.module)
+            .iter()
+            .map(|val| {
+                // Annotation is here to prevent spurious IDE warnings.
+                #[allow(unused_unsafe)]
+                unsafe {
+                    Reflect::get(val.as_ref(), &"module".into())
+                        .unwrap()
+                        .as_string()
+                        .unwrap()
+                }
+            })
+            .collect()
+    }
+
     pub fn imports<'a>(&'a self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + 'a>> {
         let imports = WebAssembly::Module::imports(&self.module);
         let iter = imports
@@ -329,7 +380,7 @@ impl Module {
                             _ => unimplemented!(),
                         }
                     };
-                    ImportType::new(&module, &field, extern_type)
+                    ImportType::new(&self.import_module(module), &field, extern_type)
                 }
             })
             .collect::<Vec<_>>()
@@ -450,6 +501,11 @@ impl Module {
     pub(crate) fn info(&self) -> &ModuleInfo {
         unimplemented!()
     }
+
+    pub(crate) fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        // The binary isn't always kept around, as for `info`.
+        Vec::new()
+    }
 }
 
 impl From<WebAssembly::Module> for Module {
@@ -461,6 +517,8 @@ impl From<WebAssembly::Module> for Module {
             type_hints: None,
             #[cfg(feature = "js-serializable-module")]
             raw_bytes: None,
+            hash: None,
+            import_remappings: vec![],
         }
     }
 }
@@ -78,6 +78,15 @@ impl Table {
         }
     }
 
+    pub fn is_null_at(&self, store: &impl AsStoreRef, index: u32) -> Result<bool, RuntimeError> {
+        if index >= self.size(store) {
+            return Err(crate::entities::table::out_of_bounds(index));
+        }
+
+        let item = self.handle.table.get(index).map_err(RuntimeError::from)?;
+        Ok(item.is_null())
+    }
+
     pub fn set(
         &self,
         store: &mut impl AsStoreMut,
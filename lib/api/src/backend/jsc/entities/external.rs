@@ -15,6 +15,17 @@ impl ExternRef {
         unimplemented!("ExternRef is not yet supported in Javascript");
     }
 
+    pub fn new_with_finalizer<T>(
+        _store: &mut impl AsStoreMut,
+        _value: T,
+        _finalizer: Box<dyn FnOnce(&mut T) + Send>,
+    ) -> Self
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        unimplemented!("ExternRef is not yet supported in Javascript");
+    }
+
     pub fn downcast<'a, T>(&self, _store: &'a impl AsStoreRef) -> Option<&'a T>
     where
         T: Any + Send + Sync + 'static + Sized,
@@ -22,6 +33,28 @@ impl ExternRef {
         unimplemented!("ExternRef is not yet supported in Javascript");
     }
 
+    pub fn downcast_mut<'a, T>(&self, _store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        unimplemented!("ExternRef is not yet supported in Javascript");
+    }
+
+    pub fn take<T>(self, _store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        unimplemented!("ExternRef is not yet supported in Javascript");
+    }
+
+    pub fn type_name(&self, _store: &impl AsStoreRef) -> &'static str {
+        unimplemented!("ExternRef is not yet supported in Javascript");
+    }
+
+    pub(crate) fn ptr_eq(_a: &Self, _b: &Self) -> bool {
+        unimplemented!("ExternRef is not yet supported in Javascript");
+    }
+
     pub(crate) fn vm_externref(&self) -> VMExternRef {
         unimplemented!("ExternRef is not yet supported in Javascript");
     }
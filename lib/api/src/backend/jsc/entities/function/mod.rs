@@ -444,12 +444,12 @@ macro_rules! impl_host_function {
                     },
                     #[cfg(feature = "std")]
                     Ok(Err(err)) => {
-                        let trap = crate::backend::jsc::error::Trap::user(Box::new(err));
+                        let trap = crate::backend::jsc::error::Trap::user(crate::error::box_user_error(err));
                         Err(trap.into_jsc_value(&ctx))
                     },
                     #[cfg(feature = "core")]
                     Ok(Err(err)) => {
-                        let trap = crate::backend::jsc::error::Trap::user(Box::new(err));
+                        let trap = crate::backend::jsc::error::Trap::user(crate::error::box_user_error(err));
                         Err(trap.into_jsc_value(&ctx))
                     },
                     Err(panic) => {
@@ -547,12 +547,12 @@ macro_rules! impl_host_function {
                     },
                     #[cfg(feature = "std")]
                     Ok(Err(err)) => {
-                        let trap = crate::jsc::vm::Trap::user(Box::new(err));
+                        let trap = crate::jsc::vm::Trap::user(crate::error::box_user_error(err));
                         Err(trap.into_jsc_value(&ctx))
                     },
                     #[cfg(feature = "core")]
                     Ok(Err(err)) => {
-                        let trap = crate::jsc::vm::Trap::user(Box::new(err));
+                        let trap = crate::jsc::vm::Trap::user(crate::error::box_user_error(err));
                         Err(trap.into_jsc_value(&ctx))
                     },
                     Err(panic) => {
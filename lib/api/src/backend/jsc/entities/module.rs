@@ -1,11 +1,11 @@
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 
 use bytes::Bytes;
 use rusty_jsc::{JSObject, JSValue};
 use tracing::warn;
 use wasmer_types::{
     CompileError, DeserializeError, ExportType, ExportsIterator, ImportType, ImportsIterator,
-    ModuleInfo, SerializeError,
+    ModuleHash, ModuleInfo, SerializeError,
 };
 
 use crate::{
@@ -175,9 +175,13 @@ impl Module {
     }
 
     pub fn serialize(&self) -> Result<Bytes, SerializeError> {
-        return self.raw_bytes.clone().ok_or(SerializeError::Generic(
-            "Not able to serialize module".to_string(),
-        ));
+        // The runtime has no serialization format of its own: the original
+        // bytes are kept, and compiled again when deserializing.
+        self.raw_bytes
+            .clone()
+            .ok_or(SerializeError::UnsupportedByRuntime(
+                "the original bytes of the module are not available".to_string(),
+            ))
     }
 
     pub unsafe fn deserialize_unchecked(
@@ -216,6 +220,15 @@ impl Module {
         true
     }
 
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.info.hash
+    }
+
+    pub fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        self.info.remap_import_modules(remapping);
+        self
+    }
+
     pub fn imports<'a>(&'a self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + 'a>> {
         self.info().imports()
     }
@@ -234,6 +247,12 @@ impl Module {
     pub(crate) fn info(&self) -> &ModuleInfo {
         &self.info
     }
+
+    pub(crate) fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        self.raw_bytes.as_deref().map_or_else(Vec::new, |bytes| {
+            crate::utils::polyfill::data_initializers(bytes).unwrap()
+        })
+    }
 }
 
 impl crate::Module {
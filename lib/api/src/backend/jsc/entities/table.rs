@@ -77,6 +77,30 @@ impl Table {
         unimplemented!();
     }
 
+    pub fn is_null_at(&self, store: &impl AsStoreRef, index: u32) -> Result<bool, RuntimeError> {
+        if index >= self.size(store) {
+            return Err(crate::entities::table::out_of_bounds(index));
+        }
+
+        let store_ref = store.as_store_ref();
+        let engine = store_ref.engine();
+        let context = engine.as_jsc().context();
+        let func = self
+            .handle
+            .table
+            .get_property(&context, "get".to_string())
+            .to_object(&context)
+            .unwrap();
+        let item = func
+            .call(
+                &context,
+                Some(&self.handle.table),
+                &[JSValue::number(&context, index as _)],
+            )
+            .map_err(|e| <JSValue as Into<RuntimeError>>::into(e))?;
+        Ok(item.is_null(&context))
+    }
+
     pub fn set(
         &self,
         store: &mut impl AsStoreMut,
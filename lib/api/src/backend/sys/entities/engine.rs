@@ -211,6 +211,23 @@ impl crate::Engine {
     pub fn is_sys(&self) -> bool {
         matches!(self.be, BackendEngine::Sys(_))
     }
+
+    /// Registers the signatures of host functions in `engine` ahead of time,
+    /// returning their indices in the same order.
+    ///
+    /// The engine is locked once for all the signatures, instead of once
+    /// per signature when creating host functions with them. Registering
+    /// an already registered signature returns its existing index.
+    ///
+    /// Panics if `engine` is not a `sys` engine.
+    pub fn register_host_function_signatures(
+        engine: &Self,
+        types: &[wasmer_types::FunctionType],
+    ) -> Vec<wasmer_vm::VMSharedSignatureIndex> {
+        let inner = engine.as_sys().inner();
+        let signatures = inner.signatures();
+        types.iter().map(|ty| signatures.register(ty)).collect()
+    }
 }
 
 impl From<Engine> for crate::Engine {
@@ -1,5 +1,5 @@
 //! Data types, functions and traits for `sys` runtime's `Tag` implementation.
-use std::any::Any;
+use std::{any::Any, sync::Arc};
 
 use wasmer_types::{TagType, Type};
 use wasmer_vm::StoreHandle;
@@ -30,6 +30,8 @@ impl Exception {
 /// A WebAssembly `extern ref` in the `sys` runtime.
 pub(crate) struct ExceptionRef {
     handle: StoreHandle<wasmer_vm::VMExceptionObj>,
+    /// The token of the object, counting the host handles to it.
+    liveness: Arc<()>,
 }
 
 impl ExceptionRef {
@@ -38,12 +40,13 @@ impl ExceptionRef {
     where
         T: Any + Send + Sync + 'static + Sized,
     {
-        Self {
-            handle: crate::backend::sys::store::StoreHandle::new(
-                store.objects_mut().as_sys_mut(),
-                wasmer_vm::VMExceptionObj::new(value),
-            ),
-        }
+        let objects = store.objects_mut().as_sys_mut();
+        let handle = crate::backend::sys::store::StoreHandle::new(
+            objects,
+            wasmer_vm::VMExceptionObj::new(value),
+        );
+        let liveness = handle.get(objects).liveness().clone();
+        Self { handle, liveness }
     }
 
     /// Try to downcast to the given value.
@@ -57,6 +60,37 @@ impl ExceptionRef {
             .downcast_ref::<T>()
     }
 
+    /// Try to mutably downcast to the given value.
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.handle
+            .get_mut(store.objects_mut().as_sys_mut())
+            .as_mut()
+            .downcast_mut::<T>()
+    }
+
+    /// Move the value out of the store, if it is a `T` and this is the
+    /// last host handle to it.
+    pub fn try_unwrap<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        // One count is held by the object itself.
+        if Arc::strong_count(&self.liveness) != 2 {
+            return Err(self);
+        }
+        match self
+            .handle
+            .get_mut(store.objects_mut().as_sys_mut())
+            .take::<T>()
+        {
+            Some(value) => Ok(value),
+            None => Err(self),
+        }
+    }
+
     /// Create a [`VMExceptionRef`] from [`Self`].
     pub(crate) fn vm_exceptionref(&self) -> VMExceptionRef {
         wasmer_vm::VMExceptionRef(self.handle.internal_handle())
@@ -67,9 +101,10 @@ impl ExceptionRef {
         store: &mut impl AsStoreMut,
         vm_exceptionref: VMExceptionRef,
     ) -> Self {
-        Self {
-            handle: StoreHandle::from_internal(store.objects_mut().id(), vm_exceptionref.0),
-        }
+        let objects = store.objects_mut().as_sys_mut();
+        let handle = StoreHandle::from_internal(objects.id(), vm_exceptionref.0);
+        let liveness = handle.get(objects).liveness().clone();
+        Self { handle, liveness }
     }
 
     /// Checks whether this `ExceptionRef` can be used with the given context.
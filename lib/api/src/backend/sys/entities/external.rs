@@ -1,15 +1,17 @@
 //! Data types, functions and traits for `sys` runtime's `ExternRef` implementation.
 
 use std::any::Any;
+use std::sync::{Arc, Weak};
 use wasmer_vm::{StoreHandle, VMExternRef};
 
 use crate::store::{AsStoreMut, AsStoreRef};
 
 #[derive(Debug, Clone)]
-#[repr(transparent)]
 /// A WebAssembly `extern ref` in the `sys` runtime.
 pub(crate) struct ExternRef {
     handle: StoreHandle<wasmer_vm::VMExternObj>,
+    /// Keeps the object from being released, see [`crate::Store::gc`].
+    liveness: Arc<()>,
 }
 
 impl ExternRef {
@@ -18,11 +20,39 @@ impl ExternRef {
     where
         T: Any + Send + Sync + 'static + Sized,
     {
-        Self {
-            handle: StoreHandle::new(
-                store.objects_mut().as_sys_mut(),
-                wasmer_vm::VMExternObj::new(value),
-            ),
+        let objects = store.objects_mut().as_sys_mut();
+        let handle = objects.insert_extern_obj(wasmer_vm::VMExternObj::new(value));
+        let liveness = handle.get(objects).liveness().clone();
+        Self { handle, liveness }
+    }
+
+    /// Make a new extern reference, calling `finalizer` with the value
+    /// when it is freed.
+    pub fn new_with_finalizer<T>(
+        store: &mut impl AsStoreMut,
+        value: T,
+        finalizer: Box<dyn FnOnce(&mut T) + Send>,
+    ) -> Self
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        let finalizer: wasmer_vm::VMExternFinalizer = Box::new(move |value| {
+            if let Some(value) = value.downcast_mut::<T>() {
+                finalizer(value)
+            }
+        });
+        let objects = store.objects_mut().as_sys_mut();
+        let handle =
+            objects.insert_extern_obj(wasmer_vm::VMExternObj::new_with_finalizer(value, finalizer));
+        let liveness = handle.get(objects).liveness().clone();
+        Self { handle, liveness }
+    }
+
+    /// Creates a weak reference to the same host object.
+    pub(crate) fn downgrade(&self) -> WeakExternRef {
+        WeakExternRef {
+            handle: self.handle.clone(),
+            liveness: Arc::downgrade(&self.liveness),
         }
     }
 
@@ -37,6 +67,49 @@ impl ExternRef {
             .downcast_ref::<T>()
     }
 
+    /// Try to downcast to the given value, mutably.
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.handle
+            .get_mut(store.objects_mut().as_sys_mut())
+            .as_mut()
+            .downcast_mut::<T>()
+    }
+
+    /// Move the value out of the store, if it is a `T` and this is the
+    /// last host handle to it.
+    pub fn take<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        // One count is held by the object itself.
+        if Arc::strong_count(&self.liveness) != 2 {
+            return Err(self);
+        }
+        match self
+            .handle
+            .get_mut(store.objects_mut().as_sys_mut())
+            .take::<T>()
+        {
+            Some(value) => Ok(value),
+            None => Err(self),
+        }
+    }
+
+    /// Returns the name of the type of the stored value.
+    pub fn type_name(&self, store: &impl AsStoreRef) -> &'static str {
+        self.handle
+            .get(store.as_store_ref().objects().as_sys())
+            .type_name()
+    }
+
+    /// Checks whether `a` and `b` refer to the same host object.
+    pub(crate) fn ptr_eq(a: &Self, b: &Self) -> bool {
+        a.handle == b.handle
+    }
+
     /// Create a [`VMExternRef`] from [`Self`].
     pub(crate) fn vm_externref(&self) -> VMExternRef {
         wasmer_vm::VMExternRef(self.handle.internal_handle())
@@ -47,9 +120,10 @@ impl ExternRef {
         store: &mut impl AsStoreMut,
         vm_externref: VMExternRef,
     ) -> Self {
-        Self {
-            handle: StoreHandle::from_internal(store.objects_mut().id(), vm_externref.0),
-        }
+        let objects = store.objects_mut().as_sys_mut();
+        let handle = StoreHandle::from_internal(objects.id(), vm_externref.0);
+        let liveness = handle.get(objects).liveness().clone();
+        Self { handle, liveness }
     }
 
     /// Checks whether this `ExternRef` can be used with the given context.
@@ -63,3 +137,26 @@ impl ExternRef {
         self.handle.store_id() == store.as_store_ref().objects().id()
     }
 }
+
+#[derive(Debug, Clone)]
+/// A weak WebAssembly `extern ref` in the `sys` runtime.
+pub(crate) struct WeakExternRef {
+    handle: StoreHandle<wasmer_vm::VMExternObj>,
+    liveness: Weak<()>,
+}
+
+impl WeakExternRef {
+    /// Returns the extern reference, if the host object wasn't released.
+    ///
+    /// Borrowing the store makes sure the object is not being released
+    /// concurrently.
+    pub fn upgrade(&self, store: &impl AsStoreRef) -> Option<ExternRef> {
+        if self.handle.store_id() != store.as_store_ref().objects().id() {
+            return None;
+        }
+        Some(ExternRef {
+            handle: self.handle.clone(),
+            liveness: self.liveness.upgrade()?,
+        })
+    }
+}
@@ -4,7 +4,7 @@ pub(crate) mod env;
 pub(crate) mod typed;
 
 use crate::{
-    backend::sys::{engine::NativeEngineExt, vm::VMFunctionCallback},
+    backend::sys::vm::VMFunctionCallback,
     entities::store::{AsStoreMut, AsStoreRef, StoreMut},
     utils::{FromToNativeWasmType, IntoResult, NativeWasmTypeInto, WasmTypeList},
     vm::{VMExtern, VMExternFunction},
@@ -17,9 +17,26 @@ use wasmer_types::{NativeWasmType, RawValue};
 use wasmer_vm::{
     on_host_stack, raise_user_trap, resume_panic, wasmer_call_trampoline, MaybeInstanceOwned,
     StoreHandle, VMCallerCheckedAnyfunc, VMContext, VMDynamicFunctionContext, VMFuncRef,
-    VMFunction, VMFunctionBody, VMFunctionContext, VMFunctionKind, VMTrampoline,
+    VMFunction, VMFunctionBody, VMFunctionContext, VMFunctionKind, VMSharedSignatureIndex,
+    VMTrampoline,
 };
 
+#[cfg(test)]
+thread_local! {
+    /// The number of host function signatures registered by this thread.
+    pub(crate) static SIGNATURE_REGISTRATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Registers the signature of a host function in `engine`.
+pub(crate) fn register_signature(
+    engine: &crate::Engine,
+    ty: &FunctionType,
+) -> VMSharedSignatureIndex {
+    #[cfg(test)]
+    SIGNATURE_REGISTRATIONS.with(|count| count.set(count.get() + 1));
+    engine.as_sys().register_signature(ty)
+}
+
 #[cfg_attr(feature = "artifact-size", derive(loupe::MemoryUsage))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// A WebAssembly `function` instance, in the `sys` runtime.
@@ -97,11 +114,7 @@ impl Function {
         // The engine linker will replace the address with one pointing to a
         // generated dynamic trampoline.
         let func_ptr = std::ptr::null() as VMFunctionCallback;
-        let type_index = store
-            .as_store_mut()
-            .engine()
-            .as_sys()
-            .register_signature(&function_type);
+        let type_index = register_signature(store.as_store_mut().engine(), &function_type);
         let vmctx = VMFunctionContext {
             host_env: host_data.as_ref() as *const _ as *mut c_void,
         };
@@ -126,6 +139,27 @@ impl Function {
 
     /// Creates a new host `Function` from a native function.
     pub(crate) fn new_typed<F, Args, Rets>(store: &mut impl AsStoreMut, func: F) -> Self
+    where
+        F: HostFunction<(), Args, Rets, WithoutEnv> + 'static + Send + Sync,
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        let function_type = FunctionType::new(Args::wasm_types(), Rets::wasm_types());
+        let type_index = register_signature(store.as_store_mut().engine(), &function_type);
+        Self::new_typed_with_signature(store, func, function_type, type_index)
+    }
+
+    /// Creates a new host `Function` from a native function whose signature
+    /// was already registered in the engine of `store`.
+    ///
+    /// This lets [`crate::HostModule`] skip the signature registration when
+    /// materializing the same functions in many stores.
+    pub(crate) fn new_typed_with_signature<F, Args, Rets>(
+        store: &mut impl AsStoreMut,
+        func: F,
+        function_type: FunctionType,
+        type_index: VMSharedSignatureIndex,
+    ) -> Self
     where
         F: HostFunction<(), Args, Rets, WithoutEnv> + 'static + Send + Sync,
         Args: WasmTypeList,
@@ -138,13 +172,6 @@ impl Function {
             env,
             func,
         });
-        let function_type = FunctionType::new(Args::wasm_types(), Rets::wasm_types());
-
-        let type_index = store
-            .as_store_mut()
-            .engine()
-            .as_sys()
-            .register_signature(&function_type);
         let vmctx = VMFunctionContext {
             host_env: host_data.as_ref() as *const _ as *mut c_void,
         };
@@ -168,6 +195,35 @@ impl Function {
         }
     }
 
+    /// Creates a new host `Function` from native code, see
+    /// [`crate::Function::new_raw`].
+    pub(crate) unsafe fn new_raw(
+        store: &mut impl AsStoreMut,
+        ty: FunctionType,
+        func_ptr: *const (),
+        trampoline: VMTrampoline,
+    ) -> Self {
+        let type_index = register_signature(store.as_store_mut().engine(), &ty);
+        let anyfunc = VMCallerCheckedAnyfunc {
+            func_ptr: func_ptr as *const VMFunctionBody,
+            type_index,
+            vmctx: VMFunctionContext {
+                host_env: std::ptr::null_mut(),
+            },
+            call_trampoline: trampoline,
+        };
+
+        let vm_function = VMFunction {
+            anyfunc: MaybeInstanceOwned::Host(Box::new(UnsafeCell::new(anyfunc))),
+            kind: VMFunctionKind::Static,
+            signature: ty,
+            host_data: Box::new(()),
+        };
+        Self {
+            handle: StoreHandle::new(store.as_store_mut().objects_mut().as_sys_mut(), vm_function),
+        }
+    }
+
     pub(crate) fn new_typed_with_env<T: Send + 'static, F, Args, Rets>(
         store: &mut impl AsStoreMut,
         env: &FunctionEnv<T>,
@@ -186,11 +242,7 @@ impl Function {
         });
         let function_type = FunctionType::new(Args::wasm_types(), Rets::wasm_types());
 
-        let type_index = store
-            .as_store_mut()
-            .engine()
-            .as_sys()
-            .register_signature(&function_type);
+        let type_index = register_signature(store.as_store_mut().engine(), &function_type);
         let vmctx = VMFunctionContext {
             host_env: host_data.as_ref() as *const _ as *mut c_void,
         };
@@ -289,7 +341,7 @@ impl Function {
             loop {
                 let storeref = store.as_store_ref();
                 let vm_function = self.handle.get(storeref.objects().as_sys());
-                let config = storeref.engine().tunables().vmconfig();
+                let config = &storeref.vmconfig();
                 r = unsafe {
                     wasmer_call_trampoline(
                         store.as_store_ref().signal_handler(),
@@ -320,7 +372,7 @@ impl Function {
             r
         };
         if let Err(error) = result {
-            return Err(error.into());
+            return Err(RuntimeError::from_trap_in_store(error, store));
         }
 
         // Load the return values out of `values_vec`.
@@ -445,7 +497,7 @@ where
 
         match result {
             Ok(Ok(())) => {}
-            Ok(Err(trap)) => raise_user_trap(Box::new(trap)),
+            Ok(Err(trap)) => raise_user_trap(crate::error::box_user_error(trap)),
             Err(panic) => resume_panic(panic),
         }
     }
@@ -536,7 +588,7 @@ macro_rules! impl_host_function {
 
                 match result {
                     Ok(Ok(result)) => return result.into_c_struct(&mut store),
-                    Ok(Err(trap)) => raise_user_trap(Box::new(trap)),
+                    Ok(Err(trap)) => raise_user_trap(crate::error::box_user_error(trap)),
                     Err(panic) => resume_panic(panic) ,
                 }
             }
@@ -603,7 +655,7 @@ macro_rules! impl_host_function {
 
   	            match result {
   	                Ok(Ok(result)) => return result.into_c_struct(&mut store),
-  	                Ok(Err(trap)) => wasmer_vm::raise_user_trap(Box::new(trap)),
+  	                Ok(Err(trap)) => wasmer_vm::raise_user_trap(crate::error::box_user_error(trap)),
   	                Err(panic) => wasmer_vm::resume_panic(panic),
   	            }
             }
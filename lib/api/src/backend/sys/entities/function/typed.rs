@@ -1,4 +1,3 @@
-use crate::backend::sys::engine::NativeEngineExt;
 use crate::store::{AsStoreMut, AsStoreRef};
 use crate::{FromToNativeWasmType, NativeWasmTypeInto, RuntimeError, TypedFunction, WasmTypeList};
 use wasmer_types::RawValue;
@@ -49,7 +48,7 @@ macro_rules! impl_native_traits {
                 let mut r;
                 loop {
                     let storeref = store.as_store_ref();
-                    let config = storeref.engine().tunables().vmconfig();
+                    let config = &storeref.vmconfig();
                     r = unsafe {
                         wasmer_vm::wasmer_call_trampoline(
                             store.as_store_ref().signal_handler(),
@@ -71,7 +70,7 @@ macro_rules! impl_native_traits {
                     }
                     break;
                 }
-                r?;
+                r.map_err(|trap| RuntimeError::from_trap_in_store(trap, store))?;
 
                 let num_rets = rets_list.len();
                 if !using_rets_array && num_rets > 0 {
@@ -131,7 +130,7 @@ macro_rules! impl_native_traits {
                 let mut r;
                 loop {
                     let storeref = store.as_store_ref();
-                    let config = storeref.engine().tunables().vmconfig();
+                    let config = &storeref.vmconfig();
                     r = unsafe {
                         wasmer_vm::wasmer_call_trampoline(
                             store.as_store_ref().signal_handler(),
@@ -154,7 +153,7 @@ macro_rules! impl_native_traits {
                     }
                     break;
                 }
-                r?;
+                r.map_err(|trap| RuntimeError::from_trap_in_store(trap, store))?;
 
                 let num_rets = rets_list.len();
                 if !using_rets_array && num_rets > 0 {
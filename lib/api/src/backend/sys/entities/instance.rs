@@ -1,10 +1,15 @@
 //! Data types, functions and traits for `sys` runtime's `Instance` implementation.
 
 use crate::{
-    error::InstantiationError, exports::Exports, imports::Imports, module::Module,
-    store::AsStoreMut, Extern,
+    error::{InstantiationError, LinkError},
+    exports::Exports,
+    imports::Imports,
+    module::Module,
+    store::{AsStoreMut, AsStoreRef},
+    Extern, Function, Memory,
 };
-use wasmer_vm::{StoreHandle, VMInstance};
+use wasmer_types::{ExportIndex, ExternType, FunctionIndex, ImportError, ImportIndex, MemoryIndex};
+use wasmer_vm::{StoreHandle, VMFunctionImport, VMFunctionKind, VMInstance};
 
 use super::store::Store;
 
@@ -12,6 +17,11 @@ use super::store::Store;
 /// A WebAssembly `instance` in the `sys` runtime.
 pub struct Instance {
     _handle: StoreHandle<VMInstance>,
+    /// The module the instance was created from, whose imports may be
+    /// renamed by [`Module::with_imports_override`].
+    module: Module,
+    /// The module's start function, resolved at instantiation.
+    start: Option<Function>,
 }
 
 #[cfg(test)]
@@ -48,52 +58,187 @@ impl Instance {
         imports: &Imports,
     ) -> Result<(Self, Exports), InstantiationError> {
         let externs = imports
-            .imports_for_module(module)
+            .resolve_for_module(store, module)
             .map_err(InstantiationError::Link)?;
-        let mut handle = module.as_sys().instantiate(store, &externs)?;
-        let exports = Self::get_exports(store, module, handle.as_sys_mut());
+        Self::new_by_index(store, module, &externs)
+    }
 
-        let instance = Self {
-            _handle: StoreHandle::new(store.objects_mut().as_sys_mut(), handle.into_sys()),
-        };
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn new_by_index(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        externs: &[Extern],
+    ) -> Result<(Self, Exports), InstantiationError> {
+        let (instance, exports) = Self::new_without_start(store, module, externs)?;
+        if let Some(start) = instance.start_function(store) {
+            start.call(store, &[]).map_err(InstantiationError::Start)?;
+        }
 
         Ok((instance, exports))
     }
 
+    /// Instantiates `module` without invoking its start function.
     #[allow(clippy::result_large_err)]
-    pub(crate) fn new_by_index(
+    pub(crate) fn new_without_start(
         store: &mut impl AsStoreMut,
         module: &Module,
         externs: &[Extern],
     ) -> Result<(Self, Exports), InstantiationError> {
-        let externs = externs.to_vec();
-        let mut handle = module.as_sys().instantiate(store, &externs)?;
+        let mut handle = module.as_sys().instantiate(store, externs, true)?;
         let exports = Self::get_exports(store, module, handle.as_sys_mut());
+        let start = Self::get_start_function(store, module, handle.as_sys_mut());
         let instance = Self {
             _handle: StoreHandle::new(
                 store.as_store_mut().objects_mut().as_sys_mut(),
                 handle.into_sys(),
             ),
+            module: module.clone(),
+            start,
         };
 
         Ok((instance, exports))
     }
 
+    pub(crate) fn start_function(&self, _store: &impl AsStoreRef) -> Option<Function> {
+        self.start.clone()
+    }
+
+    /// Returns the index of the imported function `module`.`name`, or
+    /// `None` if the module has no function import with this name.
+    ///
+    /// The names are looked up in the module's info rather than the
+    /// artifact's, so that overridden import modules resolve.
+    fn function_import_index(&self, module: &str, name: &str) -> Option<Option<FunctionIndex>> {
+        self.module
+            .info()
+            .imports
+            .iter()
+            .find(|(key, _)| key.module == module && key.field == name)
+            .map(|(_, index)| match index {
+                ImportIndex::Function(index) => Some(*index),
+                _ => None,
+            })
+    }
+
+    /// Marks the imported function `module`.`name` as bound to a
+    /// placeholder, see [`Self::link_with`].
+    pub(crate) fn mark_unlinked(&self, store: &mut impl AsStoreMut, module: &str, name: &str) {
+        if let Some(Some(index)) = self.function_import_index(module, name) {
+            let handle = self._handle.get_mut(store.objects_mut().as_sys_mut());
+            handle.mark_function_import_unlinked(index);
+        }
+    }
+
+    pub(crate) fn link_with(
+        &self,
+        store: &mut impl AsStoreMut,
+        module: &str,
+        name: &str,
+        extern_val: Extern,
+    ) -> Result<(), LinkError> {
+        let import_error = |error| LinkError::Import(module.to_string(), name.to_string(), error);
+
+        let provided = extern_val.ty(store);
+        let handle = self._handle.get(store.as_store_ref().objects().as_sys());
+        let index = match self.function_import_index(module, name) {
+            Some(Some(index)) if handle.is_function_import_unlinked(index) => index,
+            Some(_) => {
+                return Err(LinkError::AlreadyLinked(
+                    module.to_string(),
+                    name.to_string(),
+                ))
+            }
+            None => return Err(import_error(ImportError::UnknownImport(provided))),
+        };
+        let module_info = self.module.info();
+        let expected = module_info.signatures[module_info.functions[index]].clone();
+
+        let Extern::Function(function) = &extern_val else {
+            return Err(import_error(ImportError::IncompatibleType(
+                ExternType::Function(expected),
+                provided,
+            )));
+        };
+        if !function.is_from_store(store) {
+            return Err(LinkError::Resource(format!(
+                "the function linked to `{module}`.`{name}` belongs to another store"
+            )));
+        }
+        let function_handle = function.as_sys().handle.clone();
+        let vm_function = function_handle.get(store.as_store_ref().objects().as_sys());
+        if vm_function.signature != expected {
+            return Err(import_error(ImportError::IncompatibleType(
+                ExternType::Function(expected),
+                provided,
+            )));
+        }
+        // Dynamic host functions are only callable from Wasm through a
+        // trampoline generated when instantiating a module that imports them.
+        if vm_function.kind == VMFunctionKind::Dynamic {
+            return Err(LinkError::Resource(format!(
+                "dynamic host functions can't be linked after instantiation, \
+                 `{module}`.`{name}` must be a typed function"
+            )));
+        }
+        let anyfunc_ptr = vm_function.anyfunc.as_ptr();
+        let anyfunc = unsafe { anyfunc_ptr.as_ref() };
+        let import = VMFunctionImport {
+            body: anyfunc.func_ptr,
+            environment: anyfunc.vmctx,
+            handle: function_handle.internal_handle(),
+        };
+
+        let handle = self._handle.get_mut(store.objects_mut().as_sys_mut());
+        // Safety: the signature was checked above and the function belongs
+        // to the store of this instance.
+        unsafe { handle.link_function_import(index, import, anyfunc_ptr) };
+        Ok(())
+    }
+
+    pub(crate) fn memory(&self, store: &mut impl AsStoreMut, index: MemoryIndex) -> Option<Memory> {
+        let handle = self._handle.get_mut(store.objects_mut().as_sys_mut());
+        if index.as_u32() as usize >= handle.module_ref().memories.len() {
+            return None;
+        }
+        let export = handle.lookup_by_declaration(ExportIndex::Memory(index));
+        match Extern::from_vm_extern(store, crate::vm::VMExtern::Sys(export)) {
+            Extern::Memory(memory) => Some(memory),
+            _ => None,
+        }
+    }
+
     fn get_exports(
         store: &mut impl AsStoreMut,
         module: &Module,
         handle: &mut VMInstance,
     ) -> Exports {
+        // Walk the module's own export index instead of going through
+        // `Module::exports`, which would compute every export type and look
+        // each name up again.
         module
-            .exports()
-            .map(|export| {
-                let name = export.name().to_string();
-                let export = handle.lookup(&name).expect("export");
+            .info()
+            .exports
+            .iter()
+            .map(|(name, index)| {
+                let export = handle.lookup_by_declaration(*index);
                 let extern_ = Extern::from_vm_extern(store, crate::vm::VMExtern::Sys(export));
-                (name, extern_)
+                (name.clone(), extern_)
             })
             .collect::<Exports>()
     }
+
+    fn get_start_function(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        handle: &mut VMInstance,
+    ) -> Option<Function> {
+        let start = module.info().start_function?;
+        let export = handle.lookup_by_declaration(ExportIndex::Function(start));
+        match Extern::from_vm_extern(store, crate::vm::VMExtern::Sys(export)) {
+            Extern::Function(function) => Some(function),
+            _ => None,
+        }
+    }
 }
 
 impl crate::BackendInstance {
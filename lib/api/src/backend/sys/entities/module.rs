@@ -1,12 +1,14 @@
 //! Data types, functions and traits for `sys` runtime's `Module` implementation.
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 
 use bytes::Bytes;
 use wasmer_compiler::{Artifact, ArtifactCreate, Engine};
 use wasmer_types::{
-    CompileError, DeserializeError, ExportType, ExportsIterator, ImportType, ImportsIterator,
-    ModuleInfo, SerializeError,
+    CompileError, DataInitializer, DataInitializerLike, DataInitializerLocation,
+    DataInitializerLocationLike, DeserializeError, ExportType, ExportsIterator, ImportType,
+    ImportsIterator, ModuleHash, ModuleInfo, SerializeError,
 };
 
 use crate::{
@@ -33,6 +35,12 @@ pub struct Module {
     // In the future, this code should be refactored to properly describe the
     // ownership of the code and its metadata.
     artifact: Arc<Artifact>,
+    // A patched copy of the artifact's module info, set when the module
+    // info was changed after compilation (e.g. by renaming imports).
+    info_override: Option<Arc<ModuleInfo>>,
+    // The hash of the Wasm binary, recorded in the artifact when the engine
+    // has a hash algorithm, or computed when compiling otherwise.
+    hash: Option<ModuleHash>,
 }
 
 impl Module {
@@ -60,7 +68,11 @@ impl Module {
     #[cfg(feature = "compiler")]
     fn compile(engine: &impl AsEngineRef, binary: &[u8]) -> Result<Self, CompileError> {
         let artifact = engine.as_engine_ref().engine().as_sys().compile(binary)?;
-        Ok(Self::from_artifact(artifact))
+        let mut module = Self::from_artifact(artifact);
+        module
+            .hash
+            .get_or_insert_with(|| ModuleHash::xxhash(binary));
+        Ok(module)
     }
 
     #[cfg(not(feature = "compiler"))]
@@ -127,14 +139,21 @@ impl Module {
     }
 
     pub(super) fn from_artifact(artifact: Arc<Artifact>) -> Self {
-        Self { artifact }
+        Self {
+            hash: artifact.module_info().hash,
+            artifact,
+            info_override: None,
+        }
     }
 
+    /// Instantiates the module; with `defer_start`, the start function is
+    /// not invoked and is left to the caller.
     #[allow(clippy::result_large_err)]
     pub(crate) fn instantiate(
         &self,
         store: &mut impl AsStoreMut,
         imports: &[crate::Extern],
+        defer_start: bool,
     ) -> Result<VMInstance, InstantiationError> {
         if !self.artifact.allocated() {
             // Return an error mentioning that the artifact is compiled for a different
@@ -148,9 +167,9 @@ impl Module {
             }
         }
         let signal_handler = store.as_store_ref().signal_handler();
+        let config = store.as_store_ref().vmconfig();
         let mut store_mut = store.as_store_mut();
         let (engine, objects) = store_mut.engine_and_objects_mut();
-        let config = engine.tunables().vmconfig();
         unsafe {
             let mut instance_handle = self.artifact.instantiate(
                 engine.tunables(),
@@ -160,6 +179,9 @@ impl Module {
                     .collect::<Vec<_>>(),
                 objects.as_sys_mut(),
             )?;
+            if defer_start {
+                instance_handle.defer_start_function();
+            }
 
             // After the instance handle is created, we need to initialize
             // the data, call the start function and so. However, if any
@@ -167,7 +189,7 @@ impl Module {
             // as some of the Instance elements may have placed in other
             // instance tables.
             self.artifact
-                .finish_instantiation(config, signal_handler, &mut instance_handle)?;
+                .finish_instantiation(&config, signal_handler, &mut instance_handle)?;
 
             Ok(VMInstance::Sys(instance_handle))
         }
@@ -178,9 +200,27 @@ impl Module {
     }
 
     pub(crate) fn set_name(&mut self, name: &str) -> bool {
-        Arc::get_mut(&mut self.artifact).map_or(false, |artifact| {
+        let renamed = Arc::get_mut(&mut self.artifact).map_or(false, |artifact| {
             artifact.set_module_info_name(name.to_string())
-        })
+        });
+        if renamed {
+            if let Some(info) = self.info_override.as_mut() {
+                Arc::make_mut(info).name = Some(name.to_string());
+            }
+        }
+        renamed
+    }
+
+    pub(crate) fn hash(&self) -> Option<ModuleHash> {
+        self.hash
+    }
+
+    pub(crate) fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        let info = self
+            .info_override
+            .get_or_insert_with(|| Arc::new(self.artifact.module_info().clone()));
+        Arc::make_mut(info).remap_import_modules(remapping);
+        self
     }
 
     pub(crate) fn imports(&self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + '_>> {
@@ -198,8 +238,28 @@ impl Module {
         self.info().custom_sections(name)
     }
 
+    pub(crate) fn data_initializers(&self) -> Vec<DataInitializer<'_>> {
+        self.artifact
+            .data_initializers()
+            .map(|init| {
+                let location = init.location();
+                DataInitializer {
+                    location: DataInitializerLocation {
+                        memory_index: location.memory_index(),
+                        base: location.base(),
+                        offset: location.offset(),
+                    },
+                    data: init.data(),
+                }
+            })
+            .collect()
+    }
+
     pub(crate) fn info(&self) -> &ModuleInfo {
-        self.artifact.module_info()
+        match &self.info_override {
+            Some(info) => info,
+            None => self.artifact.module_info(),
+        }
     }
 }
 
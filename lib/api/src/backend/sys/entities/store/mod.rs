@@ -2,8 +2,8 @@
 use crate::entities::engine::{AsEngineRef, Engine, EngineRef};
 use crate::BackendStore;
 use wasmer_vm::init_traps;
-use wasmer_vm::TrapHandlerFn;
 pub use wasmer_vm::{StoreHandle, StoreObjects};
+use wasmer_vm::{TrapHandlerFn, VMConfig};
 
 mod obj;
 pub use obj::*;
@@ -12,6 +12,8 @@ pub use obj::*;
 pub struct Store {
     pub(crate) engine: Engine,
     pub(crate) trap_handler: Option<Box<TrapHandlerFn<'static>>>,
+    /// Overrides the stack size of the engine's tunables.
+    pub(crate) wasm_stack_size: Option<usize>,
 }
 
 impl std::fmt::Debug for Store {
@@ -29,6 +31,7 @@ impl Store {
         Self {
             engine,
             trap_handler: None,
+            wasm_stack_size: None,
         }
     }
 
@@ -41,6 +44,25 @@ impl Store {
     }
 }
 
+impl crate::StoreRef<'_> {
+    /// Returns the size of the stack Wasm code of this store runs on.
+    pub(crate) fn wasm_stack_size(&self) -> usize {
+        use crate::backend::sys::engine::NativeEngineExt;
+        let store = self.inner.store.as_sys();
+        store
+            .wasm_stack_size
+            .or(store.engine.tunables().vmconfig().wasm_stack_size)
+            .unwrap_or_else(wasmer_vm::stack_size)
+    }
+
+    /// Returns the configuration to run Wasm code of this store with.
+    pub(crate) fn vmconfig(&self) -> VMConfig {
+        VMConfig {
+            wasm_stack_size: Some(self.wasm_stack_size()),
+        }
+    }
+}
+
 impl AsEngineRef for Store {
     fn as_engine_ref(&self) -> EngineRef<'_> {
         EngineRef::new(&self.engine)
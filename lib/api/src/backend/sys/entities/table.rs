@@ -97,6 +97,17 @@ impl Table {
         Some(value_from_table_element(store, item))
     }
 
+    pub(crate) fn is_null_at(
+        &self,
+        store: &impl AsStoreRef,
+        index: u32,
+    ) -> Result<bool, RuntimeError> {
+        self.handle
+            .get(store.as_store_ref().objects().as_sys())
+            .is_null(index)
+            .ok_or_else(|| crate::entities::table::out_of_bounds(index))
+    }
+
     pub(crate) fn set(
         &self,
         store: &mut impl AsStoreMut,
@@ -1,4 +1,5 @@
-use crate::LinkError;
+use crate::{AsStoreRef, LinkError};
+use wasmer_types::TrapCode;
 use wasmer_vm::Trap;
 
 impl From<wasmer_compiler::LinkError> for LinkError {
@@ -22,3 +23,22 @@ impl From<Trap> for crate::RuntimeError {
         Self::new_from_source(crate::BackendTrap::Sys(trap), wasm_trace, trap_code)
     }
 }
+
+impl crate::RuntimeError {
+    /// Converts a trap caught while running code of `store`, locating the
+    /// out-of-bounds memory accesses in the memories of the store and
+    /// recording the stack size of the store for stack overflows.
+    pub(crate) fn from_trap_in_store(trap: Trap, store: &impl AsStoreRef) -> Self {
+        let offset = trap.fault_address().and_then(|address| {
+            store
+                .as_store_ref()
+                .objects()
+                .as_sys()
+                .memory_offset_of(address)
+        });
+        let error = Self::from(trap).with_memory_offset(offset);
+        let stack_size = (error.inner.trap_code == Some(TrapCode::StackOverflow))
+            .then(|| store.as_store_ref().wasm_stack_size());
+        error.with_stack_size(stack_size)
+    }
+}
@@ -60,6 +60,26 @@ impl<'a, F> std::fmt::Debug for FunctionCallbackEnv<'a, F> {
     }
 }
 
+/// Moves the environment of a host function callback to the heap, to be
+/// dropped with the store of the function.
+fn register_callback_env<F>(env: FunctionCallbackEnv<'_, F>) -> *mut c_void {
+    unsafe fn drop_env<F>(env: *mut c_void) {
+        drop(Box::from_raw(env as *mut FunctionCallbackEnv<'static, F>));
+    }
+
+    let env = Box::into_raw(Box::new(env));
+    unsafe {
+        (*env)
+            .store
+            .inner
+            .store
+            .as_v8_mut()
+            .callback_envs
+            .push((env as *mut c_void, drop_env::<F>));
+    }
+    env as *mut c_void
+}
+
 impl Function {
     /// To `VMExtern`.
     pub fn to_vm_extern(&self) -> VMExtern {
@@ -131,21 +151,14 @@ impl Function {
 
         let callback: CCallback = make_fn_callback(&func, param_types.len());
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::leak(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: Some(env.as_v8().handle.clone()),
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: Some(env.as_v8().handle.clone()),
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as *mut _ as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -203,21 +216,14 @@ impl Function {
             std::mem::transmute(func.function_callback(crate::BackendKind::V8).into_v8())
         };
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::into_raw(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: None,
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: None,
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -283,21 +289,14 @@ impl Function {
             std::mem::transmute(func.function_callback(crate::BackendKind::V8).into_v8())
         };
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::into_raw(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: Some(env.as_v8().handle.clone()),
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: Some(env.as_v8().handle.clone()),
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -493,7 +492,7 @@ where
             }
 
             Ok(Err(e)) => {
-                let trap: Trap = Trap::user(Box::new(e));
+                let trap: Trap = Trap::user(crate::error::box_user_error(e));
                 unsafe { trap.into_wasm_trap(&mut store) }
             }
 
@@ -600,7 +599,7 @@ macro_rules! impl_host_function {
                     },
 
                     Ok(Err(e)) => {
-                        let trap =  crate::backend::v8::error::Trap::user(Box::new(e));
+                        let trap =  crate::backend::v8::error::Trap::user(crate::error::box_user_error(e));
                         unsafe { trap.into_wasm_trap(store) }
                         // unimplemented!("host function panicked");
                     },
@@ -674,7 +673,7 @@ macro_rules! impl_host_function {
 	          	  unsafe { std::ptr::null_mut() }
 	            },
 
-	            Ok(Err(e)) => { let trap = crate::backend::v8::error::Trap::user(Box::new(e)); unsafe { trap.into_wasm_trap(store) } },
+	            Ok(Err(e)) => { let trap = crate::backend::v8::error::Trap::user(crate::error::box_user_error(e)); unsafe { trap.into_wasm_trap(store) } },
 
 	            Err(e) => { unimplemented!("host function panicked"); }
 	          }
@@ -98,14 +98,9 @@ impl Instance {
         check_isolate(store);
         let mut store = store.as_store_mut();
 
-        let externs = module
-            .imports()
-            .map(|import_ty| {
-                imports
-                    .get_export(import_ty.module(), import_ty.name())
-                    .expect(format!("Export {import_ty:?} not found").as_str())
-            })
-            .collect::<Vec<_>>();
+        let externs = imports
+            .resolve_for_module(&store, module)
+            .map_err(InstantiationError::Link)?;
 
         return Self::new_by_index(&mut store, module, &externs);
     }
@@ -1,5 +1,5 @@
 //! Data types, functions and traits for `v8` runtime's `Module` implementation.
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use crate::{
     backend::v8::bindings::*, v8::utils::convert::IntoWasmerExternType, AsEngineRef, BackendModule,
@@ -9,7 +9,7 @@ use crate::{
 use bytes::Bytes;
 use wasmer_types::{
     CompileError, DeserializeError, ExportType, ExportsIterator, ExternType, FunctionType,
-    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleInfo, Mutability, Pages,
+    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleHash, ModuleInfo, Mutability, Pages,
     SerializeError, TableType, Type,
 };
 
@@ -111,17 +111,17 @@ impl ModuleHandle {
             data: std::ptr::null_mut(),
         };
 
-        let bytes = unsafe {
+        unsafe {
             wasm_module_serialize(handle, &mut bytes as *mut _);
             if bytes.data.is_null() || bytes.size == 0 {
                 return Err(SerializeError::Generic(String::from(
                     "V8 returned an empty vector as serialized module",
                 )));
             }
-            std::slice::from_raw_parts(bytes.data as *mut u8, bytes.size)
-        };
-
-        Ok(bytes.to_vec())
+            let data = std::slice::from_raw_parts(bytes.data as *mut u8, bytes.size).to_vec();
+            wasm_byte_vec_delete(&mut bytes as *mut _);
+            Ok(data)
+        }
     }
 }
 
@@ -136,6 +136,10 @@ impl Drop for ModuleHandle {
 pub struct Module {
     pub(crate) handle: Arc<ModuleHandle>,
     name: Option<String>,
+    /// The hash of the Wasm binary, unknown for deserialized modules.
+    hash: Option<ModuleHash>,
+    /// The renamings of import modules, in the order they were applied.
+    import_remappings: Vec<HashMap<String, String>>,
 }
 
 unsafe impl Send for Module {}
@@ -167,6 +171,8 @@ impl Module {
         Ok(Self {
             handle: Arc::new(module),
             name: info.name,
+            hash: info.hash,
+            import_remappings: vec![],
         })
     }
 
@@ -218,16 +224,26 @@ impl Module {
     ) -> Result<Self, DeserializeError> {
         tracing::info!("Creating module from deserialize_unchecked");
         let binary = bytes.into_bytes();
-        let off = &binary[0..8];
-        let off = usize::from_ne_bytes(off.try_into().unwrap());
-        let name_bytes = &binary[8..(8 + off)];
-        let name = String::from_utf8_lossy(name_bytes).to_string();
-        let mod_bytes = &binary[off..];
-        let module = ModuleHandle::deserialize(engine, mod_bytes)?;
+        // The name of the module, prefixed with its length, comes first.
+        const LEN_SIZE: usize = std::mem::size_of::<usize>();
+        let name_len = binary
+            .get(..LEN_SIZE)
+            .map(|len| usize::from_ne_bytes(len.try_into().unwrap()))
+            .ok_or_else(|| DeserializeError::CorruptedBinary("missing module name".to_string()))?;
+        let name_end = LEN_SIZE
+            .checked_add(name_len)
+            .filter(|end| *end <= binary.len())
+            .ok_or_else(|| {
+                DeserializeError::CorruptedBinary("truncated module name".to_string())
+            })?;
+        let name = String::from_utf8_lossy(&binary[LEN_SIZE..name_end]).to_string();
+        let module = ModuleHandle::deserialize(engine, &binary[name_end..])?;
 
         Ok(Self {
             handle: Arc::new(module),
-            name: Some(name),
+            name: (!name.is_empty()).then_some(name),
+            hash: None,
+            import_remappings: vec![],
         })
     }
 
@@ -259,6 +275,26 @@ impl Module {
         true
     }
 
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.hash
+    }
+
+    pub fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        self.import_remappings.push(remapping.clone());
+        self
+    }
+
+    /// Returns the name `module` is imported under, once renamed by
+    /// [`Module::with_imports_override`].
+    fn import_module(&self, mut module: String) -> String {
+        for remapping in &self.import_remappings {
+            if let Some(renamed) = remapping.get(&module) {
+                module.clone_from(renamed);
+            }
+        }
+        module
+    }
+
     pub fn imports<'a>(&'a self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + 'a>> {
         let mut imports = wasm_importtype_vec_t {
             size: 0,
@@ -289,7 +325,7 @@ impl Module {
                 let module = wasm_importtype_module(i as *const _);
                 let module =
                     std::slice::from_raw_parts((*module).data as *const u8, (*module).size);
-                let module_str = String::from_utf8_lossy(module).to_string();
+                let module_str = self.import_module(String::from_utf8_lossy(module).to_string());
 
                 let ty = IntoWasmerExternType::into_wextt(wasm_importtype_type(i as *const _));
                 if ty.is_err() {
@@ -358,6 +394,11 @@ impl Module {
     pub(crate) fn info(&self) -> &ModuleInfo {
         panic!("no info for V8 modules")
     }
+
+    pub(crate) fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        // The binary isn't kept around, as for `info`.
+        Vec::new()
+    }
 }
 
 impl crate::Module {
@@ -1,5 +1,5 @@
 //! Data types, functions and traits for `v8` runtime's `Store` implementation.
-use std::thread::ThreadId;
+use std::{ffi::c_void, thread::ThreadId};
 
 use crate::{
     backend::v8::bindings::{wasm_store_delete, wasm_store_new, wasm_store_t},
@@ -11,10 +11,21 @@ mod obj;
 pub use obj::*;
 
 /// A WebAssembly `store` in the `v8` runtime.
+///
+/// Every store owns a V8 isolate, created by `wasm_store_new` and entered
+/// on the thread creating the store: the heaps, the out-of-memory
+/// conditions and the execution of the guests of two stores are
+/// independent, and stores created on different threads run concurrently.
+/// The isolate is never exited, so the store must stay on the thread that
+/// created it; each isolate also reserves its own heap, costing a few
+/// megabytes per store.
 pub struct Store {
     pub(crate) engine: Engine,
     pub(crate) inner: *mut wasm_store_t,
     pub(crate) thread_id: ThreadId,
+    /// The environments of the host function callbacks of this store, with
+    /// the function dropping each of them.
+    pub(crate) callback_envs: Vec<(*mut c_void, unsafe fn(*mut c_void))>,
 }
 
 impl std::fmt::Debug for Store {
@@ -33,6 +44,7 @@ impl Store {
             inner,
             engine,
             thread_id,
+            callback_envs: Vec::new(),
         }
     }
 
@@ -47,7 +59,14 @@ impl Store {
 
 impl Drop for Store {
     fn drop(&mut self) {
-        unsafe { wasm_store_delete(self.inner) }
+        unsafe {
+            // The environments may own the last handles to objects of the
+            // store, which must be deleted before the store itself.
+            for (env, drop_env) in self.callback_envs.drain(..) {
+                drop_env(env);
+            }
+            wasm_store_delete(self.inner);
+        }
     }
 }
 
@@ -1,6 +1,7 @@
 use std::{any::Any, fmt::Debug, marker::PhantomData};
 
 use crate::{
+    entities::function::env::{take_function_env, TAKEN_FUNCTION_ENV},
     store::{AsStoreMut, AsStoreRef, StoreRef},
     wamr::{store::StoreHandle, vm::VMFunctionEnvironment},
     StoreMut,
@@ -39,7 +40,7 @@ impl<T> FunctionEnv<T> {
             .get(store.as_store_ref().objects().as_wamr())
             .as_ref()
             .downcast_ref::<T>()
-            .unwrap()
+            .expect(TAKEN_FUNCTION_ENV)
     }
 
     pub(crate) fn from_handle(handle: StoreHandle<VMFunctionEnvironment>) -> Self {
@@ -58,7 +59,32 @@ impl<T> FunctionEnv<T> {
             .get_mut(store.objects_mut().as_wamr_mut())
             .as_mut()
             .downcast_mut::<T>()
-            .unwrap()
+            .expect(TAKEN_FUNCTION_ENV)
+    }
+
+    /// Checks whether this environment was created in `store`.
+    pub fn is_from_store(&self, store: &impl AsStoreRef) -> bool {
+        self.handle.store_id() == store.as_store_ref().objects().id()
+    }
+
+    /// Moves the data out of the store, leaving later accesses to panic.
+    ///
+    /// Returns `None` if it was already moved out.
+    pub fn into_inner(self, store: &mut impl AsStoreMut) -> Option<T>
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        let env = self.handle.get_mut(store.objects_mut().as_wamr_mut());
+        take_function_env(&mut env.contents)
+    }
+
+    /// Puts back data moved out with [`FunctionEnv::into_inner`].
+    pub(crate) fn restore(&self, store: &mut impl AsStoreMut, value: T)
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        let env = self.handle.get_mut(store.objects_mut().as_wamr_mut());
+        env.contents = Box::new(value);
     }
 
     /// Convert it into a `FunctionEnvMut`
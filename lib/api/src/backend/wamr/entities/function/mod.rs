@@ -61,6 +61,52 @@ impl<'a, F> std::fmt::Debug for FunctionCallbackEnv<'a, F> {
     }
 }
 
+/// Returns the `wasm_functype_t` of `ty` in `store`.
+///
+/// WAMR copies the type of every function it creates, so the stores keep a
+/// single one per signature, deleted along with them.
+fn functype(store: &mut StoreMut<'_>, ty: &FunctionType) -> *mut wasm_functype_t {
+    let functypes = &mut store.inner.store.as_wamr_mut().functypes;
+    if let Some(functype) = functypes.get(ty) {
+        return *functype;
+    }
+
+    let valtypes = |types: &[wasmer_types::Type]| {
+        let types = types
+            .iter()
+            .map(|ty| unsafe { wasm_valtype_new(ty.into_ct()) })
+            .collect::<Vec<_>>();
+        let mut vec = wasm_valtype_vec_t::default();
+        unsafe { wasm_valtype_vec_new(&mut vec, types.len(), types.as_ptr()) };
+        vec
+    };
+    let mut params = valtypes(ty.params());
+    let mut results = valtypes(ty.results());
+    let functype = unsafe { wasm_functype_new(&mut params, &mut results) };
+    functypes.insert(ty.clone(), functype);
+    functype
+}
+
+/// Moves the environment of a host function callback to the heap, to be
+/// dropped with the store of the function.
+fn register_callback_env<F>(env: FunctionCallbackEnv<'_, F>) -> *mut c_void {
+    unsafe fn drop_env<F>(env: *mut c_void) {
+        drop(Box::from_raw(env as *mut FunctionCallbackEnv<'static, F>));
+    }
+
+    let env = Box::into_raw(Box::new(env));
+    unsafe {
+        (*env)
+            .store
+            .inner
+            .store
+            .as_wamr_mut()
+            .callback_envs
+            .push((env as *mut c_void, drop_env::<F>));
+    }
+    env as *mut c_void
+}
+
 impl Function {
     /// To `VMExtern`.
     pub fn to_vm_extern(&self) -> VMExtern {
@@ -87,64 +133,21 @@ impl Function {
             + Sync,
     {
         let fn_ty: FunctionType = ty.into();
-        let params = fn_ty.params();
-
-        let mut param_types = params
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_param_types = unsafe {
-            let mut vec = Default::default();
-            wasm_valtype_vec_new(&mut vec, param_types.len(), param_types.as_ptr());
-            vec
-        };
-
-        let results = fn_ty.results();
-        let mut result_types = results
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_result_types = unsafe {
-            let mut vec = Default::default();
-            wasm_valtype_vec_new(&mut vec, result_types.len(), result_types.as_ptr());
-            vec
-        };
-
-        let wasm_functype = unsafe {
-            wasm_functype_new(
-                &mut wasm_param_types as *mut _,
-                &mut wasm_result_types as *mut _,
-            )
-        };
 
         let mut store = store.as_store_mut();
+        let wasm_functype = functype(&mut store, &fn_ty);
         let inner = store.inner.store.as_wamr().inner;
 
-        let callback: CCallback = make_fn_callback(&func, param_types.len());
+        let callback: CCallback = make_fn_callback(&func, fn_ty.params().len());
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::leak(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: Some(env.as_wamr().handle.clone()),
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: Some(env.as_wamr().handle.clone()),
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as *mut _ as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -163,59 +166,24 @@ impl Function {
         Args: WasmTypeList,
         Rets: WasmTypeList,
     {
-        let mut param_types = Args::wasm_types()
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_param_types = unsafe {
-            let mut vec = Default::default();
-            wasm_valtype_vec_new(&mut vec, param_types.len(), param_types.as_ptr());
-            vec
-        };
-
-        let mut result_types = Rets::wasm_types()
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_result_types = unsafe {
-            let mut vec = Default::default();
-            wasm_valtype_vec_new(&mut vec, result_types.len(), result_types.as_ptr());
-            vec
-        };
-
-        let wasm_functype =
-            unsafe { wasm_functype_new(&mut wasm_param_types, &mut wasm_result_types) };
+        let fn_ty = FunctionType::new(Args::wasm_types(), Rets::wasm_types());
 
         let mut store = store.as_store_mut();
+        let wasm_functype = functype(&mut store, &fn_ty);
         let inner = store.inner.store.as_wamr().inner;
 
         let callback: CCallback = unsafe {
             std::mem::transmute(func.function_callback(crate::BackendKind::Wamr).into_wamr())
         };
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::into_raw(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: None,
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: None,
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -238,63 +206,24 @@ impl Function {
         Rets: WasmTypeList,
         T: Send + 'static,
     {
-        let mut param_types = Args::wasm_types()
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_param_types = unsafe {
-            let mut vec = wasm_valtype_vec_t::default();
-            wasm_valtype_vec_new(&mut vec, param_types.len(), param_types.as_ptr());
-            vec
-        };
-
-        let mut result_types = Rets::wasm_types()
-            .into_iter()
-            .map(|param| {
-                let kind = param.into_ct();
-                unsafe { wasm_valtype_new(kind) }
-            })
-            .collect::<Vec<_>>();
-
-        let mut wasm_result_types = unsafe {
-            let mut vec: wasm_valtype_vec_t = Default::default();
-            wasm_valtype_vec_new(&mut vec, result_types.len(), result_types.as_ptr());
-            vec
-        };
-
-        let wasm_functype = unsafe {
-            wasm_functype_new(
-                &mut wasm_param_types as *mut _,
-                &mut wasm_result_types as *mut _,
-            )
-        };
+        let fn_ty = FunctionType::new(Args::wasm_types(), Rets::wasm_types());
 
         let mut store = store.as_store_mut();
+        let wasm_functype = functype(&mut store, &fn_ty);
         let inner = store.inner.store.as_wamr().inner;
 
         let callback: CCallback = unsafe {
             std::mem::transmute(func.function_callback(crate::BackendKind::Wamr).into_wamr())
         };
 
-        let mut callback_env: *mut FunctionCallbackEnv<'_, F> =
-            Box::into_raw(Box::new(FunctionCallbackEnv {
-                store,
-                func,
-                env_handle: Some(env.as_wamr().handle.clone()),
-            }));
+        let callback_env = register_callback_env(FunctionCallbackEnv {
+            store,
+            func,
+            env_handle: Some(env.as_wamr().handle.clone()),
+        });
 
         let wasm_function = unsafe {
-            wasm_func_new_with_env(
-                inner,
-                wasm_functype,
-                Some(callback),
-                callback_env as _,
-                None,
-            )
+            wasm_func_new_with_env(inner, wasm_functype, Some(callback), callback_env, None)
         };
 
         if wasm_function.is_null() {
@@ -488,7 +417,7 @@ where
             }
 
             Ok(Err(e)) => {
-                let trap: Trap = Trap::user(Box::new(e));
+                let trap: Trap = Trap::user(crate::error::box_user_error(e));
                 unsafe { trap.into_wasm_trap(&mut store) }
             }
 
@@ -595,7 +524,7 @@ macro_rules! impl_host_function {
                     },
 
                     Ok(Err(e)) => {
-                        let trap =  crate::backend::wamr::error::Trap::user(Box::new(e));
+                        let trap =  crate::backend::wamr::error::Trap::user(crate::error::box_user_error(e));
                         unsafe { trap.into_wasm_trap(store) }
                         // unimplemented!("host function panicked");
                     },
@@ -669,7 +598,7 @@ macro_rules! impl_host_function {
 	          	  unsafe { std::ptr::null_mut() }
 	            },
 
-	            Ok(Err(e)) => { let trap = crate::backend::wamr::error::Trap::user(Box::new(e)); unsafe { trap.into_wasm_trap(store) } },
+	            Ok(Err(e)) => { let trap = crate::backend::wamr::error::Trap::user(crate::error::box_user_error(e)); unsafe { trap.into_wasm_trap(store) } },
 
 	            Err(e) => { unimplemented!("host function panicked"); }
 	          }
@@ -3,9 +3,42 @@ use std::sync::Arc;
 
 use crate::{
     backend::wamr::bindings::*, vm::VMExtern, wamr::error::Trap, AsStoreMut, AsStoreRef, Exports,
-    Extern, Imports, InstantiationError, Module,
+    Extern, ExternType, Imports, InstantiationError, Module, RuntimeError,
 };
 
+/// Sizes of the memory areas `wamr` allocates for each instance.
+///
+/// The default of a store can be changed with
+/// [`Store::set_wamr_instance_config`](crate::Store::set_wamr_instance_config),
+/// and the one of a single instance given to
+/// [`Instance::new_wamr_with_config`](crate::Instance::new_wamr_with_config).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InstanceConfig {
+    /// Size in bytes of the stack the code of the instance runs on, which
+    /// bounds how deep it can recurse.
+    pub stack_size: u32,
+    /// Size in bytes of the heap WAMR reserves in the linear memory of the
+    /// instance for host allocations.
+    ///
+    /// WAMR adds the pages of this heap to the memory of modules which
+    /// don't export their own allocator, past its declared size and
+    /// maximum. Set it to 0 for such memories to behave like on the other
+    /// backends.
+    pub heap_size: u32,
+}
+
+impl Default for InstanceConfig {
+    fn default() -> Self {
+        Self {
+            stack_size: 2 * 1024 * 1024,
+            heap_size: 2 * 1024 * 1024,
+        }
+    }
+}
+
+/// The message of WAMR when it can't allocate the memory of an instance.
+const ALLOCATION_FAILED: &str = "allocate memory failed";
+
 #[derive(PartialEq, Eq)]
 pub(crate) struct InstanceHandle(pub(crate) *mut wasm_instance_t);
 
@@ -17,6 +50,7 @@ impl InstanceHandle {
         store: *mut wasm_store_t,
         module: *mut wasm_module_t,
         mut externs: Vec<VMExtern>,
+        config: InstanceConfig,
     ) -> Result<Self, InstantiationError> {
         // Check if the thread env was already initialised.
         //unsafe {
@@ -39,22 +73,34 @@ impl InstanceHandle {
 
             std::mem::forget(externs);
 
-            let stack_size = 2 * 1024 * 1024;
-            let heap_size = 2 * 1024 * 1024;
-
             wasm_instance_new_with_args(
                 store,
                 module,
                 &mut imports,
                 &mut trap,
-                stack_size,
-                heap_size,
+                config.stack_size,
+                config.heap_size,
             )
         };
 
         if instance.is_null() {
-            let trap = Trap::from(trap);
-            return Err(InstantiationError::Start(trap.into()));
+            if trap.is_null() {
+                return Err(InstantiationError::InsufficientResources(
+                    "wamr could not instantiate the module".to_string(),
+                ));
+            }
+            let error: RuntimeError = Trap::from(trap).into();
+            let message = error.message();
+            // WAMR reports allocation failures of the instance the same way
+            // as start function traps, and its C API has no error code for
+            // them: only the exact message of its allocator, after the
+            // prefixes of the instantiation, is recognized.
+            // `wamr_insufficient_resources` pins it.
+            let reason = message.rsplit(": ").next().unwrap_or(&message);
+            if reason == ALLOCATION_FAILED {
+                return Err(InstantiationError::InsufficientResources(message));
+            }
+            return Err(InstantiationError::Start(error));
         }
 
         Ok(InstanceHandle(instance))
@@ -81,6 +127,15 @@ impl InstanceHandle {
                 // Annotation is here to prevent spurious IDE warnings.
 
                 let extern_ = Extern::from_vm_extern(&mut store, VMExtern::Wamr(*wasm_export));
+                if let (Extern::Memory(memory), ExternType::Memory(ty)) = (&extern_, extern_type) {
+                    let handle = memory.as_wamr().handle;
+                    store
+                        .inner
+                        .store
+                        .as_wamr_mut()
+                        .memory_types
+                        .insert(handle, *ty);
+                }
                 (name.to_string(), extern_)
             })
             .collect::<Exports>();
@@ -105,14 +160,19 @@ impl Instance {
         module: &Module,
         imports: &Imports,
     ) -> Result<(Self, Exports), InstantiationError> {
-        let externs = module
-            .imports()
-            .map(|import_ty| {
-                imports
-                    .get_export(import_ty.module(), import_ty.name())
-                    .expect("Extern not found")
-            })
-            .collect::<Vec<_>>();
+        let config = store.as_store_ref().inner.store.as_wamr().instance_config;
+        Self::new_with_config(store, module, imports, config)
+    }
+
+    pub(crate) fn new_with_config(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        imports: &Imports,
+        config: InstanceConfig,
+    ) -> Result<(Self, Exports), InstantiationError> {
+        let externs = imports
+            .resolve_for_module(store, module)
+            .map_err(InstantiationError::Link)?;
 
         _ = store;
         // Hacky: we need to tie a *module* to a store before instantiating it..
@@ -120,13 +180,23 @@ impl Instance {
         let mut store_from_module = wamr_module.handle.store.lock().unwrap();
         let mut store = store_from_module.as_store_mut();
 
-        return Self::new_by_index(&mut store, module, &externs);
+        return Self::new_by_index_with_config(&mut store, module, &externs, config);
     }
 
     pub(crate) fn new_by_index(
         store: &mut impl AsStoreMut,
         module: &Module,
         externs: &[Extern],
+    ) -> Result<(Self, Exports), InstantiationError> {
+        let config = store.as_store_ref().inner.store.as_wamr().instance_config;
+        Self::new_by_index_with_config(store, module, externs, config)
+    }
+
+    fn new_by_index_with_config(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        externs: &[Extern],
+        config: InstanceConfig,
     ) -> Result<(Self, Exports), InstantiationError> {
         let store_ref = store.as_store_ref();
         let externs: Vec<VMExtern> = externs
@@ -138,6 +208,7 @@ impl Instance {
             store_ref.inner.store.as_wamr().inner,
             module.as_wamr().handle.inner,
             externs,
+            config,
         )?;
         let exports = instance.get_exports(store, module);
 
@@ -12,8 +12,8 @@ use crate::{
     wamr::{
         bindings::{
             wasm_limits_max_default, wasm_limits_t, wasm_memory_as_extern, wasm_memory_copy,
-            wasm_memory_new, wasm_memory_type, wasm_memorytype_limits, wasm_memorytype_new,
-            wasm_memorytype_t,
+            wasm_memory_data_size, wasm_memory_grow, wasm_memory_new, wasm_memory_size,
+            wasm_memory_type, wasm_memorytype_limits, wasm_memorytype_new, wasm_memorytype_t,
         },
         vm::VMMemory,
     },
@@ -34,6 +34,14 @@ unsafe impl Sync for Memory {}
 
 impl Memory {
     pub fn new(store: &mut impl AsStoreMut, ty: MemoryType) -> Result<Self, MemoryError> {
+        // Shared memories can only be defined by modules: the C API of WAMR
+        // has no way to mark a memory type as shared.
+        if ty.shared {
+            return Err(MemoryError::UnsupportedOperation {
+                message: "wamr can't create shared memories from the host".to_string(),
+            });
+        }
+
         let limits = Box::into_raw(Box::new(wasm_limits_t {
             min: ty.minimum.0,
             max: match ty.maximum {
@@ -45,8 +53,9 @@ impl Memory {
         let memorytype = unsafe { wasm_memorytype_new(limits) };
 
         let mut store = store.as_store_mut();
-        let inner = store.inner.store.as_wamr().inner;
-        let c_memory = unsafe { wasm_memory_new(inner, memorytype) };
+        let wamr_store = store.inner.store.as_wamr_mut();
+        let c_memory = unsafe { wasm_memory_new(wamr_store.inner, memorytype) };
+        wamr_store.memory_types.insert(c_memory, ty);
 
         Ok(Self { handle: c_memory })
     }
@@ -59,16 +68,28 @@ impl Memory {
         VMExtern::Wamr(unsafe { wasm_memory_as_extern(self.handle) })
     }
 
-    pub fn ty(&self, _store: &impl AsStoreRef) -> MemoryType {
+    pub fn ty(&self, store: &impl AsStoreRef) -> MemoryType {
         let memory_type: *mut wasm_memorytype_t = unsafe { wasm_memory_type(self.handle) };
         let limits: *const wasm_limits_t = unsafe { wasm_memorytype_limits(memory_type) };
 
+        let (minimum, maximum) = unsafe { ((*limits).min, (*limits).max) };
+
+        let store = store.as_store_ref();
+        let declared_ty = store
+            .inner
+            .store
+            .as_wamr()
+            .memory_types
+            .get(&self.handle)
+            .copied();
         MemoryType {
-            // [TODO]: Find a way to extract this from the inner memory type instead
-            // of hardcoding.
-            shared: true,
-            minimum: unsafe { wasmer_types::Pages((*limits).min) },
-            maximum: unsafe { Some(wasmer_types::Pages((*limits).max)) },
+            shared: declared_ty.is_some_and(|ty| ty.shared),
+            minimum: Pages(minimum),
+            maximum: match declared_ty {
+                Some(ty) => ty.maximum,
+                None if maximum == wasm_limits_max_default => None,
+                None => Some(Pages(maximum)),
+            },
         }
     }
 
@@ -85,9 +106,31 @@ impl Memory {
     where
         IntoPages: Into<Pages>,
     {
-        unimplemented!(
-            "calling grow from host is not supported! Use the memory.grow opcode instead."
-        );
+        let delta = delta.into();
+        let current = Pages(unsafe { wasm_memory_size(self.handle) });
+        let could_not_grow = MemoryError::CouldNotGrow {
+            current,
+            attempted_delta: delta,
+        };
+        // Like `memory.grow` in the guest, growing past the maximum fails.
+        let new_pages = current.checked_add(delta).ok_or(could_not_grow.clone())?;
+        let maximum = self.ty(store).maximum.unwrap_or(Pages::max_value());
+        if new_pages > maximum {
+            return Err(could_not_grow);
+        }
+        if delta.0 == 0 {
+            return Ok(current);
+        }
+
+        // WAMR only grows memories with the `memory.grow` instruction.
+        if unsafe { wasm_memory_grow(self.handle, delta.0) } {
+            Ok(current)
+        } else {
+            Err(MemoryError::UnsupportedOperation {
+                message: "wamr can't grow memories from the host, use `memory.grow` instead"
+                    .to_string(),
+            })
+        }
     }
 
     pub fn grow_at_least(
@@ -95,9 +138,13 @@ impl Memory {
         store: &mut impl AsStoreMut,
         min_size: u64,
     ) -> Result<(), MemoryError> {
-        unimplemented!(
-            "calling grow from host is not supported! Use the memory.grow opcode instead."
-        );
+        let current = unsafe { wasm_memory_data_size(self.handle) } as u64;
+        if min_size <= current {
+            return Ok(());
+        }
+        let delta = (min_size - current).div_ceil(WASM_PAGE_SIZE as u64);
+        let delta = Pages(u32::try_from(delta).unwrap_or(u32::MAX));
+        self.grow(store, delta).map(|_| ())
     }
 
     pub fn reset(&self, _store: &mut impl AsStoreMut) -> Result<(), MemoryError> {
@@ -165,7 +212,8 @@ impl Memory {
     }
 
     pub fn as_shared(&self, _store: &impl AsStoreRef) -> Option<SharedMemory> {
-        // Not supported.
+        // Atomic waits and notifications of the guest are handled by WAMR
+        // itself, the C API exposes no way for the host to take part.
         None
     }
 }
@@ -1,5 +1,5 @@
 //! Data types, functions and traits for `wamr`'s `Module` implementation.
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use crate::{
     backend::wamr::bindings::{
@@ -11,9 +11,34 @@ use crate::{
 use bytes::Bytes;
 use wasmer_types::{
     CompileError, DeserializeError, ExportType, ExportsIterator, ExternType, FunctionType,
-    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleInfo, Mutability, Pages,
+    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleHash, ModuleInfo, Mutability, Pages,
     SerializeError, TableType, Type,
 };
+/// How `wamr` executes the code of a module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExecutionMode {
+    /// The code is interpreted.
+    Interpreter,
+    /// The code is compiled with WAMR's fast JIT.
+    FastJit,
+    /// The code was compiled ahead of time with `wamrc`, into an `.aot`
+    /// file.
+    Aot,
+}
+
+impl ExecutionMode {
+    /// Returns true if the bundled WAMR build supports this mode.
+    ///
+    /// It is built with the fast interpreter only, see the `WAMR_BUILD_*`
+    /// options of `build.rs`.
+    pub fn is_supported(self) -> bool {
+        matches!(self, Self::Interpreter)
+    }
+}
+
+/// Magic number starting the `.aot` files generated by `wamrc`.
+const AOT_MAGIC: &[u8; 4] = b"\0aot";
+
 pub(crate) struct ModuleHandle {
     pub(crate) inner: *mut wasm_module_t,
     pub(crate) store: std::sync::Mutex<crate::store::Store>,
@@ -77,10 +102,40 @@ impl Module {
         unsafe { Self::from_binary_unchecked(_engine, binary) }
     }
 
+    /// Loads `binary`, to be executed in the given mode.
+    ///
+    /// Returns [`CompileError::UnsupportedFeature`] if the bundled WAMR
+    /// doesn't support `mode`, see [`ExecutionMode::is_supported`].
+    pub fn from_binary_with_mode(
+        engine: &impl AsEngineRef,
+        binary: &[u8],
+        mode: ExecutionMode,
+    ) -> Result<Self, CompileError> {
+        if mode == ExecutionMode::Aot && !binary.starts_with(AOT_MAGIC) {
+            return Err(CompileError::Validate(
+                "not an AOT module generated by `wamrc`".to_string(),
+            ));
+        }
+        match mode {
+            ExecutionMode::Interpreter => Self::from_binary(engine, binary),
+            ExecutionMode::FastJit | ExecutionMode::Aot => Err(CompileError::UnsupportedFeature(
+                format!("WAMR execution mode {mode:?}"),
+            )),
+        }
+    }
+
+    /// Loads a module compiled ahead of time with `wamrc`.
+    pub fn from_aot_bytes(engine: &impl AsEngineRef, binary: &[u8]) -> Result<Self, CompileError> {
+        Self::from_binary_with_mode(engine, binary, ExecutionMode::Aot)
+    }
+
     pub(crate) unsafe fn from_binary_unchecked(
         engine: &impl AsEngineRef,
         binary: &[u8],
     ) -> Result<Self, CompileError> {
+        if binary.starts_with(AOT_MAGIC) {
+            return Self::from_aot_bytes(engine, binary);
+        }
         let mut binary = binary.to_vec();
         let binary = binary.into_bytes();
         let module = ModuleHandle::new(engine, &binary)?;
@@ -106,9 +161,13 @@ impl Module {
     }
 
     pub fn serialize(&self) -> Result<Bytes, SerializeError> {
-        return self.raw_bytes.clone().ok_or(SerializeError::Generic(
-            "Not able to serialize module".to_string(),
-        ));
+        // The runtime has no serialization format of its own: the original
+        // bytes are kept, and compiled again when deserializing.
+        self.raw_bytes
+            .clone()
+            .ok_or(SerializeError::UnsupportedByRuntime(
+                "the original bytes of the module are not available".to_string(),
+            ))
     }
 
     pub unsafe fn deserialize_unchecked(
@@ -147,6 +206,15 @@ impl Module {
         true
     }
 
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.info.hash
+    }
+
+    pub fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        self.info.remap_import_modules(remapping);
+        self
+    }
+
     pub fn imports<'a>(&'a self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + 'a>> {
         self.info().imports()
     }
@@ -165,6 +233,12 @@ impl Module {
     pub(crate) fn info(&self) -> &ModuleInfo {
         &self.info
     }
+
+    pub(crate) fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        self.raw_bytes.as_deref().map_or_else(Vec::new, |bytes| {
+            crate::utils::polyfill::data_initializers(bytes).unwrap()
+        })
+    }
 }
 
 impl crate::Module {
@@ -191,4 +265,31 @@ impl crate::Module {
             _ => panic!("Not a `wamr` module!"),
         }
     }
+
+    /// Creates a module of a `wamr` engine, to be executed in the given
+    /// mode.
+    ///
+    /// `.aot` files generated by `wamrc` are also detected by
+    /// [`Module::from_binary`](crate::Module::from_binary).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CompileError::UnsupportedFeature`] if the bundled WAMR
+    /// doesn't support `mode`, see [`ExecutionMode::is_supported`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `engine` is not a `wamr` engine.
+    pub fn new_wamr_with_mode(
+        engine: &impl AsEngineRef,
+        bytes: impl AsRef<[u8]>,
+        mode: ExecutionMode,
+    ) -> Result<Self, CompileError> {
+        assert!(
+            engine.as_engine_ref().engine().is_wamr(),
+            "Not a `wamr` engine!"
+        );
+        let module = Module::from_binary_with_mode(engine, bytes.as_ref(), mode)?;
+        Ok(Self(BackendModule::Wamr(module)))
+    }
 }
@@ -1,6 +1,16 @@
 //! Data types, functions and traits for `wamr`'s `Store` implementation.
+use std::{collections::HashMap, ffi::c_void};
+
+use wasmer_types::{FunctionType, MemoryType};
+
 use crate::{
-    backend::wamr::bindings::{wasm_store_delete, wasm_store_new, wasm_store_t},
+    backend::wamr::{
+        bindings::{
+            wasm_functype_delete, wasm_functype_t, wasm_memory_t, wasm_store_delete,
+            wasm_store_new, wasm_store_t,
+        },
+        instance::InstanceConfig,
+    },
     engine::{AsEngineRef, Engine, EngineRef},
     AsStoreRef, BackendStore, StoreRef,
 };
@@ -12,6 +22,17 @@ pub use obj::*;
 pub(crate) struct Store {
     pub(crate) engine: Engine,
     pub(crate) inner: *mut wasm_store_t,
+    /// Configuration of the instances created in this store.
+    pub(crate) instance_config: InstanceConfig,
+    /// The types of the host functions of this store, one per signature.
+    pub(crate) functypes: HashMap<FunctionType, *mut wasm_functype_t>,
+    /// The environments of the host function callbacks of this store, with
+    /// the function dropping each of them.
+    pub(crate) callback_envs: Vec<(*mut c_void, unsafe fn(*mut c_void))>,
+    /// The types the memories of this store were declared with. The C API
+    /// of WAMR doesn't expose whether a memory is shared, nor whether its
+    /// maximum was declared or is the default one.
+    pub(crate) memory_types: HashMap<*mut wasm_memory_t, MemoryType>,
 }
 
 impl std::fmt::Debug for Store {
@@ -25,7 +46,14 @@ impl std::fmt::Debug for Store {
 impl Store {
     pub(crate) fn new(engine: crate::engine::Engine) -> Self {
         let inner: *mut wasm_store_t = unsafe { wasm_store_new(engine.as_wamr().inner.engine) };
-        Store { inner, engine }
+        Store {
+            inner,
+            engine,
+            instance_config: InstanceConfig::default(),
+            functypes: HashMap::new(),
+            callback_envs: Vec::new(),
+            memory_types: HashMap::new(),
+        }
     }
 
     pub(crate) fn engine(&self) -> &Engine {
@@ -39,7 +67,17 @@ impl Store {
 
 impl Drop for Store {
     fn drop(&mut self) {
-        unsafe { wasm_store_delete(self.inner) }
+        unsafe {
+            // The environments may own the last handles to objects of the
+            // store, which must be deleted before the store itself.
+            for (env, drop_env) in self.callback_envs.drain(..) {
+                drop_env(env);
+            }
+            wasm_store_delete(self.inner);
+            for functype in self.functypes.drain().map(|(_, functype)| functype) {
+                wasm_functype_delete(functype);
+            }
+        }
     }
 }
 
@@ -100,4 +138,25 @@ impl crate::Store {
     pub fn is_wamr(&self) -> bool {
         self.inner.store.is_wamr()
     }
+
+    /// Returns the stack and heap sizes of the instances created in this
+    /// `wamr` store.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`self`] is not a `wamr` store.
+    pub fn wamr_instance_config(&self) -> InstanceConfig {
+        self.as_wamr().instance_config
+    }
+
+    /// Sets the stack and heap sizes of the instances created in this
+    /// `wamr` store from now on. A single instance can use other ones with
+    /// [`Instance::new_wamr_with_config`](crate::Instance::new_wamr_with_config).
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`self`] is not a `wamr` store.
+    pub fn set_wamr_instance_config(&mut self, config: InstanceConfig) {
+        self.as_wamr_mut().instance_config = config;
+    }
 }
@@ -1,14 +1,32 @@
-use std::{
-    error::Error,
-    ffi::{c_char, CStr},
-};
+use std::{cell::RefCell, collections::HashMap, error::Error};
+
+use wasmer_types::{FrameInfo, SourceLoc, TrapCode};
 
 use crate::{wamr::bindings::*, AsStoreMut};
 
+/// Prefix of the message of the traps carrying a host error, followed by
+/// the key of the error in [`HOST_ERRORS`].
+const HOST_ERROR_PREFIX: &str = "wasmer host error #";
+
+/// Prefix WAMR adds to the message of the traps it raises.
+const WAMR_EXCEPTION_PREFIX: &str = "Exception: ";
+
+thread_local! {
+    /// The errors of the host functions, until the trap carrying them is
+    /// returned to the caller. Host functions are called on the thread
+    /// calling into Wasm, which gets the trap back.
+    static HOST_ERRORS: RefCell<(u64, HashMap<u64, Box<dyn Error + Send + Sync>>)> =
+        RefCell::new((0, HashMap::new()));
+}
+
 #[derive(Debug)]
 enum InnerTrap {
     User(Box<dyn Error + Send + Sync>),
-    CApi(*mut wasm_trap_t),
+    CApi {
+        trap: *mut wasm_trap_t,
+        message: String,
+        frames: Vec<FrameInfo>,
+    },
 }
 
 /// A struct representing a Trap
@@ -52,62 +70,127 @@ impl Trap {
         }
     }
 
+    /// Returns the trap code of a trap raised by the runtime, recognized
+    /// from its message.
+    pub(crate) fn trap_code(&self) -> Option<TrapCode> {
+        match &self.inner {
+            InnerTrap::User(_) => None,
+            InnerTrap::CApi { message, .. } => TrapCode::from_message(message),
+        }
+    }
+
+    /// Returns the frames of the trap raised by the runtime, the innermost
+    /// first.
+    fn frames(&self) -> Vec<FrameInfo> {
+        match &self.inner {
+            InnerTrap::User(_) => vec![],
+            InnerTrap::CApi { frames, .. } => frames.clone(),
+        }
+    }
+
     pub unsafe fn into_wasm_trap(self, store: &mut impl AsStoreMut) -> *mut wasm_trap_t {
         match self.inner {
-            InnerTrap::CApi(t) => t,
+            InnerTrap::CApi { trap, .. } => trap,
             InnerTrap::User(err) => {
-                let err_ptr = Box::leak(Box::new(err));
-                let mut data = std::mem::zeroed();
-                // let x = format!("")
-                let s1 = format!("🐛{:p}", err_ptr);
-                let _s = s1.into_bytes().into_boxed_slice();
-                wasm_byte_vec_new(&mut data, _s.len(), _s.as_ptr() as _);
-                std::mem::forget(_s);
+                // WAMR only keeps the message of the traps of host functions:
+                // the error itself is kept aside, under a key in the message.
+                let key = HOST_ERRORS.with(|errors| {
+                    let (next, errors) = &mut *errors.borrow_mut();
+                    *next += 1;
+                    errors.insert(*next, err);
+                    *next
+                });
+                let message = format!("{HOST_ERROR_PREFIX}{key}\0");
+                let mut data = wasm_byte_vec_t::default();
+                wasm_byte_vec_new(&mut data, message.len(), message.as_ptr() as _);
                 let store = store.as_store_mut();
-                wasm_trap_new(store.inner.store.as_wamr().inner, &mut data)
+                let trap = wasm_trap_new(store.inner.store.as_wamr().inner, &data);
+                wasm_byte_vec_delete(&mut data);
+                trap
             }
         }
     }
+}
 
-    // pub unsafe fn deserialize_from_wasm_trap(trap: *mut wasm_trap_t) -> Self {
-    //     let mut data = std::mem::zeroed();
-    //     wasm_trap_message(trap, data);
-    //     println!("data: {:p}", data);
+/// Reads the message of `trap`, without the prefix WAMR adds to it.
+unsafe fn trap_message(trap: *const wasm_trap_t) -> String {
+    let mut data = wasm_byte_vec_t::default();
+    wasm_trap_message(trap, &mut data);
+    let message = if data.data.is_null() {
+        String::new()
+    } else {
+        let bytes = std::slice::from_raw_parts(data.data as *const u8, data.size);
+        String::from_utf8_lossy(bytes)
+            .trim_end_matches('\0')
+            .to_string()
+    };
+    wasm_byte_vec_delete(&mut data);
+    match message.strip_prefix(WAMR_EXCEPTION_PREFIX) {
+        Some(message) => message.to_string(),
+        None => message,
+    }
+}
 
-    //     std::ptr::read(data as *const _)
-    // }
+/// Reads the frames of `trap`, which WAMR records with the offsets of the
+/// code in the module.
+unsafe fn trap_frames(trap: *const wasm_trap_t) -> Vec<FrameInfo> {
+    let frame_info = |frame: *const wasm_frame_t| {
+        let instr = wasm_frame_module_offset(frame);
+        let func_start = instr.saturating_sub(wasm_frame_func_offset(frame));
+        FrameInfo::new(
+            String::new(),
+            wasm_frame_func_index(frame),
+            None,
+            SourceLoc::new(func_start as u32),
+            SourceLoc::new(instr as u32),
+        )
+    };
+
+    let mut trace = wasm_frame_vec_t::default();
+    wasm_trap_trace(trap, &mut trace);
+    let mut frames = if trace.data.is_null() {
+        vec![]
+    } else {
+        std::slice::from_raw_parts(trace.data, trace.size)
+            .iter()
+            .map(|frame| frame_info(*frame))
+            .collect::<Vec<_>>()
+    };
+    wasm_frame_vec_delete(&mut trace);
+
+    // Without a trace, the origin of the trap is still known.
+    if frames.is_empty() {
+        let origin = wasm_trap_origin(trap);
+        if !origin.is_null() {
+            frames.push(frame_info(origin));
+            wasm_frame_delete(origin);
+        }
+    }
+    frames
 }
 
 impl From<*mut wasm_trap_t> for Trap {
     fn from(value: *mut wasm_trap_t) -> Self {
-        let message = unsafe {
-            let mut message = std::mem::zeroed();
-            wasm_trap_message(value, &mut message);
-
-            CStr::from_ptr(message.data as *const c_char)
-                .to_str()
-                .unwrap()
-        };
-
-        println!("{message}");
-
-        if message.starts_with("Exception: 🐛") {
-            let ptr_str = message.replace("Exception: 🐛", "");
-            let ptr: Box<dyn Error + Send + Sync + 'static> = unsafe {
-                let r = ptr_str.trim_start_matches("0x");
-                std::ptr::read(
-                    (usize::from_str_radix(&r, 16).unwrap()
-                        as *const Box<dyn Error + Send + Sync + 'static>),
-                )
+        let message = unsafe { trap_message(value) };
+
+        let host_error = message
+            .strip_prefix(HOST_ERROR_PREFIX)
+            .and_then(|key| key.parse::<u64>().ok())
+            .and_then(|key| HOST_ERRORS.with(|errors| errors.borrow_mut().1.remove(&key)));
+        if let Some(err) = host_error {
+            unsafe { wasm_trap_delete(value) };
+            return Self {
+                inner: InnerTrap::User(err),
             };
+        }
 
-            Self {
-                inner: InnerTrap::User(ptr),
-            }
-        } else {
-            Self {
-                inner: InnerTrap::CApi(value),
-            }
+        let frames = unsafe { trap_frames(value) };
+        Self {
+            inner: InnerTrap::CApi {
+                trap: value,
+                message,
+                frames,
+            },
         }
     }
 }
@@ -125,18 +208,7 @@ impl std::fmt::Display for Trap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.inner {
             InnerTrap::User(e) => write!(f, "{}", e),
-            InnerTrap::CApi(value) => {
-                // let message: wasm_message_t;
-                // wasm_trap_message(value, &mut message);
-                let mut out = unsafe {
-                    let mut vec: wasm_byte_vec_t = Default::default();
-                    wasm_byte_vec_new_empty(&mut vec);
-                    &mut vec as *mut _
-                };
-                unsafe { wasm_trap_message(*value, out) };
-                let cstr = unsafe { CStr::from_ptr((*out).data) };
-                write!(f, "wasm-c-api trap: {}", cstr.to_str().unwrap())
-            }
+            InnerTrap::CApi { message, .. } => write!(f, "{message}"),
         }
     }
 }
@@ -145,18 +217,7 @@ impl std::fmt::Debug for Trap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.inner {
             InnerTrap::User(e) => write!(f, "{}", e),
-            InnerTrap::CApi(value) => {
-                // let message: wasm_message_t;
-                // wasm_trap_message(value, &mut message);
-                let mut out = unsafe {
-                    let mut vec: wasm_byte_vec_t = Default::default();
-                    wasm_byte_vec_new_empty(&mut vec);
-                    &mut vec as *mut _
-                };
-                unsafe { wasm_trap_message(*value, out) };
-                let cstr = unsafe { CStr::from_ptr((*out).data) };
-                write!(f, "wasm-c-api trap: {}", cstr.to_str().unwrap())
-            }
+            InnerTrap::CApi { message, .. } => write!(f, "wasm-c-api trap: {message}"),
         }
     }
 }
@@ -167,6 +228,8 @@ impl From<Trap> for crate::RuntimeError {
             return trap.downcast::<Self>().unwrap();
         }
 
-        crate::RuntimeError::new_from_source(crate::BackendTrap::Wamr(trap), vec![], None)
+        let trap_code = trap.trap_code();
+        let frames = trap.frames();
+        crate::RuntimeError::new_from_source(crate::BackendTrap::Wamr(trap), frames, trap_code)
     }
 }
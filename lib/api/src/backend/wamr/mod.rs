@@ -6,4 +6,4 @@ pub(crate) mod error;
 pub(crate) mod utils;
 pub(crate) mod vm;
 
-pub use entities::{engine::Engine as Wamr, *};
+pub use entities::{engine::Engine as Wamr, instance::InstanceConfig, module::ExecutionMode, *};
@@ -495,7 +495,7 @@ where
             }
 
             Ok(Err(e)) => {
-                let trap: Trap = Trap::user(Box::new(e));
+                let trap: Trap = Trap::user(crate::error::box_user_error(e));
                 unsafe { trap.into_wasm_trap(&mut store) }
             }
 
@@ -602,7 +602,7 @@ macro_rules! impl_host_function {
                     },
 
                     Ok(Err(e)) => {
-                        let trap =  crate::backend::wasmi::error::Trap::user(Box::new(e));
+                        let trap =  crate::backend::wasmi::error::Trap::user(crate::error::box_user_error(e));
                         unsafe { trap.into_wasm_trap(store) }
                         // unimplemented!("host function panicked");
                     },
@@ -676,7 +676,7 @@ macro_rules! impl_host_function {
 	          	  unsafe { std::ptr::null_mut() }
 	            },
 
-	            Ok(Err(e)) => { let trap = crate::backend::wasmi::error::Trap::user(Box::new(e)); unsafe { trap.into_wasm_trap(store) } },
+	            Ok(Err(e)) => { let trap = crate::backend::wasmi::error::Trap::user(crate::error::box_user_error(e)); unsafe { trap.into_wasm_trap(store) } },
 
 	            Err(e) => { unimplemented!("host function panicked"); }
 	          }
@@ -95,14 +95,9 @@ impl Instance {
         module: &Module,
         imports: &Imports,
     ) -> Result<(Self, Exports), InstantiationError> {
-        let externs = module
-            .imports()
-            .map(|import_ty| {
-                imports
-                    .get_export(import_ty.module(), import_ty.name())
-                    .expect("Extern not found")
-            })
-            .collect::<Vec<_>>();
+        let externs = imports
+            .resolve_for_module(store, module)
+            .map_err(InstantiationError::Link)?;
 
         return Self::new_by_index(store, module, &externs);
     }
@@ -1,5 +1,5 @@
 //! Data types, functions and traits for `wasmi`'s `Module` implementation.
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 use crate::{
     backend::wasmi::bindings::{
@@ -11,7 +11,7 @@ use crate::{
 use bytes::Bytes;
 use wasmer_types::{
     CompileError, DeserializeError, ExportType, ExportsIterator, ExternType, FunctionType,
-    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleInfo, Mutability, Pages,
+    GlobalType, ImportType, ImportsIterator, MemoryType, ModuleHash, ModuleInfo, Mutability, Pages,
     SerializeError, TableType, Type,
 };
 pub(crate) struct ModuleHandle {
@@ -103,9 +103,13 @@ impl Module {
     }
 
     pub fn serialize(&self) -> Result<Bytes, SerializeError> {
-        return self.raw_bytes.clone().ok_or(SerializeError::Generic(
-            "Not able to serialize module".to_string(),
-        ));
+        // The runtime has no serialization format of its own: the original
+        // bytes are kept, and compiled again when deserializing.
+        self.raw_bytes
+            .clone()
+            .ok_or(SerializeError::UnsupportedByRuntime(
+                "the original bytes of the module are not available".to_string(),
+            ))
     }
 
     pub unsafe fn deserialize_unchecked(
@@ -144,6 +148,15 @@ impl Module {
         true
     }
 
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.info.hash
+    }
+
+    pub fn with_imports_override(mut self, remapping: &HashMap<String, String>) -> Self {
+        self.info.remap_import_modules(remapping);
+        self
+    }
+
     pub fn imports<'a>(&'a self) -> ImportsIterator<Box<dyn Iterator<Item = ImportType> + 'a>> {
         self.info().imports()
     }
@@ -162,6 +175,12 @@ impl Module {
     pub(crate) fn info(&self) -> &ModuleInfo {
         &self.info
     }
+
+    pub(crate) fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        self.raw_bytes.as_deref().map_or_else(Vec::new, |bytes| {
+            crate::utils::polyfill::data_initializers(bytes).unwrap()
+        })
+    }
 }
 
 impl crate::Module {
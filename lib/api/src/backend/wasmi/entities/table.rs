@@ -102,6 +102,21 @@ impl Table {
         }
     }
 
+    pub fn is_null_at(&self, store: &impl AsStoreRef, index: u32) -> Result<bool, RuntimeError> {
+        if index >= self.size(store) {
+            return Err(crate::entities::table::out_of_bounds(index));
+        }
+
+        unsafe {
+            let ref_ = wasm_table_get(self.handle, index);
+            if ref_.is_null() {
+                return Ok(true);
+            }
+            wasm_ref_delete(ref_);
+            Ok(false)
+        }
+    }
+
     pub fn set(
         &self,
         store: &mut impl AsStoreMut,
@@ -52,6 +52,15 @@ impl Trap {
         }
     }
 
+    /// Returns the trap code of a trap raised by the runtime, recognized
+    /// from its message.
+    pub(crate) fn trap_code(&self) -> Option<wasmer_types::TrapCode> {
+        match &self.inner {
+            InnerTrap::User(_) => None,
+            InnerTrap::CApi(_) => wasmer_types::TrapCode::from_message(&self.to_string()),
+        }
+    }
+
     pub unsafe fn into_wasm_trap(self, store: &mut impl AsStoreMut) -> *mut wasm_trap_t {
         match self.inner {
             InnerTrap::CApi(t) => t,
@@ -167,6 +176,7 @@ impl From<Trap> for crate::RuntimeError {
             return trap.downcast::<Self>().unwrap();
         }
 
-        crate::RuntimeError::new_from_source(crate::BackendTrap::Wasmi(trap), vec![], None)
+        let trap_code = trap.trap_code();
+        crate::RuntimeError::new_from_source(crate::BackendTrap::Wasmi(trap), vec![], trap_code)
     }
 }
@@ -10,7 +10,8 @@ use wasmer_compiler::Artifact;
 #[cfg(feature = "compiler")]
 use wasmer_compiler::CompilerConfig;
 
-use crate::{BackendKind, IntoBytes, Store};
+use crate::{BackendKind, IntoBytes, Module, Store};
+use wasmer_types::{CompileError, ModuleHash};
 
 /// Create temporary handles to engines.
 mod engine_ref;
@@ -154,6 +155,114 @@ impl Engine {
         }
     }
 
+    /// Returns whether the `sys` runtime was compiled in.
+    ///
+    /// The `sys` runtime runs native code, produced by a compiler or loaded
+    /// from an artifact.
+    ///
+    /// This lets embedders pick a backend at runtime without repeating the
+    /// crate's `cfg`s; it returns `false` when the `sys` feature is disabled.
+    pub const fn sys_is_available() -> bool {
+        cfg!(feature = "sys")
+    }
+
+    /// Returns whether the `cranelift` compiler was compiled in.
+    pub const fn cranelift_is_available() -> bool {
+        cfg!(feature = "cranelift")
+    }
+
+    /// Returns whether the `llvm` compiler was compiled in.
+    pub const fn llvm_is_available() -> bool {
+        cfg!(feature = "llvm")
+    }
+
+    /// Returns whether the `singlepass` compiler was compiled in.
+    pub const fn singlepass_is_available() -> bool {
+        cfg!(feature = "singlepass")
+    }
+
+    /// Returns whether the `wamr` runtime was compiled in.
+    pub const fn wamr_is_available() -> bool {
+        cfg!(feature = "wamr")
+    }
+
+    /// Returns whether the `wasmi` runtime was compiled in.
+    pub const fn wasmi_is_available() -> bool {
+        cfg!(feature = "wasmi")
+    }
+
+    /// Returns whether the `v8` runtime was compiled in.
+    pub const fn v8_is_available() -> bool {
+        cfg!(feature = "v8")
+    }
+
+    /// Returns whether the `js` runtime was compiled in.
+    pub const fn js_is_available() -> bool {
+        cfg!(feature = "js")
+    }
+
+    /// Returns whether the `jsc` runtime was compiled in.
+    pub const fn jsc_is_available() -> bool {
+        cfg!(feature = "jsc")
+    }
+
+    /// Compiles `wasm_bytes`, reusing the artifact cached in `cache_dir`
+    /// by a previous call when there is one.
+    ///
+    /// Cache entries are stored as `<sha256 of wasm_bytes>.wasm.cache`.
+    /// Each entry starts with the [deterministic id](Self::deterministic_id)
+    /// of the engine that wrote it, so entries written by another engine
+    /// or Wasmer version are recompiled and replaced. An entry that can't
+    /// be loaded is replaced as well.
+    ///
+    /// Writing the cache is best-effort: the module is returned even if
+    /// the entry couldn't be stored, or if the backend doesn't support
+    /// serializing modules.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::Engine;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let engine = Engine::default();
+    /// let cache_dir = tempfile::tempdir()?;
+    /// let wasm = wat::parse_str("(module (func (export \"run\")))")?;
+    ///
+    /// // Compiles the module and stores the artifact.
+    /// let module = unsafe { engine.compile_and_cache(&wasm, cache_dir.path())? };
+    /// // Loads the stored artifact.
+    /// let cached = unsafe { engine.compile_and_cache(&wasm, cache_dir.path())? };
+    /// assert_eq!(module.exports().count(), cached.exports().count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Safety
+    /// Cached artifacts are loaded as executable code without validation,
+    /// see [`Module::deserialize`]: `cache_dir` must only be writable by
+    /// trusted parties.
+    pub unsafe fn compile_and_cache(
+        &self,
+        wasm_bytes: &[u8],
+        cache_dir: &Path,
+    ) -> Result<Module, CompileError> {
+        let hash = ModuleHash::sha256(wasm_bytes).to_string().to_lowercase();
+        let path = cache_dir.join(format!("{hash}.wasm.cache"));
+
+        if let Ok(entry) = std::fs::read(&path) {
+            if let Some(artifact) = strip_cache_header(&entry, self.deterministic_id()) {
+                if let Ok(module) = Module::deserialize(self, artifact) {
+                    return Ok(module);
+                }
+            }
+        }
+
+        let module = Module::from_binary(self, wasm_bytes)?;
+        if let Ok(artifact) = module.serialize() {
+            let _ = write_cache_entry(&path, self.deterministic_id(), &artifact);
+        }
+        Ok(module)
+    }
+
     #[cfg(all(feature = "sys", not(target_arch = "wasm32")))]
     /// Deserializes a WebAssembly module which was previously serialized with
     /// `Module::serialize`,
@@ -224,3 +333,136 @@ impl Engine {
         self.be.deserialize_from_file_unchecked(file_ref)
     }
 }
+
+const CACHE_MAGIC: &[u8; 8] = b"WASMERC\0";
+
+/// Returns the artifact stored in a cache entry, if the entry was written
+/// by an engine with the given deterministic id.
+///
+/// Entries are laid out as the magic, the length of the deterministic id
+/// as a little-endian `u32`, the deterministic id and the artifact.
+fn strip_cache_header<'a>(entry: &'a [u8], deterministic_id: &str) -> Option<&'a [u8]> {
+    let entry = entry.strip_prefix(CACHE_MAGIC)?;
+    let (len, entry) = entry.split_first_chunk::<4>()?;
+    let len = u32::from_le_bytes(*len) as usize;
+    if entry.get(..len)? != deterministic_id.as_bytes() {
+        return None;
+    }
+    Some(&entry[len..])
+}
+
+/// Writes a cache entry, going through a temporary file so that readers
+/// never see a partially written entry.
+fn write_cache_entry(path: &Path, deterministic_id: &str, artifact: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut entry =
+        Vec::with_capacity(CACHE_MAGIC.len() + 4 + deterministic_id.len() + artifact.len());
+    entry.extend_from_slice(CACHE_MAGIC);
+    entry.extend_from_slice(&(deterministic_id.len() as u32).to_le_bytes());
+    entry.extend_from_slice(deterministic_id.as_bytes());
+    entry.extend_from_slice(artifact);
+
+    let tmp = path.with_extension(format!("tmp{}", std::process::id()));
+    std::fs::write(&tmp, entry)?;
+    std::fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp);
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{strip_cache_header, write_cache_entry, Engine};
+
+    #[test]
+    fn available_backends() {
+        assert_eq!(Engine::sys_is_available(), cfg!(feature = "sys"));
+        assert_eq!(Engine::wamr_is_available(), cfg!(feature = "wamr"));
+        // The default engine is always built on an available backend.
+        assert!(
+            Engine::sys_is_available()
+                || Engine::wamr_is_available()
+                || Engine::wasmi_is_available()
+                || Engine::v8_is_available()
+                || Engine::js_is_available()
+                || Engine::jsc_is_available()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn register_host_function_signatures() {
+        use crate::backend::sys::function::register_signature;
+        use wasmer_types::{FunctionType, Type};
+
+        let engine = Engine::default();
+        let types = [
+            FunctionType::new([Type::I32], [Type::I32]),
+            FunctionType::new([], [Type::F64]),
+            FunctionType::new([Type::I32], [Type::I32]),
+        ];
+        let indices = Engine::register_host_function_signatures(&engine, &types);
+        assert_eq!(indices.len(), 3);
+        assert_ne!(indices[0], indices[1]);
+        assert_eq!(indices[0], indices[2]);
+        // Host functions created later find the same signatures.
+        assert_eq!(register_signature(&engine, &types[1]), indices[1]);
+    }
+
+    #[test]
+    fn cache_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("entry.wasm.cache");
+        write_cache_entry(&path, "engine-a", b"artifact").unwrap();
+
+        let entry = std::fs::read(&path).unwrap();
+        assert_eq!(
+            strip_cache_header(&entry, "engine-a"),
+            Some(&b"artifact"[..])
+        );
+        assert_eq!(strip_cache_header(&entry, "engine-b"), None);
+        assert_eq!(strip_cache_header(&entry, "engine"), None);
+        assert_eq!(strip_cache_header(&entry[..10], "engine-a"), None);
+        assert_eq!(strip_cache_header(b"artifact", "engine-a"), None);
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only the sys backend serializes modules"
+    )]
+    fn compile_and_cache() {
+        let engine = Engine::default();
+        let dir = tempfile::tempdir().unwrap();
+        let wasm = wat::parse_str(r#"(module (func (export "answer") (result i32) i32.const 42))"#)
+            .unwrap();
+
+        let module = unsafe { engine.compile_and_cache(&wasm, dir.path()).unwrap() };
+        let entries = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        let name = entries[0].file_name().unwrap().to_str().unwrap();
+        assert!(name.ends_with(".wasm.cache"));
+        assert_eq!(name.len(), 64 + ".wasm.cache".len());
+
+        // The cached artifact is used, and is the same module.
+        let cached = unsafe { engine.compile_and_cache(&wasm, dir.path()).unwrap() };
+        let mut store = crate::Store::new(engine.clone());
+        let instance = crate::Instance::new(&mut store, &cached, &crate::imports! {}).unwrap();
+        let answer = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "answer")
+            .unwrap();
+        assert_eq!(answer.call(&mut store).unwrap(), 42);
+        drop(module);
+
+        // A corrupted entry is replaced.
+        std::fs::write(&entries[0], b"garbage").unwrap();
+        unsafe { engine.compile_and_cache(&wasm, dir.path()).unwrap() };
+        let entry = std::fs::read(&entries[0]).unwrap();
+        assert!(strip_cache_header(&entry, engine.deterministic_id()).is_some());
+    }
+}
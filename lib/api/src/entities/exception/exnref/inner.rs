@@ -51,6 +51,26 @@ impl BackendExceptionRef {
         })
     }
 
+    /// Try to mutably downcast to the given value.
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        match_rt!(on self => r {
+            r.downcast_mut::<T>(store)
+        })
+    }
+
+    /// Try to move the value out of the store.
+    pub fn try_unwrap<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        match_rt!(on self => r {
+            r.try_unwrap::<T>(store).map_err(Self::from)
+        })
+    }
+
     /// Create a [`VMExceptionRef`] from [`Self`].
     pub(crate) fn vm_exceptionref(&self) -> VMExceptionRef {
         match self {
@@ -28,6 +28,31 @@ impl ExceptionRef {
         self.0.downcast(store)
     }
 
+    /// Try to mutably downcast to the given value.
+    ///
+    /// All handles to an exception reference share the same value, so a
+    /// change made through this one is seen through every other handle,
+    /// including the ones held by Wasm. The exclusive borrow of `store`
+    /// guarantees no other access to the value while the returned
+    /// reference is alive.
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.0.downcast_mut(store)
+    }
+
+    /// Try to move the value out of the store.
+    ///
+    /// Returns `Err(self)` if the value is not a `T`, or if other host
+    /// handles to it are alive, such as clones of this `ExceptionRef`.
+    pub fn try_unwrap<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.0.try_unwrap(store).map_err(Self)
+    }
+
     /// Create a [`VMExceptionRef`] from [`Self`].
     pub(crate) fn vm_exceptionref(&self) -> VMExceptionRef {
         self.0.vm_exceptionref()
@@ -55,3 +80,36 @@ impl ExceptionRef {
         self.0.is_from_store(store)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ExceptionRef, Store};
+
+    #[test]
+    fn mutate_and_unwrap() {
+        let mut store = Store::default();
+        let exn = ExceptionRef::new(&mut store, vec!["inner".to_string()]);
+        let other = exn.clone();
+
+        exn.downcast_mut::<Vec<String>>(&mut store)
+            .unwrap()
+            .push("outer".to_string());
+        assert_eq!(
+            other.downcast::<Vec<String>>(&store).unwrap(),
+            &["inner", "outer"]
+        );
+        assert!(exn.downcast_mut::<String>(&mut store).is_none());
+
+        let exn = exn.try_unwrap::<String>(&mut store).unwrap_err();
+        // `other` still refers to the value.
+        let exn = exn.try_unwrap::<Vec<String>>(&mut store).unwrap_err();
+        assert_eq!(
+            other.downcast::<Vec<String>>(&store).unwrap(),
+            &["inner", "outer"]
+        );
+
+        drop(other);
+        let context = exn.try_unwrap::<Vec<String>>(&mut store).unwrap();
+        assert_eq!(context, ["inner", "outer"]);
+    }
+}
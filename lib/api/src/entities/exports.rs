@@ -1,5 +1,5 @@
 use crate::store::AsStoreRef;
-use crate::{Extern, Function, Global, Memory, Table, TypedFunction, WasmTypeList};
+use crate::{Extern, Function, Global, Memory, Table, Tag, TypedFunction, WasmTypeList};
 use indexmap::IndexMap;
 use std::fmt;
 use std::iter::{ExactSizeIterator, FromIterator};
@@ -51,11 +51,30 @@ pub enum ExportError {
     /// This error arises when an export is missing
     #[error("Missing export {0}")]
     Missing(String),
+    /// Several exports failed to be looked up at once, each with the name
+    /// of the export and its error.
+    ///
+    /// Returned by the `from_instance` method generated by
+    /// [`WasmExports`](crate::WasmExports).
+    #[error("{}", format_errors(.0))]
+    Multiple(Vec<(String, ExportError)>),
+}
+
+fn format_errors(errors: &[(String, ExportError)]) -> String {
+    errors
+        .iter()
+        .map(|(name, error)| format!("`{name}`: {error}"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Exports is a special kind of map that allows easily unwrapping
 /// the types of instances.
 ///
+/// Lookups by name are hash-based, while iteration follows the order in
+/// which the exports were inserted (the module's export order for
+/// instance exports).
+///
 /// TODO: add examples of using exports
 #[derive(Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "artifact-size", derive(loupe::MemoryUsage))]
@@ -128,6 +147,11 @@ impl Exports {
         self.get(name)
     }
 
+    /// Get an export as a `Tag`.
+    pub fn get_tag(&self, name: &str) -> Result<&Tag, ExportError> {
+        self.get(name)
+    }
+
     /// Get an export as a `Func`.
     pub fn get_function(&self, name: &str) -> Result<&Function, ExportError> {
         self.get(name)
@@ -169,12 +193,15 @@ impl Exports {
     /// Returns true if the `Exports` contains the given export name.
     pub fn contains<S>(&self, name: S) -> bool
     where
-        S: Into<String>,
+        S: AsRef<str>,
     {
-        self.map.contains_key(&name.into())
+        self.map.contains_key(name.as_ref())
     }
 
-    /// Get an iterator over the exports.
+    /// Get an iterator over the exports, in insertion order.
+    ///
+    /// For the exports of an [`Instance`](crate::Instance), this is the
+    /// order in which the module declares them.
     pub fn iter(&self) -> ExportsIterator<impl Iterator<Item = (&String, &Extern)>> {
         ExportsIterator {
             iter: self.map.iter(),
@@ -200,10 +227,12 @@ impl<'a, I> Iterator for ExportsIterator<'a, I>
 where
     I: Iterator<Item = (&'a String, &'a Extern)> + Sized,
 {
-    type Item = (&'a String, &'a Extern);
+    type Item = (&'a str, &'a Extern);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        self.iter
+            .next()
+            .map(|(name, export)| (name.as_str(), export))
     }
 }
 
@@ -221,32 +250,32 @@ where
     I: Iterator<Item = (&'a String, &'a Extern)> + Sized,
 {
     /// Get only the functions.
-    pub fn functions(self) -> impl Iterator<Item = (&'a String, &'a Function)> + Sized {
-        self.iter.filter_map(|(name, export)| match export {
+    pub fn functions(self) -> impl Iterator<Item = (&'a str, &'a Function)> + Sized {
+        self.filter_map(|(name, export)| match export {
             Extern::Function(function) => Some((name, function)),
             _ => None,
         })
     }
 
     /// Get only the memories.
-    pub fn memories(self) -> impl Iterator<Item = (&'a String, &'a Memory)> + Sized {
-        self.iter.filter_map(|(name, export)| match export {
+    pub fn memories(self) -> impl Iterator<Item = (&'a str, &'a Memory)> + Sized {
+        self.filter_map(|(name, export)| match export {
             Extern::Memory(memory) => Some((name, memory)),
             _ => None,
         })
     }
 
     /// Get only the globals.
-    pub fn globals(self) -> impl Iterator<Item = (&'a String, &'a Global)> + Sized {
-        self.iter.filter_map(|(name, export)| match export {
+    pub fn globals(self) -> impl Iterator<Item = (&'a str, &'a Global)> + Sized {
+        self.filter_map(|(name, export)| match export {
             Extern::Global(global) => Some((name, global)),
             _ => None,
         })
     }
 
     /// Get only the tables.
-    pub fn tables(self) -> impl Iterator<Item = (&'a String, &'a Table)> + Sized {
-        self.iter.filter_map(|(name, export)| match export {
+    pub fn tables(self) -> impl Iterator<Item = (&'a str, &'a Table)> + Sized {
+        self.filter_map(|(name, export)| match export {
             Extern::Table(table) => Some((name, table)),
             _ => None,
         })
@@ -305,3 +334,232 @@ impl<'a, T: Exportable<'a> + Clone + 'static> ExportableWithGenerics<'a, (), ()>
         T::get_self_from_extern(_extern).cloned()
     }
 }
+
+/// A type that can be looked up by name in an instance's [`Exports`].
+///
+/// This is what the [`WasmExports`](crate::WasmExports) derive macro uses
+/// to extract each field. `Option<T>` maps a missing export to `None`.
+pub trait FromExport: Sized {
+    /// Looks up the export `name` in `exports`.
+    fn from_export(
+        store: &impl AsStoreRef,
+        exports: &Exports,
+        name: &str,
+    ) -> Result<Self, ExportError>;
+}
+
+macro_rules! impl_from_export {
+    ($($ty:ty),*) => {
+        $(
+            impl FromExport for $ty {
+                fn from_export(
+                    _store: &impl AsStoreRef,
+                    exports: &Exports,
+                    name: &str,
+                ) -> Result<Self, ExportError> {
+                    exports.get::<Self>(name).cloned()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_export!(Extern, Function, Global, Memory, Table);
+
+impl<Args, Rets> FromExport for TypedFunction<Args, Rets>
+where
+    Args: WasmTypeList,
+    Rets: WasmTypeList,
+{
+    fn from_export(
+        store: &impl AsStoreRef,
+        exports: &Exports,
+        name: &str,
+    ) -> Result<Self, ExportError> {
+        exports.get_typed_function(store, name)
+    }
+}
+
+impl<T: FromExport> FromExport for Option<T> {
+    fn from_export(
+        store: &impl AsStoreRef,
+        exports: &Exports,
+        name: &str,
+    ) -> Result<Self, ExportError> {
+        match T::from_export(store, exports, name) {
+            Ok(value) => Ok(Some(value)),
+            Err(ExportError::Missing(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        imports, ExportError, Exports, Extern, FunctionType, Global, Instance, Memory, Module,
+        Store, Tag, TagKind, TagType, Type, TypedFunction, WasmExports,
+    };
+
+    #[test]
+    fn many_exports() {
+        const COUNT: usize = 10_000;
+
+        let mut store = Store::default();
+        // A single function exported many times keeps compilation cheap.
+        let exports: String = (0..COUNT)
+            .map(|i| format!("(export \"f{i}\" (func $f))"))
+            .collect();
+        let module = Module::new(&store, format!("(module (func $f) {exports})")).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let exports = &instance.exports;
+
+        assert_eq!(exports.len(), COUNT);
+        // Iteration follows the module's export order.
+        assert!(exports
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .eq((0..COUNT).map(|i| format!("f{i}"))));
+
+        // Looking up every export is linear overall; a linear scan per
+        // lookup would be quadratic and take far longer than this bound.
+        let start = std::time::Instant::now();
+        for i in 0..COUNT {
+            let name = format!("f{i}");
+            assert!(exports.contains(&name));
+            assert!(exports.get_extern(&name).is_some());
+            assert!(exports.get_function(&name).is_ok());
+        }
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        assert!(!exports.contains("missing"));
+    }
+
+    #[test]
+    fn host_tag_import() {
+        let mut store = Store::default();
+        let tag = Tag::new(&mut store, [Type::I32]);
+        let imports = imports! {
+            "env" => {
+                "e" => tag.clone(),
+            },
+        };
+
+        let Some(Extern::Tag(imported)) = imports.get_export("env", "e") else {
+            panic!("`e` should be a tag");
+        };
+        assert_eq!(imported, tag);
+        assert_eq!(
+            imported.ty(&store),
+            TagType::from_fn_type(TagKind::Exception, FunctionType::new([Type::I32], []))
+        );
+
+        let namespace = crate::namespace! { "e" => tag.clone() };
+        assert_eq!(namespace.get_tag("e").unwrap(), &tag);
+        assert!(matches!(
+            namespace.get_function("e"),
+            Err(ExportError::IncompatibleType)
+        ));
+    }
+
+    #[test]
+    fn exports_follow_declaration_order() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "z_func"))
+                (memory (export "m_memory") 1)
+                (global (export "a_global") i32 (i32.const 0))
+                (table (export "t_table") 1 funcref)
+                (func (export "b_func")))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let order = ["z_func", "m_memory", "a_global", "t_table", "b_func"];
+
+        let names = instance
+            .exports
+            .iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, order);
+        assert_eq!(
+            instance
+                .exports
+                .iter()
+                .functions()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["z_func", "b_func"]
+        );
+
+        // Filtering and rebuilding keeps the order.
+        let rebuilt = instance
+            .exports
+            .clone()
+            .into_iter()
+            .filter(|(_, export)| !matches!(export, Extern::Table(_)))
+            .collect::<Exports>();
+        let names = rebuilt.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names, ["z_func", "m_memory", "a_global", "b_func"]);
+    }
+
+    const WAT: &str = r#"(module
+        (memory (export "memory") 1)
+        (global (export "__heap_base") i32 (i32.const 1024))
+        (func (export "alloc") (param i32) (result i32) local.get 0))"#;
+
+    #[derive(WasmExports)]
+    struct Abi {
+        #[wasm(func)]
+        alloc: TypedFunction<i32, i32>,
+        #[wasm(memory)]
+        memory: Memory,
+        #[wasm(global, name = "__heap_base")]
+        heap_base: Global,
+        #[wasm(func)]
+        dealloc: Option<TypedFunction<i32, ()>>,
+    }
+
+    #[derive(WasmExports)]
+    struct Broken {
+        #[wasm(func)]
+        alloc: TypedFunction<i64, i32>,
+        #[wasm(memory)]
+        missing: Memory,
+        #[wasm(memory)]
+        memory: Memory,
+    }
+
+    #[test]
+    fn from_instance() {
+        let mut store = Store::default();
+        let module = Module::new(&store, WAT).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+
+        let abi = Abi::from_instance(&store, &instance).unwrap();
+        assert_eq!(abi.alloc.call(&mut store, 7).unwrap(), 7);
+        assert_eq!(abi.memory.view(&store).size().0, 1);
+        assert_eq!(abi.heap_base.get(&mut store).unwrap_i32(), 1024);
+        assert!(abi.dealloc.is_none());
+    }
+
+    #[test]
+    fn from_instance_reports_all_errors() {
+        let mut store = Store::default();
+        let module = Module::new(&store, WAT).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+
+        match Broken::from_instance(&store, &instance) {
+            Err(ExportError::Multiple(errors)) => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].0, "alloc");
+                assert!(matches!(errors[0].1, ExportError::IncompatibleType));
+                assert_eq!(errors[1].0, "missing");
+                assert!(matches!(&errors[1].1, ExportError::Missing(name) if name == "missing"));
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+}
@@ -42,6 +42,57 @@ impl BackendExternRef {
         }
     }
 
+    /// Make a new extern reference, calling `finalizer` with the value
+    /// when it is freed.
+    #[inline]
+    pub fn new_with_finalizer<T>(
+        store: &mut impl AsStoreMut,
+        value: T,
+        finalizer: Box<dyn FnOnce(&mut T) + Send>,
+    ) -> Self
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        match &store.as_store_mut().inner.store {
+            #[cfg(feature = "sys")]
+            crate::BackendStore::Sys(_) => Self::Sys(
+                crate::backend::sys::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+            #[cfg(feature = "wamr")]
+            crate::BackendStore::Wamr(_) => Self::Wamr(
+                crate::backend::wamr::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+            #[cfg(feature = "wasmi")]
+            crate::BackendStore::Wasmi(_) => Self::Wasmi(
+                crate::backend::wasmi::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+            #[cfg(feature = "v8")]
+            crate::BackendStore::V8(_) => Self::V8(
+                crate::backend::v8::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+            #[cfg(feature = "js")]
+            crate::BackendStore::Js(_) => Self::Js(
+                crate::backend::js::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+            #[cfg(feature = "jsc")]
+            crate::BackendStore::Jsc(_) => Self::Jsc(
+                crate::backend::jsc::entities::external::ExternRef::new_with_finalizer(
+                    store, value, finalizer,
+                ),
+            ),
+        }
+    }
+
     /// Try to downcast to the given value.
     #[inline]
     pub fn downcast<'a, T>(&self, store: &'a impl AsStoreRef) -> Option<&'a T>
@@ -53,6 +104,69 @@ impl BackendExternRef {
         })
     }
 
+    /// Try to downcast to the given value, mutably.
+    #[inline]
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        match_rt!(on self => r {
+            r.downcast_mut::<T>(store)
+        })
+    }
+
+    /// Move the value out of the store, if it is a `T` and this is the
+    /// last host handle to it.
+    #[inline]
+    pub fn take<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        match_rt!(on self => r {
+            r.take::<T>(store).map_err(Self::from)
+        })
+    }
+
+    /// Returns the name of the type of the stored value.
+    #[inline]
+    pub fn type_name(&self, store: &impl AsStoreRef) -> &'static str {
+        match_rt!(on self => r {
+            r.type_name(store)
+        })
+    }
+
+    /// Checks whether `a` and `b` refer to the same host object.
+    #[inline]
+    pub(crate) fn ptr_eq(a: &Self, b: &Self) -> bool {
+        match (a, b) {
+            #[cfg(feature = "sys")]
+            (Self::Sys(a), Self::Sys(b)) => {
+                crate::backend::sys::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            #[cfg(feature = "wamr")]
+            (Self::Wamr(a), Self::Wamr(b)) => {
+                crate::backend::wamr::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            #[cfg(feature = "wasmi")]
+            (Self::Wasmi(a), Self::Wasmi(b)) => {
+                crate::backend::wasmi::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            #[cfg(feature = "v8")]
+            (Self::V8(a), Self::V8(b)) => {
+                crate::backend::v8::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            #[cfg(feature = "js")]
+            (Self::Js(a), Self::Js(b)) => {
+                crate::backend::js::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            #[cfg(feature = "jsc")]
+            (Self::Jsc(a), Self::Jsc(b)) => {
+                crate::backend::jsc::entities::external::ExternRef::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
+
     /// Create a [`VMExternRef`] from [`Self`].
     #[inline]
     pub(crate) fn vm_externref(&self) -> VMExternRef {
@@ -2,7 +2,8 @@ use std::any::Any;
 
 use crate::entities::store::{AsStoreMut, AsStoreRef};
 use crate::vm::VMExternRef;
-use crate::StoreRef;
+use crate::{StoreRef, Value};
+use wasmer_types::{RawValue, Type};
 
 pub(crate) mod inner;
 pub(crate) use inner::*;
@@ -20,6 +21,29 @@ impl ExternRef {
         Self(BackendExternRef::new(store, value))
     }
 
+    /// Make a new extern reference, calling `finalizer` with the value
+    /// when it is freed: by [`Store::gc`](crate::Store::gc) once it is not
+    /// used anymore, or when the store is dropped at the latest.
+    ///
+    /// The finalizer is not called if the value was moved out with
+    /// [`ExternRef::take`].
+    ///
+    /// Finalizers run while the store is being collected or dropped, so
+    /// they only get the value: they can't access the store, and in
+    /// particular can't create new extern references.
+    pub fn new_with_finalizer<T>(
+        store: &mut impl AsStoreMut,
+        value: T,
+        finalizer: Box<dyn FnOnce(&mut T) + Send>,
+    ) -> Self
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        Self(BackendExternRef::new_with_finalizer(
+            store, value, finalizer,
+        ))
+    }
+
     /// Try to downcast to the given value.
     pub fn downcast<'a, T>(&self, store: &'a impl AsStoreRef) -> Option<&'a T>
     where
@@ -28,6 +52,101 @@ impl ExternRef {
         self.0.downcast(store)
     }
 
+    /// Try to downcast to the given value, mutably.
+    ///
+    /// The returned reference borrows the store mutably, so the value
+    /// can't be accessed through another handle at the same time.
+    pub fn downcast_mut<'a, T>(&self, store: &'a mut impl AsStoreMut) -> Option<&'a mut T>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.0.downcast_mut(store)
+    }
+
+    /// Try to move the value out of the store.
+    ///
+    /// Returns `Err(self)` if the value is not a `T`, or if other
+    /// [`ExternRef`]s to it are alive. Tables and globals still referencing
+    /// the object are left with an empty one, which no longer downcasts to
+    /// any type.
+    pub fn take<T>(self, store: &mut impl AsStoreMut) -> Result<T, Self>
+    where
+        T: Any + Send + Sync + 'static + Sized,
+    {
+        self.0.take(store).map_err(Self)
+    }
+
+    /// Returns the name of the type of the value stored in this reference,
+    /// as given by [`std::any::type_name`], e.g. for debug logging.
+    ///
+    /// ```
+    /// # use wasmer::{ExternRef, Store};
+    /// # let mut store = Store::default();
+    /// let externref = ExternRef::new(&mut store, 1u32);
+    /// assert_eq!(externref.type_name(&store), "u32");
+    /// ```
+    pub fn type_name(&self, store: &impl AsStoreRef) -> &'static str {
+        self.0.type_name(store)
+    }
+
+    /// Checks whether `a` and `b` refer to the same host object.
+    ///
+    /// This is the reference equality of the reference types proposal: two
+    /// references created separately are different even if they hold equal
+    /// values, while a reference that went through Wasm is still equal to
+    /// the original.
+    ///
+    /// ```
+    /// # use wasmer::{ExternRef, Store};
+    /// # let mut store = Store::default();
+    /// let a = ExternRef::new(&mut store, 1u32);
+    /// let b = ExternRef::new(&mut store, 1u32);
+    /// assert!(ExternRef::ptr_eq(&a, &a.clone()));
+    /// assert!(!ExternRef::ptr_eq(&a, &b));
+    /// ```
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        BackendExternRef::ptr_eq(&a.0, &b.0)
+    }
+
+    /// Converts this reference into a [`RawValue`], e.g. to pass it through
+    /// an FFI boundary, like [`Value::as_raw`] does for `externref` values.
+    ///
+    /// The raw value is only meaningful to `store`, and does not keep the
+    /// referenced object alive: see [`ExternRef::from_raw`].
+    pub fn as_raw(&self, _store: &impl AsStoreRef) -> RawValue {
+        self.vm_externref().into_raw()
+    }
+
+    /// Converts a [`RawValue`] created by [`ExternRef::as_raw`] back into
+    /// an `ExternRef`, or returns `None` for a null reference.
+    ///
+    /// With the `sys` backend, `None` is also returned if `raw` is out of
+    /// the range of the objects of `store`, or designates an object that
+    /// was released. Nothing else is checked: a raw value from another
+    /// store, or of a released object whose slot was reused, is converted
+    /// into whichever object of `store` now has that index.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have been created by [`ExternRef::as_raw`] or compiled
+    /// code running against the same, still alive `store`, and the object
+    /// it references must have been kept alive since, for example by
+    /// another `ExternRef` or a table of the store. The other backends
+    /// can't check this.
+    pub unsafe fn from_raw(store: &mut impl AsStoreMut, raw: RawValue) -> Option<Self> {
+        #[cfg(feature = "sys")]
+        #[allow(irrefutable_let_patterns)]
+        if let crate::StoreObjects::Sys(objects) = store.as_store_ref().objects() {
+            if raw.externref != 0 && !objects.is_live_extern_obj(raw.externref) {
+                return None;
+            }
+        }
+        match Value::from_raw(store, Type::ExternRef, raw) {
+            Value::ExternRef(externref) => externref,
+            _ => unreachable!(),
+        }
+    }
+
     /// Create a [`VMExternRef`] from [`Self`].
     pub(crate) fn vm_externref(&self) -> VMExternRef {
         self.0.vm_externref()
@@ -51,4 +170,224 @@ impl ExternRef {
     pub fn is_from_store(&self, store: &impl AsStoreRef) -> bool {
         self.0.is_from_store(store)
     }
+
+    /// Creates a [`WeakExternRef`] to the same host object, which does not
+    /// keep it from being released by [`Store::gc`](crate::Store::gc).
+    ///
+    /// ```
+    /// # use wasmer::{ExternRef, Store};
+    /// # let mut store = Store::default();
+    /// let value = ExternRef::new(&mut store, 1u32);
+    /// let weak = value.downgrade();
+    /// assert!(weak.upgrade(&store).is_some());
+    ///
+    /// // Once the last `ExternRef` is dropped, the object can be released.
+    /// drop(value);
+    /// store.gc();
+    /// ```
+    pub fn downgrade(&self) -> WeakExternRef {
+        #[allow(unreachable_patterns)]
+        WeakExternRef(match &self.0 {
+            #[cfg(feature = "sys")]
+            BackendExternRef::Sys(r) => BackendWeakExternRef::Sys(r.downgrade()),
+            r => BackendWeakExternRef::Strong(r.clone()),
+        })
+    }
+}
+
+/// A weak reference to the host object of an [`ExternRef`], created with
+/// [`ExternRef::downgrade`].
+///
+/// Unlike an [`ExternRef`], it doesn't keep the host object from being
+/// released by [`Store::gc`](crate::Store::gc), which makes it suitable
+/// for caches of host objects.
+#[derive(Debug, Clone)]
+pub struct WeakExternRef(BackendWeakExternRef);
+
+#[derive(Debug, Clone)]
+enum BackendWeakExternRef {
+    #[cfg(feature = "sys")]
+    Sys(crate::backend::sys::entities::external::WeakExternRef),
+    /// The other backends never release host objects before their store
+    /// is dropped.
+    Strong(BackendExternRef),
+}
+
+impl WeakExternRef {
+    /// Returns an [`ExternRef`] to the host object, or `None` if it was
+    /// released or `store` is not the store of the object.
+    pub fn upgrade(&self, store: &impl AsStoreRef) -> Option<ExternRef> {
+        match &self.0 {
+            #[cfg(feature = "sys")]
+            BackendWeakExternRef::Sys(r) => r.upgrade(store).map(|r| ExternRef(r.into())),
+            BackendWeakExternRef::Strong(r) => r.is_from_store(store).then(|| ExternRef(r.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{imports, AsStoreRef, ExternRef, Global, Instance, Module, Store, Value};
+
+    #[test]
+    #[cfg_attr(not(feature = "sys"), ignore = "ExternRef is only supported by `sys`")]
+    fn ptr_eq_survives_a_round_trip_through_wasm() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "id") (param externref) (result externref)
+                    local.get 0))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let id = instance.exports.get_function("id").unwrap();
+
+        let a = ExternRef::new(&mut store, String::from("host"));
+        let b = ExternRef::new(&mut store, String::from("host"));
+        let result = id
+            .call(&mut store, &[Value::ExternRef(Some(a.clone()))])
+            .unwrap();
+        let Value::ExternRef(Some(returned)) = &result[0] else {
+            panic!("unexpected result: {result:?}");
+        };
+        assert!(ExternRef::ptr_eq(&a, returned));
+        assert!(!ExternRef::ptr_eq(&b, returned));
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn gc_releases_transient_externrefs() {
+        use wasmer_vm::{StoreObject, VMExternObj};
+
+        let mut store = Store::default();
+        let extern_objs =
+            |store: &Store| VMExternObj::list(store.as_store_ref().objects().as_sys()).len();
+
+        let kept = ExternRef::new(&mut store, String::from("kept"));
+        let global = Global::new_mut(
+            &mut store,
+            Value::ExternRef(Some(ExternRef::new(&mut store, String::from("global")))),
+        );
+        let weak = ExternRef::new(&mut store, String::from("cached")).downgrade();
+        for i in 0..100_000u32 {
+            drop(ExternRef::new(&mut store, i));
+            if i % 1000 == 0 {
+                store.gc();
+            }
+        }
+        assert!(extern_objs(&store) <= 1004);
+
+        store.gc();
+        assert!(weak.upgrade(&store).is_none());
+        assert_eq!(kept.downcast::<String>(&store).unwrap(), "kept");
+        let Value::ExternRef(Some(value)) = global.get(&mut store) else {
+            panic!("the global was cleared");
+        };
+        assert_eq!(value.downcast::<String>(&store).unwrap(), "global");
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "sys"), ignore = "ExternRef is only supported by `sys`")]
+    fn mutate_and_take() {
+        use std::collections::HashMap;
+
+        let mut store = Store::default();
+        let a = ExternRef::new(&mut store, HashMap::<String, u32>::new());
+        let b = a.clone();
+
+        a.downcast_mut::<HashMap<String, u32>>(&mut store)
+            .unwrap()
+            .insert("calls".to_string(), 1);
+        *b.downcast_mut::<HashMap<String, u32>>(&mut store)
+            .unwrap()
+            .get_mut("calls")
+            .unwrap() += 1;
+        assert_eq!(
+            a.downcast::<HashMap<String, u32>>(&store).unwrap()["calls"],
+            2
+        );
+        assert!(a.downcast_mut::<String>(&mut store).is_none());
+
+        // `b` is still alive.
+        let a = a.take::<HashMap<String, u32>>(&mut store).unwrap_err();
+        drop(b);
+        let a = a.take::<String>(&mut store).unwrap_err();
+        let map = a.take::<HashMap<String, u32>>(&mut store).unwrap();
+        assert_eq!(map["calls"], 2);
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn finalizers() {
+        use std::sync::{Arc, Mutex};
+
+        let finalized = Arc::new(Mutex::new(Vec::new()));
+        let finalizer = |finalized: &Arc<Mutex<Vec<String>>>| {
+            let finalized = finalized.clone();
+            Box::new(move |name: &mut String| finalized.lock().unwrap().push(name.clone()))
+                as Box<dyn FnOnce(&mut String) + Send>
+        };
+
+        let mut store = Store::default();
+        let a = ExternRef::new_with_finalizer(&mut store, "a".to_string(), finalizer(&finalized));
+        let a2 = a.clone();
+        let b = ExternRef::new_with_finalizer(&mut store, "b".to_string(), finalizer(&finalized));
+        let taken =
+            ExternRef::new_with_finalizer(&mut store, "taken".to_string(), finalizer(&finalized));
+        let _c = ExternRef::new_with_finalizer(&mut store, "c".to_string(), finalizer(&finalized));
+
+        drop(a);
+        store.gc();
+        assert!(finalized.lock().unwrap().is_empty());
+
+        drop(a2);
+        drop(b);
+        assert_eq!(taken.take::<String>(&mut store).unwrap(), "taken");
+        store.gc();
+        assert_eq!(*finalized.lock().unwrap(), ["a", "b"]);
+
+        // `c` is still alive: it is finalized with the store.
+        drop(store);
+        assert_eq!(*finalized.lock().unwrap(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn type_names() {
+        struct Session;
+
+        let mut store = Store::default();
+        let session = ExternRef::new(&mut store, Session);
+        assert!(session.type_name(&store).ends_with("::Session"));
+        let name = ExternRef::new(&mut store, String::from("name"));
+        assert_eq!(name.type_name(&store), "alloc::string::String");
+        let list = ExternRef::new(&mut store, vec![1u8]);
+        assert_eq!(list.type_name(&store), std::any::type_name::<Vec<u8>>());
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn raw_round_trip() {
+        use crate::{Function, RawValue};
+
+        let mut store = Store::default();
+        let externref = ExternRef::new(&mut store, 42u32);
+        let raw = externref.as_raw(&store);
+        let back = unsafe { ExternRef::from_raw(&mut store, raw) }.unwrap();
+        assert!(ExternRef::ptr_eq(&externref, &back));
+        assert_eq!(back.downcast::<u32>(&store), Some(&42));
+        assert!(unsafe { ExternRef::from_raw(&mut store, RawValue { externref: 0 }) }.is_none());
+
+        // A raw value of another store doesn't designate any of its objects.
+        let mut other = Store::default();
+        assert!(unsafe { ExternRef::from_raw(&mut other, raw) }.is_none());
+
+        let double = Function::new_typed(&mut store, |x: i32| x * 2);
+        let raw = double.as_raw(&store);
+        let back = unsafe { Function::from_raw(&mut store, raw) }.unwrap();
+        let back = back.typed::<i32, i32>(&store).unwrap();
+        assert_eq!(back.call(&mut store, 21).unwrap(), 42);
+        assert!(unsafe { Function::from_raw(&mut store, RawValue { funcref: 0 }) }.is_none());
+    }
 }
@@ -61,6 +61,17 @@ impl Extern {
         }
     }
 
+    /// Returns the name of the backend this `Extern` comes from.
+    pub(crate) fn backend_name(&self) -> &'static str {
+        match self {
+            Self::Function(f) => f.0.backend_name(),
+            Self::Global(g) => g.0.backend_name(),
+            Self::Tag(t) => t.0.backend_name(),
+            Self::Memory(m) => m.0.backend_name(),
+            Self::Table(t) => t.0.backend_name(),
+        }
+    }
+
     /// To `VMExtern`.
     pub fn to_vm_extern(&self) -> VMExtern {
         match self {
@@ -110,6 +110,33 @@ impl<T> BackendFunctionEnv<T> {
         })
     }
 
+    /// Checks whether this environment was created in `store`.
+    pub fn is_from_store(&self, store: &impl AsStoreRef) -> bool {
+        match_rt!(on self => f {
+            f.is_from_store(store)
+        })
+    }
+
+    /// Moves the data out of the store, see [`FunctionEnv::into_inner`].
+    pub fn into_inner(self, store: &mut impl AsStoreMut) -> Option<T>
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        match_rt!(on self => f {
+            f.into_inner(store)
+        })
+    }
+
+    /// Puts back data moved out with [`BackendFunctionEnv::into_inner`].
+    pub(crate) fn restore(&self, store: &mut impl AsStoreMut, value: T)
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        match_rt!(on self => f {
+            f.restore(store, value)
+        })
+    }
+
     /// Convert it into a `FunctionEnvMut`
     pub fn into_mut(self, store: &mut impl AsStoreMut) -> FunctionEnvMut<T>
     where
@@ -121,6 +148,27 @@ impl<T> BackendFunctionEnv<T> {
     }
 }
 
+/// Panic message of accesses to a function environment whose data was moved
+/// out with [`FunctionEnv::into_inner`].
+pub(crate) const TAKEN_FUNCTION_ENV: &str =
+    "the data of this function environment was taken with `FunctionEnv::into_inner`";
+
+/// Placeholder left in the store by [`FunctionEnv::into_inner`].
+struct TakenFunctionEnv;
+
+/// Moves the data of type `T` out of the contents of a function environment,
+/// leaving a placeholder behind. Returns `None` if it was already taken.
+pub(crate) fn take_function_env<T: Any>(contents: &mut Box<dyn Any + Send>) -> Option<T> {
+    let taken = std::mem::replace(contents, Box::new(TakenFunctionEnv));
+    match taken.downcast::<T>() {
+        Ok(data) => Some(*data),
+        Err(taken) => {
+            *contents = taken;
+            None
+        }
+    }
+}
+
 /// A temporary handle to a [`FunctionEnv`].
 #[derive(derive_more::From)]
 pub enum BackendFunctionEnvMut<'a, T: 'a> {
@@ -45,6 +45,37 @@ impl<T> FunctionEnv<T> {
         self.0.as_mut(store)
     }
 
+    /// Checks whether this environment was created in `store`.
+    ///
+    /// An environment can only be used with the store it was created in.
+    pub fn is_from_store(&self, store: &impl AsStoreRef) -> bool {
+        self.0.is_from_store(store)
+    }
+
+    /// Replaces the data with `value`, returning the previous one.
+    ///
+    /// Host functions using this environment see the new data from their
+    /// next call on.
+    pub fn replace(&self, store: &mut impl AsStoreMut, value: T) -> T
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        std::mem::replace(self.as_mut(store), value)
+    }
+
+    /// Moves the data out of the store.
+    ///
+    /// The environment is shared by all its clones and the host functions
+    /// created with it, which can't be tracked: once the data is moved out,
+    /// accessing it through any of them panics, including by calling such a
+    /// host function. Returns `None` if the data was already moved out.
+    pub fn into_inner(self, store: &mut impl AsStoreMut) -> Option<T>
+    where
+        T: Any + Send + 'static + Sized,
+    {
+        self.0.into_inner(store)
+    }
+
     /// Convert it into a `FunctionEnvMut`
     pub fn into_mut(self, store: &mut impl AsStoreMut) -> FunctionEnvMut<T>
     where
@@ -79,10 +110,37 @@ impl<T: Send + 'static> FunctionEnvMut<'_, T> {
         self.0.as_mut()
     }
 
-    /// Borrows a new mutable reference of both the attached Store and host state
+    /// Borrows a new mutable reference of both the attached Store and host state.
+    ///
+    /// Both borrows are tied to `&mut self`, so neither can outlive this
+    /// `FunctionEnvMut`.
+    ///
+    /// The host state must not be reached again through the returned store
+    /// (e.g. via a cloned [`FunctionEnv`]) while the `&mut T` is in use;
+    /// [`FunctionEnvMut::with_data_and_store`] rules that out.
     pub fn data_and_store_mut(&mut self) -> (&mut T, StoreMut) {
         self.0.data_and_store_mut()
     }
+
+    /// Runs `f` with mutable access to both the host state and the attached
+    /// Store.
+    ///
+    /// This allows a host function to, for example, read a [`crate::Memory`]
+    /// kept in its environment and grow it through the store in the same
+    /// call. The host state is moved out of the store while `f` runs, so
+    /// reaching it again through the store panics instead of aliasing the
+    /// `&mut T`. If `f` panics, the host state is lost, as with
+    /// [`FunctionEnv::into_inner`].
+    pub fn with_data_and_store<R>(&mut self, f: impl FnOnce(&mut T, StoreMut<'_>) -> R) -> R {
+        let env = self.as_ref();
+        let mut data = env
+            .clone()
+            .into_inner(self)
+            .expect("the data of this function environment was moved out");
+        let result = f(&mut data, self.as_store_mut());
+        env.0.restore(self, data);
+        result
+    }
 }
 
 impl<T> AsStoreRef for FunctionEnvMut<'_, T> {
@@ -109,3 +167,36 @@ where
         self.0.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{imports, Function, FunctionEnv, FunctionEnvMut, Instance, Module, Store};
+
+    #[test]
+    fn replace_and_into_inner() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "get" (func $get (result i32)))
+                (func (export "run") (result i32) (call $get)))"#,
+        )
+        .unwrap();
+        let env = FunctionEnv::new(&mut store, 1);
+        let get =
+            Function::new_typed_with_env(&mut store, &env, |env: FunctionEnvMut<i32>| *env.data());
+        let instance =
+            Instance::new(&mut store, &module, &imports! { "env" => { "get" => get } }).unwrap();
+        let run = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "run")
+            .unwrap();
+
+        assert_eq!(run.call(&mut store).unwrap(), 1);
+        assert_eq!(env.replace(&mut store, 2), 1);
+        assert_eq!(run.call(&mut store).unwrap(), 2);
+
+        assert_eq!(env.clone().into_inner(&mut store), Some(2));
+        assert_eq!(env.into_inner(&mut store), None);
+    }
+}
@@ -13,7 +13,9 @@ pub use env::*;
 use wasmer_types::{FunctionType, RawValue};
 
 use crate::{
-    error::RuntimeError,
+    error::{
+        BackendMismatchError, FunctionEnvStoreError, NativeFunctionUnsupportedError, RuntimeError,
+    },
     vm::{VMExtern, VMExternFunction, VMFuncRef},
     AsStoreMut, AsStoreRef, ExportError, Exportable, Extern, StoreMut, StoreRef, TypedFunction,
     Value, WasmTypeList,
@@ -90,6 +92,11 @@ impl Function {
     ///     Ok(vec![Value::I32(sum)])
     /// });
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `env` was created in another store, see
+    /// [`Function::try_new_with_env`].
     pub fn new_with_env<FT, F, T: Send + 'static>(
         store: &mut impl AsStoreMut,
         env: &FunctionEnv<T>,
@@ -103,7 +110,28 @@ impl Function {
             + Send
             + Sync,
     {
-        Self(BackendFunction::new_with_env(store, env, ty, func))
+        Self::try_new_with_env(store, env, ty, func).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Function::new_with_env`], but returns an error instead of
+    /// panicking if `env` was created in another store than `store`.
+    pub fn try_new_with_env<FT, F, T: Send + 'static>(
+        store: &mut impl AsStoreMut,
+        env: &FunctionEnv<T>,
+        ty: FT,
+        func: F,
+    ) -> Result<Self, FunctionEnvStoreError>
+    where
+        FT: Into<FunctionType>,
+        F: Fn(FunctionEnvMut<T>, &[Value]) -> Result<Vec<Value>, RuntimeError>
+            + 'static
+            + Send
+            + Sync,
+    {
+        if !env.is_from_store(store) {
+            return Err(FunctionEnvStoreError);
+        }
+        Ok(Self(BackendFunction::new_with_env(store, env, ty, func)))
     }
 
     /// Creates a new host `Function` from a native function.
@@ -116,6 +144,46 @@ impl Function {
         Self(BackendFunction::new_typed(store, func))
     }
 
+    #[cfg(feature = "sys")]
+    /// Creates a new host `Function` from native code, such as a function
+    /// written in assembly or generated with LLVM.
+    ///
+    /// Wasm calls `func_ptr` directly, with the `Static` calling
+    /// convention of host functions: the native C calling convention, with
+    /// a leading `*mut VMContext` argument (always null here) followed by
+    /// the parameters of `ty`, and returning its results.
+    ///
+    /// `trampoline` is used to call the function from the host, for example
+    /// with [`Function::call`]: it is given the `VMContext`, `func_ptr` and
+    /// a buffer holding the parameters as [`RawValue`]s, and must call
+    /// `func_ptr` and write its results back in the same buffer.
+    ///
+    /// Only `sys` stores can run native code: the stores of other backends
+    /// return a [`NativeFunctionUnsupportedError`].
+    ///
+    /// # Safety
+    ///
+    /// `func_ptr` must point to a function with the `Static` calling
+    /// convention and the signature `ty`, and `trampoline` must call it
+    /// following that same signature. Both must stay valid as long as the
+    /// store.
+    pub unsafe fn new_raw(
+        store: &mut impl AsStoreMut,
+        ty: FunctionType,
+        func_ptr: *const (),
+        trampoline: crate::sys::vm::VMTrampoline,
+    ) -> Result<Self, NativeFunctionUnsupportedError> {
+        match store.as_store_mut().inner.store {
+            crate::BackendStore::Sys(_) => Ok(Self(BackendFunction::Sys(
+                crate::backend::sys::function::Function::new_raw(store, ty, func_ptr, trampoline),
+            ))),
+            #[allow(unreachable_patterns)]
+            _ => Err(NativeFunctionUnsupportedError(
+                store.as_store_ref().inner.store.backend_name(),
+            )),
+        }
+    }
+
     /// Creates a new host `Function` with an environment from a typed function.
     ///
     /// The function signature is automatically retrieved using the
@@ -134,6 +202,11 @@ impl Function {
     ///
     /// let f = Function::new_typed_with_env(&mut store, &env, sum);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `env` was created in another store, see
+    /// [`Function::try_new_typed_with_env`].
     pub fn new_typed_with_env<T: Send + 'static, F, Args, Rets>(
         store: &mut impl AsStoreMut,
         env: &FunctionEnv<T>,
@@ -144,7 +217,25 @@ impl Function {
         Args: WasmTypeList,
         Rets: WasmTypeList,
     {
-        Self(BackendFunction::new_typed_with_env(store, env, func))
+        Self::try_new_typed_with_env(store, env, func).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Like [`Function::new_typed_with_env`], but returns an error instead
+    /// of panicking if `env` was created in another store than `store`.
+    pub fn try_new_typed_with_env<T: Send + 'static, F, Args, Rets>(
+        store: &mut impl AsStoreMut,
+        env: &FunctionEnv<T>,
+        func: F,
+    ) -> Result<Self, FunctionEnvStoreError>
+    where
+        F: HostFunction<T, Args, Rets, WithEnv> + 'static + Send + Sync,
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        if !env.is_from_store(store) {
+            return Err(FunctionEnvStoreError);
+        }
+        Ok(Self(BackendFunction::new_typed_with_env(store, env, func)))
     }
 
     /// Returns the [`FunctionType`] of the `Function`.
@@ -247,6 +338,7 @@ impl Function {
         store: &mut impl AsStoreMut,
         params: &[Value],
     ) -> Result<Box<[Value]>, RuntimeError> {
+        self.check_backend(store)?;
         self.0.call(store, params)
     }
 
@@ -257,9 +349,48 @@ impl Function {
         store: &mut impl AsStoreMut,
         params: Vec<RawValue>,
     ) -> Result<Box<[Value]>, RuntimeError> {
+        self.check_backend(store)?;
         self.0.call_raw(store, params)
     }
 
+    /// Checks that this function comes from the backend of `store`.
+    pub(crate) fn check_backend(
+        &self,
+        store: &impl AsStoreRef,
+    ) -> Result<(), BackendMismatchError> {
+        BackendMismatchError::check(
+            store.as_store_ref().inner.store.backend_name(),
+            self.0.backend_name(),
+        )
+    }
+
+    /// Converts this function into a [`RawValue`] holding a `funcref`, e.g.
+    /// to pass it through an FFI boundary, like [`Value::as_raw`] does for
+    /// `funcref` values.
+    ///
+    /// The raw value is only meaningful to `store`: see
+    /// [`Function::from_raw`].
+    pub fn as_raw(&self, store: &impl AsStoreRef) -> RawValue {
+        self.vm_funcref(store).into_raw()
+    }
+
+    /// Converts a [`RawValue`] created by [`Function::as_raw`] back into a
+    /// `Function`, or returns `None` for a null `funcref`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have been created by [`Function::as_raw`] or compiled code
+    /// running against the same, still alive `store`. A `funcref` is a
+    /// pointer to the function's data, so unlike
+    /// [`ExternRef::from_raw`](crate::ExternRef::from_raw), a raw value
+    /// from another store can't be detected.
+    pub unsafe fn from_raw(store: &mut impl AsStoreMut, raw: RawValue) -> Option<Self> {
+        match Value::from_raw(store, wasmer_types::Type::FuncRef, raw) {
+            Value::FuncRef(func) => func,
+            _ => unreachable!(),
+        }
+    }
+
     pub(crate) fn vm_funcref(&self, store: &impl AsStoreRef) -> VMFuncRef {
         self.0.vm_funcref(store)
     }
@@ -360,6 +491,101 @@ impl Function {
         self.0.typed(store)
     }
 
+    /// Transform this WebAssembly function into a typed function without
+    /// checking that `Args` and `Rets` match its signature.
+    ///
+    /// This avoids the [`FunctionType`] comparison done by
+    /// [`Function::typed`], for hot paths where the signature is already
+    /// known to be right. When `debug_assertions` are enabled, the types are
+    /// still checked and a mismatch panics.
+    ///
+    /// # Safety
+    ///
+    /// `Args` and `Rets` must match the parameter and result types of this
+    /// function exactly. Calling the returned [`TypedFunction`] with the
+    /// wrong types is undefined behavior.
+    pub unsafe fn to_typed_unchecked<Args, Rets>(
+        self,
+        store: &impl AsStoreRef,
+    ) -> TypedFunction<Args, Rets>
+    where
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        #[cfg(debug_assertions)]
+        {
+            let ty = self.ty(store);
+            debug_assert_eq!(
+                ty.params(),
+                Args::wasm_types(),
+                "given types for the function arguments don't match the actual types"
+            );
+            debug_assert_eq!(
+                ty.results(),
+                Rets::wasm_types(),
+                "given types for the function results don't match the actual types"
+            );
+        }
+        TypedFunction::new(store, self)
+    }
+
+    /// Creates a new dynamic `Function` calling `func` with `args` as its
+    /// first parameters.
+    ///
+    /// The new function takes the remaining parameters of `func` and has
+    /// the same results. This is handy to bind a context value, such as a
+    /// pointer into guest memory, before handing a function to a module.
+    ///
+    /// ```
+    /// # use wasmer::{Function, Store, Value};
+    /// # let mut store = Store::default();
+    /// let sub = Function::new_typed(&mut store, |a: i32, b: i32| a - b);
+    /// let ten_minus = Function::bind(&mut store, &sub, &[Value::I32(10)]).unwrap();
+    /// assert_eq!(ten_minus.param_arity(&store), 1);
+    /// assert_eq!(ten_minus.call(&mut store, &[Value::I32(3)]).unwrap()[0], Value::I32(7));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `func` takes fewer than `args.len()` parameters,
+    /// or if the types of `args` don't match its first parameters.
+    pub fn bind(
+        store: &mut impl AsStoreMut,
+        func: &Self,
+        args: &[Value],
+    ) -> Result<Self, RuntimeError> {
+        let ty = func.ty(store);
+        if args.len() > ty.params().len() {
+            return Err(RuntimeError::new(format!(
+                "cannot bind {} arguments to a function taking {} parameters",
+                args.len(),
+                ty.params().len()
+            )));
+        }
+        for (i, (arg, param)) in args.iter().zip(ty.params()).enumerate() {
+            if arg.ty() != *param {
+                return Err(RuntimeError::new(format!(
+                    "bound argument {i} has type {}, expected {param}",
+                    arg.ty()
+                )));
+            }
+        }
+
+        let bound_ty = FunctionType::new(&ty.params()[args.len()..], ty.results());
+        let env = FunctionEnv::new(store, ());
+        let func = func.clone();
+        let args = args.to_vec();
+        Ok(Self::new_with_env(
+            store,
+            &env,
+            bound_ty,
+            move |mut env, rest: &[Value]| {
+                let params = args.iter().chain(rest).cloned().collect::<Vec<_>>();
+                func.call(&mut env, &params).map(Vec::from)
+            },
+        ))
+    }
+
     pub(crate) fn from_vm_extern(store: &mut impl AsStoreMut, vm_extern: VMExternFunction) -> Self {
         Self(BackendFunction::from_vm_extern(store, vm_extern))
     }
@@ -382,3 +608,331 @@ impl<'a> Exportable<'a> for Function {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{Function, Store};
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn new_raw() {
+        use crate::sys::vm::{VMContext, VMFunctionBody};
+        use crate::{imports, Instance, Module, RawValue, Value};
+        use wasmer_types::{FunctionType, Type};
+
+        extern "C" fn add(_: *mut VMContext, a: i32, b: i32) -> i32 {
+            a.wrapping_add(b)
+        }
+
+        unsafe extern "C" fn trampoline(
+            vmctx: *mut VMContext,
+            body: *const VMFunctionBody,
+            values: *mut RawValue,
+        ) {
+            let body: extern "C" fn(*mut VMContext, i32, i32) -> i32 = std::mem::transmute(body);
+            let (a, b) = ((*values).i32, (*values.add(1)).i32);
+            *values = RawValue {
+                i32: body(vmctx, a, b),
+            };
+        }
+
+        let mut store = Store::default();
+        let ty = FunctionType::new([Type::I32, Type::I32], [Type::I32]);
+        let add =
+            unsafe { Function::new_raw(&mut store, ty, add as *const (), trampoline) }.unwrap();
+        assert_eq!(
+            add.call(&mut store, &[Value::I32(2), Value::I32(40)])
+                .unwrap()
+                .to_vec(),
+            [Value::I32(42)]
+        );
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "add" (func $add (param i32 i32) (result i32)))
+                (func (export "run") (param i32) (result i32)
+                    (call $add (local.get 0) (i32.const 1))))"#,
+        )
+        .unwrap();
+        let instance =
+            Instance::new(&mut store, &module, &imports! { "env" => { "add" => add } }).unwrap();
+        let run = instance
+            .exports
+            .get_typed_function::<i32, i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store, 41).unwrap(), 42);
+    }
+
+    #[test]
+    fn to_typed_unchecked() {
+        let mut store = Store::default();
+        let add = Function::new_typed(&mut store, |a: i32, b: i32| a + b);
+        let add = unsafe { add.to_typed_unchecked::<(i32, i32), i32>(&store) };
+        assert_eq!(add.call(&mut store, 1, 2).unwrap(), 3);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "function results")]
+    fn to_typed_unchecked_checks_types_in_debug() {
+        let mut store = Store::default();
+        let add = Function::new_typed(&mut store, |a: i32, b: i32| a + b);
+        let _ = unsafe { add.to_typed_unchecked::<(i32, i32), i64>(&store) };
+    }
+
+    #[test]
+    fn bind() {
+        use crate::{imports, Instance, Module, Value};
+        use wasmer_types::Type;
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "mad") (param i32 i64 i32) (result i64)
+                    (i64.add
+                        (i64.mul (i64.extend_i32_s (local.get 0)) (local.get 1))
+                        (i64.extend_i32_s (local.get 2)))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let mad = instance.exports.get_function("mad").unwrap();
+
+        let bound = Function::bind(&mut store, mad, &[Value::I32(3), Value::I64(5)]).unwrap();
+        let ty = bound.ty(&store);
+        assert_eq!(ty.params(), [Type::I32]);
+        assert_eq!(ty.results(), [Type::I64]);
+        let result = bound.call(&mut store, &[Value::I32(1)]).unwrap();
+        assert_eq!(*result, [Value::I64(16)]);
+
+        // Binding every parameter leaves a nullary function.
+        let all = Function::bind(&mut store, &bound, &[Value::I32(2)]).unwrap();
+        assert_eq!(all.param_arity(&store), 0);
+        assert_eq!(*all.call(&mut store, &[]).unwrap(), [Value::I64(17)]);
+
+        assert!(Function::bind(&mut store, mad, &[Value::I64(3)]).is_err());
+        let too_many = [Value::I32(1), Value::I64(2), Value::I32(3), Value::I32(4)];
+        assert!(Function::bind(&mut store, mad, &too_many).is_err());
+    }
+
+    #[test]
+    fn env_from_another_store() {
+        use crate::{
+            FunctionEnv, FunctionEnvMut, FunctionEnvStoreError, FunctionType, Type, Value,
+        };
+
+        fn get(env: FunctionEnvMut<i32>) -> i32 {
+            *env.data()
+        }
+
+        let mut first = Store::default();
+        let mut second = Store::default();
+        let env = FunctionEnv::new(&mut first, 42);
+        assert!(env.is_from_store(&first));
+        assert!(!env.is_from_store(&second));
+
+        assert_eq!(
+            Function::try_new_typed_with_env(&mut second, &env, get).unwrap_err(),
+            FunctionEnvStoreError
+        );
+        let ty = FunctionType::new([Type::I32], [Type::I32]);
+        let error = Function::try_new_with_env(&mut second, &env, ty, |_, args| Ok(args.to_vec()));
+        assert!(error.is_err());
+
+        let get = Function::try_new_typed_with_env(&mut first, &env, get).unwrap();
+        assert_eq!(*get.call(&mut first, &[]).unwrap(), [Value::I32(42)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "created in another store")]
+    fn new_with_env_from_another_store() {
+        use crate::{FunctionEnv, FunctionEnvMut};
+
+        let mut first = Store::default();
+        let mut second = Store::default();
+        let env = FunctionEnv::new(&mut first, ());
+        Function::new_typed_with_env(&mut second, &env, |_: FunctionEnvMut<()>| {});
+    }
+
+    #[test]
+    fn data_and_store_mut() {
+        use crate::{FunctionEnv, FunctionEnvMut, Memory, MemoryType, Pages};
+
+        struct Env {
+            memory: Option<Memory>,
+            pages: u32,
+        }
+
+        let mut store = Store::default();
+        let memory = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+        let env = FunctionEnv::new(
+            &mut store,
+            Env {
+                memory: Some(memory.clone()),
+                pages: 2,
+            },
+        );
+
+        let grow =
+            Function::new_typed_with_env(&mut store, &env, |mut env: FunctionEnvMut<Env>| {
+                let (data, mut store) = env.data_and_store_mut();
+                let memory = data.memory.as_ref().unwrap();
+                let previous = memory.grow(&mut store, data.pages).unwrap();
+                data.pages += 1;
+                previous.0
+            });
+        let grow_scoped =
+            Function::new_typed_with_env(&mut store, &env, |mut env: FunctionEnvMut<Env>| {
+                env.with_data_and_store(|data, mut store| {
+                    let memory = data.memory.as_ref().unwrap();
+                    memory.grow(&mut store, data.pages).unwrap().0
+                })
+            });
+
+        let grow = grow.typed::<(), u32>(&store).unwrap();
+        let grow_scoped = grow_scoped.typed::<(), u32>(&store).unwrap();
+        assert_eq!(grow.call(&mut store).unwrap(), 1);
+        assert_eq!(env.as_ref(&store).pages, 3);
+        assert_eq!(grow_scoped.call(&mut store).unwrap(), 3);
+        assert_eq!(memory.view(&store).size(), Pages(6));
+    }
+
+    #[test]
+    #[should_panic(expected = "was taken")]
+    fn with_data_and_store_rejects_aliasing() {
+        use crate::FunctionEnv;
+
+        let mut store = Store::default();
+        let env = FunctionEnv::new(&mut store, 0u32);
+        let mut env_mut = env.clone().into_mut(&mut store);
+        env_mut.with_data_and_store(|data, mut store| {
+            // Reaching the host state again through the store can't alias `data`.
+            let other = env.as_mut(&mut store);
+            *other += 1;
+            *data += 1;
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_many_host_functions() {
+        use std::sync::Arc;
+
+        use crate::{Imports, Instance, Module};
+
+        const IMPORTS: usize = 200;
+        let wat = (0..IMPORTS)
+            .map(|i| format!(r#"(import "env" "f{i}" (func (param i32) (result i32)))"#))
+            .collect::<String>();
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        let module = Module::new(
+            &store,
+            format!(r#"(module {wat} (export "last" (func {})))"#, IMPORTS - 1),
+        )
+        .unwrap();
+        let tracker = Arc::new(());
+
+        for round in 0..10 {
+            let mut imports = Imports::new();
+            for i in 0..IMPORTS {
+                let tracker = tracker.clone();
+                let f = Function::new_typed(&mut store, move |x: i32| {
+                    let _ = &tracker;
+                    x + round * IMPORTS as i32 + i as i32
+                });
+                imports.define("env", &format!("f{i}"), f);
+            }
+            let instance = Instance::new(&mut store, &module, &imports).unwrap();
+            // Each instance calls its own closures.
+            let last = instance
+                .exports
+                .get_typed_function::<i32, i32>(&store, "last")
+                .unwrap();
+            assert_eq!(
+                last.call(&mut store, 1).unwrap(),
+                1 + round * IMPORTS as i32 + IMPORTS as i32 - 1
+            );
+        }
+
+        // All the functions share a single type.
+        assert_eq!(store.as_wamr().functypes.len(), 1);
+        // The closures are dropped with the store, not before.
+        assert_eq!(Arc::strong_count(&tracker), 10 * IMPORTS + 1);
+        drop(store);
+        assert_eq!(Arc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    #[cfg(any(feature = "wamr", feature = "v8"))]
+    fn host_function_holding_an_instance() {
+        use std::sync::Arc;
+
+        use crate::{Instance, Module};
+
+        fn check(mut store: Store) {
+            let module = Module::new(&store, "(module (func (export \"run\")))").unwrap();
+            let instance = Instance::new(&mut store, &module, &crate::imports! {}).unwrap();
+            let tracker = Arc::new(());
+
+            // The closure keeps the only handle to the instance.
+            Function::new_typed(&mut store, {
+                let tracker = tracker.clone();
+                move || {
+                    let _ = (&instance, &tracker);
+                }
+            });
+            assert_eq!(Arc::strong_count(&tracker), 2);
+
+            // The instance is deleted with the closure, before the store.
+            drop(store);
+            assert_eq!(Arc::strong_count(&tracker), 1);
+        }
+
+        #[cfg(feature = "wamr")]
+        check(Store::new(crate::backend::wamr::Wamr::new()));
+        #[cfg(feature = "v8")]
+        check(Store::new(crate::backend::v8::V8::new()));
+    }
+
+    #[test]
+    #[cfg(any(feature = "wamr", feature = "v8"))]
+    fn host_functions_outlive_their_handles() {
+        use crate::{imports, Instance, Module, TypedFunction};
+
+        fn check(mut store: Store) {
+            let module = Module::new(
+                &store,
+                r#"(module
+                    (import "env" "add_one" (func $add_one (param i32) (result i32)))
+                    (func (export "call_add_one") (param i32) (result i32)
+                        (call $add_one (local.get 0))))"#,
+            )
+            .unwrap();
+            let env = FunctionEnv::new(&mut store, 1);
+            let add_one = Function::new_typed_with_env(
+                &mut store,
+                &env,
+                |env: FunctionEnvMut<i32>, x: i32| x + *env.data(),
+            );
+            let imports = imports! { "env" => { "add_one" => add_one } };
+            let instance = Instance::new(&mut store, &module, &imports).unwrap();
+            let call_add_one: TypedFunction<i32, i32> = instance
+                .exports
+                .get_typed_function(&store, "call_add_one")
+                .unwrap();
+            // Only the store keeps the host function alive now.
+            drop(imports);
+            drop(instance);
+
+            for _ in 0..10 {
+                assert_eq!(call_add_one.call(&mut store, 41).unwrap(), 42);
+            }
+        }
+
+        #[cfg(feature = "wamr")]
+        check(Store::new(crate::backend::wamr::Wamr::new()));
+        #[cfg(feature = "v8")]
+        check(Store::new(crate::backend::v8::V8::new()));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     store::{AsStoreMut, AsStoreRef, StoreMut, StoreRef},
     value::Value,
     vm::{VMExtern, VMExternGlobal},
-    ExportError, Exportable, Extern,
+    ExportError, Exportable, Extern, ExternRef, Function,
 };
 use wasmer_types::{GlobalType, Mutability};
 
@@ -55,6 +55,63 @@ impl Global {
         Self(BackendGlobal::new_mut(store, val))
     }
 
+    /// Create a global of type `externref`, initialized with `val`, or
+    /// with the null reference for `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::{ExternRef, Global, Mutability, Store, Type, Value};
+    /// # let mut store = Store::default();
+    /// #
+    /// let r = ExternRef::new(&mut store, 42u32);
+    /// let g = Global::new_extern_ref(&mut store, Some(r), true);
+    ///
+    /// assert_eq!(g.ty(&mut store).ty, Type::ExternRef);
+    /// assert_eq!(g.ty(&mut store).mutability, Mutability::Var);
+    /// let Value::ExternRef(Some(r)) = g.get(&mut store) else { unreachable!() };
+    /// assert_eq!(r.downcast::<u32>(&store), Some(&42));
+    ///
+    /// let null = Global::new_extern_ref(&mut store, None, false);
+    /// assert!(null.get(&mut store).is_null_ref());
+    /// ```
+    pub fn new_extern_ref(
+        store: &mut impl AsStoreMut,
+        val: Option<ExternRef>,
+        mutable: bool,
+    ) -> Self {
+        Self::new_ref(store, Value::ExternRef(val), mutable)
+    }
+
+    /// Create a global of type `funcref`, initialized with `val`, or with
+    /// the null reference for `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::{Function, Global, Mutability, Store, Type, Value};
+    /// # let mut store = Store::default();
+    /// #
+    /// let f = Function::new_typed(&mut store, || 42);
+    /// let global = Global::new_func_ref(&mut store, Some(f.clone()), false);
+    ///
+    /// assert_eq!(global.ty(&mut store).ty, Type::FuncRef);
+    /// assert_eq!(global.ty(&mut store).mutability, Mutability::Const);
+    /// let Value::FuncRef(Some(g)) = global.get(&mut store) else { unreachable!() };
+    /// assert_eq!(g, f);
+    /// ```
+    pub fn new_func_ref(store: &mut impl AsStoreMut, val: Option<Function>, mutable: bool) -> Self {
+        Self::new_ref(store, Value::FuncRef(val), mutable)
+    }
+
+    fn new_ref(store: &mut impl AsStoreMut, val: Value, mutable: bool) -> Self {
+        if mutable {
+            Self::new_mut(store, val)
+        } else {
+            Self::new(store, val)
+        }
+    }
+
     /// Create a global with the initial [`Value`] and the provided [`Mutability`].
     fn from_value(
         store: &mut impl AsStoreMut,
@@ -0,0 +1,234 @@
+//! Host modules are sets of host functions prepared once for an [`Engine`],
+//! and cheaply added to the imports of any store using that engine.
+
+use crate::{
+    AsStoreMut, Engine, EngineId, Function, HostFunction, StoreMut, WasmTypeList, WithoutEnv,
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// Creates the [`Function`] handle of a host module entry in a store.
+type Materialize = Arc<dyn Fn(&mut StoreMut<'_>) -> Function + Send + Sync>;
+
+type Prepare = Box<dyn FnOnce(&Engine) -> Materialize>;
+
+/// Builds a [`HostModule`].
+///
+/// # Usage
+/// ```
+/// # use wasmer::{Engine, HostModuleBuilder, Imports, Store};
+/// let engine = Engine::default();
+/// let host = HostModuleBuilder::new("env")
+///     .func_typed("add", |a: i32, b: i32| a + b)
+///     .func_typed("now", || 0i64)
+///     .build(&engine);
+///
+/// for _ in 0..10 {
+///     let mut store = Store::new(engine.clone());
+///     let imports = Imports::from_host_module(&mut store, &host);
+///     assert!(imports.exists("env", "add"));
+/// }
+/// ```
+pub struct HostModuleBuilder {
+    namespace: String,
+    funcs: Vec<(String, Prepare)>,
+}
+
+impl HostModuleBuilder {
+    /// Creates a builder for a host module exposed under `namespace`.
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            funcs: Vec::new(),
+        }
+    }
+
+    /// Adds a host function named `name`.
+    ///
+    /// The function is cloned into every store the host module is
+    /// materialized in.
+    pub fn func_typed<F, Args, Rets>(mut self, name: &str, func: F) -> Self
+    where
+        F: HostFunction<(), Args, Rets, WithoutEnv> + Clone + 'static + Send + Sync,
+        Args: WasmTypeList + 'static,
+        Rets: WasmTypeList + 'static,
+    {
+        let prepare: Prepare = Box::new(move |engine| prepare(engine, func));
+        self.funcs.push((name.to_string(), prepare));
+        self
+    }
+
+    /// Prepares the host functions for `engine`.
+    ///
+    /// With the `sys` backend, the function signatures are registered in
+    /// the engine here, once, instead of every time a store materializes
+    /// them.
+    pub fn build(self, engine: &Engine) -> HostModule {
+        HostModule {
+            namespace: self.namespace,
+            engine: engine.id(),
+            funcs: self
+                .funcs
+                .into_iter()
+                .map(|(name, prepare)| (name, prepare(engine)))
+                .collect(),
+        }
+    }
+}
+
+fn prepare<F, Args, Rets>(engine: &Engine, func: F) -> Materialize
+where
+    F: HostFunction<(), Args, Rets, WithoutEnv> + Clone + 'static + Send + Sync,
+    Args: WasmTypeList + 'static,
+    Rets: WasmTypeList + 'static,
+{
+    #[cfg(feature = "sys")]
+    if engine.is_sys() {
+        use crate::backend::sys::function::{register_signature, Function as SysFunction};
+
+        let ty = wasmer_types::FunctionType::new(Args::wasm_types(), Rets::wasm_types());
+        let type_index = register_signature(engine, &ty);
+        let engine = engine.id();
+        return Arc::new(move |store| {
+            // Signature indices are only valid in the engine that issued them.
+            if store.engine().id() != engine {
+                return Function::new_typed(store, func.clone());
+            }
+            let func =
+                SysFunction::new_typed_with_signature(store, func.clone(), ty.clone(), type_index);
+            Function(crate::BackendFunction::Sys(func))
+        });
+    }
+
+    Arc::new(move |store| Function::new_typed(store, func.clone()))
+}
+
+/// A set of host functions prepared once for an [`Engine`].
+///
+/// Materializing a host module in a store with
+/// [`Imports::from_host_module`](crate::Imports::from_host_module) only creates the per-store function
+/// handles; the work that only depends on the engine, such as registering
+/// the function signatures, is done once by [`HostModuleBuilder::build`].
+///
+/// A host module can be shared between threads and materialized in stores
+/// using other engines, in which case nothing is reused.
+#[derive(Clone)]
+pub struct HostModule {
+    namespace: String,
+    engine: EngineId,
+    funcs: Vec<(String, Materialize)>,
+}
+
+impl HostModule {
+    /// Returns the namespace the functions are exposed under.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Iterates over the names of the functions of this module.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.funcs.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Creates the functions of this module in `store`.
+    pub(crate) fn materialize(&self, store: &mut impl AsStoreMut) -> Vec<(&str, Function)> {
+        let mut store = store.as_store_mut();
+        self.funcs
+            .iter()
+            .map(|(name, materialize)| (name.as_str(), materialize(&mut store)))
+            .collect()
+    }
+}
+
+impl fmt::Debug for HostModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostModule")
+            .field("namespace", &self.namespace)
+            .field("engine", &self.engine)
+            .field("funcs", &self.names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        imports, Engine, Extern, Function, HostModuleBuilder, Imports, Instance, Module, Store,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn host_module_is_reused_across_stores() {
+        use crate::backend::sys::function::SIGNATURE_REGISTRATIONS;
+        let registrations = || SIGNATURE_REGISTRATIONS.with(|count| count.get());
+
+        let engine = Engine::default();
+        let module = Module::new(
+            &engine,
+            r#"(module
+                (import "env" "add" (func $add (param i32 i32) (result i32)))
+                (import "env" "log" (func $log (param i32)))
+                (func (export "run") (param i32) (result i32)
+                    (call $log (local.get 0))
+                    (call $add (local.get 0) (i32.const 1))))"#,
+        )
+        .unwrap();
+
+        let logged = Arc::new(AtomicUsize::new(0));
+        let before = registrations();
+        let host = {
+            let logged = logged.clone();
+            HostModuleBuilder::new("env")
+                .func_typed("add", |a: i32, b: i32| a + b)
+                .func_typed("log", move |x: i32| {
+                    logged.fetch_add(x as usize, Ordering::SeqCst);
+                })
+                .build(&engine)
+        };
+        assert_eq!(registrations() - before, 2);
+
+        let before = registrations();
+        for i in 0..1000 {
+            let mut store = Store::new(engine.clone());
+            let imports = Imports::from_host_module(&mut store, &host);
+            let instance = Instance::new(&mut store, &module, &imports).unwrap();
+            let run = instance
+                .exports
+                .get_typed_function::<i32, i32>(&store, "run")
+                .unwrap();
+            assert_eq!(run.call(&mut store, i).unwrap(), i + 1);
+        }
+        assert_eq!(registrations() - before, 0);
+        assert_eq!(logged.load(Ordering::SeqCst), (0..1000).sum::<usize>());
+
+        // Building the imports by hand registers the signatures every time.
+        let before = registrations();
+        for _ in 0..10 {
+            let mut store = Store::new(engine.clone());
+            let _ = imports! {
+                "env" => {
+                    "add" => Function::new_typed(&mut store, |a: i32, b: i32| a + b),
+                    "log" => Function::new_typed(&mut store, |_: i32| {}),
+                },
+            };
+        }
+        assert_eq!(registrations() - before, 20);
+    }
+
+    #[test]
+    fn host_module_in_another_engine() {
+        let host = HostModuleBuilder::new("env")
+            .func_typed("answer", || 42)
+            .build(&Engine::default());
+
+        let mut store = Store::new(Engine::default());
+        let imports = Imports::from_host_module(&mut store, &host);
+        let Some(Extern::Function(answer)) = imports.get_export("env", "answer") else {
+            panic!("`answer` should be a function");
+        };
+        let answer = answer.typed::<(), i32>(&store).unwrap();
+        assert_eq!(answer.call(&mut store).unwrap(), 42);
+    }
+}
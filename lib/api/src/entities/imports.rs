@@ -1,10 +1,15 @@
 //! The import module contains the implementation data structures and helper functions used to
 //! manipulate and access a wasm module's imports including memories, tables, globals, and
 //! functions.
-use crate::{error::LinkError, Exports, Extern, Module};
+use crate::{
+    error::{LinkError, LinkIssue},
+    AsStoreMut, AsStoreRef, Exports, Extern, HostModule, Instance, LinkerError, Module, StoreMut,
+    StoreRef,
+};
 use std::collections::HashMap;
 use std::fmt;
-use wasmer_types::ImportError;
+use std::sync::Arc;
+use wasmer_types::ExternType;
 
 /// All of the import data used when instantiating.
 ///
@@ -36,6 +41,7 @@ use wasmer_types::ImportError;
 #[derive(Clone, Default)]
 pub struct Imports {
     pub(crate) map: HashMap<(String, String), Extern>,
+    fallbacks: Vec<Arc<Imports>>,
 }
 
 impl Imports {
@@ -53,11 +59,30 @@ impl Imports {
     /// import_object.get_export("module", "name");
     /// ```
     pub fn get_export(&self, module: &str, name: &str) -> Option<Extern> {
-        if self.exists(module, name) {
-            let ext = &self.map[&(module.to_string(), name.to_string())];
-            return Some(ext.clone());
-        }
-        None
+        self.lookup(module, name).cloned()
+    }
+
+    /// Returns the definition visible for a given module and name,
+    /// looking through the chained fallbacks if needed.
+    ///
+    /// # Usage
+    /// ```no_run
+    /// # use wasmer::Imports;
+    /// let import_object = Imports::new();
+    /// assert!(import_object.get("module", "name").is_none());
+    /// ```
+    pub fn get(&self, module: &str, name: &str) -> Option<&Extern> {
+        self.lookup(module, name)
+    }
+
+    fn lookup(&self, module: &str, name: &str) -> Option<&Extern> {
+        self.map
+            .get(&(module.to_string(), name.to_string()))
+            .or_else(|| {
+                self.fallbacks
+                    .iter()
+                    .find_map(|fallback| fallback.lookup(module, name))
+            })
     }
 
     /// Returns if an export exist for a given module and name.
@@ -69,13 +94,69 @@ impl Imports {
     /// import_object.exists("module", "name");
     /// ```
     pub fn exists(&self, module: &str, name: &str) -> bool {
-        self.map
-            .contains_key(&(module.to_string(), name.to_string()))
+        self.lookup(module, name).is_some()
     }
 
     /// Returns true if the Imports contains namespace with the provided name.
     pub fn contains_namespace(&self, name: &str) -> bool {
         self.map.keys().any(|(k, _)| (k == name))
+            || self
+                .fallbacks
+                .iter()
+                .any(|fallback| fallback.contains_namespace(name))
+    }
+
+    /// Layers `fallback` below `self`: lookups that `self` can't resolve
+    /// are resolved from `fallback` instead.
+    ///
+    /// Definitions in `self` shadow the definitions of `fallback` with the
+    /// same module and name. Chaining several fallbacks consults them in
+    /// the order they were chained, after `self`. Definitions added to the
+    /// returned `Imports` go into its top layer.
+    ///
+    /// The fallback is kept behind an [`Arc`], so a base set of imports can
+    /// be shared by many chains without copying it.
+    ///
+    /// # Usage
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use wasmer::{imports, Function, Store};
+    /// # let mut store = Store::default();
+    /// let base = Arc::new(imports! {
+    ///     "env" => {
+    ///         "log" => Function::new_typed(&mut store, |_: i32| {}),
+    ///         "now" => Function::new_typed(&mut store, || 0i64),
+    ///     },
+    /// });
+    /// let tenant = imports! {
+    ///     "env" => {
+    ///         "log" => Function::new_typed(&mut store, |_: i32| {}),
+    ///     },
+    /// }
+    /// .chain(base.clone());
+    /// assert!(tenant.exists("env", "now"));
+    /// ```
+    pub fn chain(mut self, fallback: impl Into<Arc<Self>>) -> Self {
+        self.fallbacks.push(fallback.into());
+        self
+    }
+
+    /// The number of layers consulted when resolving an import.
+    fn layers(&self) -> usize {
+        1 + self
+            .fallbacks
+            .iter()
+            .map(|fallback| fallback.layers())
+            .sum::<usize>()
+    }
+
+    /// Collects the visible definitions, letting upper layers shadow the
+    /// lower ones.
+    fn collect_visible<'a>(&'a self, visible: &mut HashMap<&'a (String, String), &'a Extern>) {
+        for fallback in self.fallbacks.iter().rev() {
+            fallback.collect_visible(visible);
+        }
+        visible.extend(self.map.iter());
     }
 
     /// Register a list of externs into a namespace.
@@ -102,6 +183,58 @@ impl Imports {
         }
     }
 
+    /// Exposes every export of `instance` under the namespace `ns`, so
+    /// another module can import them.
+    ///
+    /// The externs are shared with `instance`, not copied: a memory or table
+    /// imported this way is the same one `instance` uses.
+    ///
+    /// Like [`Linker::define_instance`](crate::Linker::define_instance)
+    /// without shadowing, nothing is registered if one of the names is
+    /// already defined in this layer; chained fallbacks can still be
+    /// shadowed.
+    ///
+    /// # Usage
+    /// ```no_run
+    /// # use wasmer::{Imports, Instance, Module, Store};
+    /// # fn foo_test(store: &mut Store, a: Instance, b: Module) -> anyhow::Result<()> {
+    /// let mut imports = Imports::new();
+    /// imports.register_instance("a", &a)?;
+    /// let b = Instance::new(store, &b, &imports)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_instance(
+        &mut self,
+        ns: &str,
+        instance: &Instance,
+    ) -> Result<&mut Self, LinkerError> {
+        if let Some((name, _)) = instance
+            .exports
+            .iter()
+            .find(|(name, _)| self.map.contains_key(&(ns.to_string(), name.to_string())))
+        {
+            return Err(LinkerError::Shadowing(ns.to_string(), name.to_string()));
+        }
+        for (name, ext) in instance.exports.iter() {
+            self.define(ns, name, ext.clone());
+        }
+        Ok(self)
+    }
+
+    /// Creates the functions of `host_module` in `store` and returns them
+    /// as imports under the namespace of the host module.
+    ///
+    /// This is much cheaper than creating the same functions one by one:
+    /// see [`HostModule`].
+    pub fn from_host_module(store: &mut impl AsStoreMut, host_module: &HostModule) -> Self {
+        let mut imports = Self::new();
+        for (name, func) in host_module.materialize(store) {
+            imports.define(host_module.namespace(), name, func);
+        }
+        imports
+    }
+
     /// Add a single import with a namespace `ns` and name `name`.
     ///
     /// # Usage
@@ -120,15 +253,62 @@ impl Imports {
             .insert((ns.to_string(), name.to_string()), val.into());
     }
 
+    /// Replaces the definition for `ns` and `name` with `val`, returning
+    /// the definition that was visible before, if any.
+    ///
+    /// The new definition goes into the top layer: a definition of a
+    /// chained fallback is shadowed rather than changed, so other chains
+    /// sharing that fallback are not affected.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::{imports, Function, Store};
+    /// # let mut store = Store::default();
+    /// let mut import_object = imports! {
+    ///     "env" => {
+    ///         "log" => Function::new_typed(&mut store, |_: i32| {}),
+    ///     },
+    /// };
+    /// let real_log = Function::new_typed(&mut store, |x: i32| println!("{x}"));
+    /// let stub = import_object.replace("env", "log", real_log);
+    /// assert!(stub.is_some());
+    /// ```
+    pub fn replace(&mut self, ns: &str, name: &str, val: impl Into<Extern>) -> Option<Extern> {
+        let previous = self.get_export(ns, name);
+        self.define(ns, name, val);
+        previous
+    }
+
+    /// Removes the definition for `ns` and `name` from the top layer,
+    /// returning it.
+    ///
+    /// Chained fallbacks are shared and left untouched: if one of them
+    /// also defines `ns` and `name`, its definition becomes visible again.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::{imports, Function, Store};
+    /// # let mut store = Store::default();
+    /// let mut import_object = imports! {
+    ///     "env" => {
+    ///         "log" => Function::new_typed(&mut store, |_: i32| {}),
+    ///     },
+    /// };
+    /// assert!(import_object.remove("env", "log").is_some());
+    /// assert!(!import_object.exists("env", "log"));
+    /// ```
+    pub fn remove(&mut self, ns: &str, name: &str) -> Option<Extern> {
+        self.map.remove(&(ns.to_string(), name.to_string()))
+    }
+
     /// Returns the contents of a namespace as an `Exports`.
     ///
     /// Returns `None` if the namespace doesn't exist.
     pub fn get_namespace_exports(&self, name: &str) -> Option<Exports> {
         let ret: Exports = self
-            .map
             .iter()
-            .filter(|((ns, _), _)| ns == name)
-            .map(|((_, name), e)| (name.clone(), e.clone()))
+            .filter(|(ns, _, _)| *ns == name)
+            .map(|(_, name, e)| (name.to_string(), e.clone()))
             .collect();
         if ret.is_empty() {
             None
@@ -140,41 +320,167 @@ impl Imports {
     /// Resolve and return a vector of imports in the order they are defined in the `module`'s source code.
     ///
     /// This means the returned `Vec<Extern>` might be a subset of the imports contained in `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LinkError::Unresolved`] listing every missing import.
     #[allow(clippy::result_large_err)]
     pub fn imports_for_module(&self, module: &Module) -> Result<Vec<Extern>, LinkError> {
-        let mut ret = vec![];
+        self.resolve(module, None)
+    }
+
+    /// Like [`Imports::imports_for_module`], but also reports the imports
+    /// whose type doesn't match what the module expects.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn resolve_for_module(
+        &self,
+        store: &impl AsStoreRef,
+        module: &Module,
+    ) -> Result<Vec<Extern>, LinkError> {
+        self.resolve(module, Some(&store.as_store_ref()))
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn resolve(
+        &self,
+        module: &Module,
+        store: Option<&StoreRef<'_>>,
+    ) -> Result<Vec<Extern>, LinkError> {
+        let mut externs = vec![];
+        let mut issues = vec![];
         for import in module.imports() {
-            if let Some(imp) = self
-                .map
-                .get(&(import.module().to_string(), import.name().to_string()))
-            {
-                ret.push(imp.clone());
-            } else {
-                return Err(LinkError::Import(
-                    import.module().to_string(),
-                    import.name().to_string(),
-                    ImportError::UnknownImport(import.ty().clone()),
-                ));
+            let Some(ext) = self.lookup(import.module(), import.name()) else {
+                issues.push(LinkIssue {
+                    module: import.module().to_string(),
+                    name: import.name().to_string(),
+                    expected: import.ty().clone(),
+                    provided: None,
+                    differences: Vec::new(),
+                });
+                continue;
+            };
+            if let Some(store) = store {
+                let provided = ext.ty(store);
+                // Tables and memories may have grown past their declared
+                // minimum, which is what imports are checked against.
+                let runtime_size = match ext {
+                    Extern::Table(table) => Some(table.size(store)),
+                    Extern::Memory(memory) => Some(memory.view(store).size().0),
+                    _ => None,
+                };
+                if !provided.is_compatible_with(import.ty(), runtime_size) {
+                    issues.push(LinkIssue {
+                        module: import.module().to_string(),
+                        name: import.name().to_string(),
+                        expected: import.ty().clone(),
+                        differences: provided.incompatibilities(import.ty(), runtime_size),
+                        provided: Some(provided),
+                    });
+                    continue;
+                }
             }
+            externs.push(ext.clone());
         }
-        Ok(ret)
+
+        if issues.is_empty() {
+            Ok(externs)
+        } else {
+            Err(LinkError::Unresolved {
+                issues,
+                layers: self.layers(),
+            })
+        }
+    }
+
+    /// Returns the module name, name and expected type of every import of
+    /// `module` that is not defined in `self`, in the order the module
+    /// declares them.
+    ///
+    /// Unlike [`Imports::imports_for_module`], this reports all the missing
+    /// imports at once, which helps building better error messages.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::{imports, Module, Store};
+    /// # let store = Store::default();
+    /// let module = Module::new(&store, r#"(module (import "env" "log" (func (param i32))))"#)?;
+    /// let missing = imports! {}.list_unsatisfied(&module);
+    /// assert_eq!(missing[0].0, "env");
+    /// assert_eq!(missing[0].1, "log");
+    /// # Ok::<_, wasmer::CompileError>(())
+    /// ```
+    pub fn list_unsatisfied(&self, module: &Module) -> Vec<(String, String, ExternType)> {
+        module
+            .imports()
+            .filter(|import| !self.exists(import.module(), import.name()))
+            .map(|import| {
+                (
+                    import.module().to_string(),
+                    import.name().to_string(),
+                    import.ty().clone(),
+                )
+            })
+            .collect()
     }
 
-    /// Iterates through all the imports in this structure
+    /// Iterates through all the imports in this structure, as
+    /// `(namespace, name, extern)` tuples.
+    ///
+    /// Only the visible definitions are yielded: a definition shadowed by
+    /// an upper layer of the chain is skipped. The order is unspecified.
     pub fn iter(&self) -> ImportsIterator<'_> {
         ImportsIterator::new(self)
     }
 }
 
+/// Provides definitions for imports on demand, when instantiating a module
+/// with [`Instance::new_with_resolver`](crate::Instance::new_with_resolver).
+///
+/// The resolver is only asked for the imports the given [`Imports`] does
+/// not define. Externs it creates, such as host functions, are created in
+/// the store being instantiated into, like any other extern.
+///
+/// Closures with the same signature as [`DynamicResolver::resolve`]
+/// implement this trait.
+pub trait DynamicResolver {
+    /// Returns the definition for the import `module`.`name` of type `ty`,
+    /// or `None` to leave it unresolved.
+    fn resolve(
+        &self,
+        store: &mut StoreMut<'_>,
+        module: &str,
+        name: &str,
+        ty: &ExternType,
+    ) -> Option<Extern>;
+}
+
+impl<F> DynamicResolver for F
+where
+    F: Fn(&mut StoreMut<'_>, &str, &str, &ExternType) -> Option<Extern>,
+{
+    fn resolve(
+        &self,
+        store: &mut StoreMut<'_>,
+        module: &str,
+        name: &str,
+        ty: &ExternType,
+    ) -> Option<Extern> {
+        self(store, module, name, ty)
+    }
+}
+
 /// An iterator over module imports.
 pub struct ImportsIterator<'a> {
-    iter: std::collections::hash_map::Iter<'a, (String, String), Extern>,
+    iter: std::collections::hash_map::IntoIter<&'a (String, String), &'a Extern>,
 }
 
 impl<'a> ImportsIterator<'a> {
     pub(crate) fn new(imports: &'a Imports) -> Self {
-        let iter = imports.map.iter();
-        Self { iter }
+        let mut visible = HashMap::with_capacity(imports.map.len());
+        imports.collect_visible(&mut visible);
+        Self {
+            iter: visible.into_iter(),
+        }
     }
 }
 
@@ -193,7 +499,10 @@ impl IntoIterator for &Imports {
     type Item = ((String, String), Extern);
 
     fn into_iter(self) -> Self::IntoIter {
-        self.map.clone().into_iter()
+        self.iter()
+            .map(|(ns, name, ext)| ((ns.to_string(), name.to_string()), ext.clone()))
+            .collect::<HashMap<_, _>>()
+            .into_iter()
     }
 }
 
@@ -260,6 +569,22 @@ impl fmt::Debug for Imports {
 ///     n
 /// }
 /// ```
+///
+/// Namespaces and names can be any expression evaluating to a `&str` or a
+/// `String`, so they can be computed at runtime:
+///
+/// ```
+/// # use wasmer::{Function, Store};
+/// # let mut store = Store::default();
+/// use wasmer::imports;
+///
+/// let version = "1.2.0";
+/// let import_object = imports! {
+///     format!("plugin:foo@{version}") => {
+///         String::from("bar") => Function::new_typed(&mut store, || {})
+///     },
+/// };
+/// ```
 #[macro_export]
 macro_rules! imports {
     ( $( $ns_name:expr => $ns:tt ),* $(,)? ) => {
@@ -270,7 +595,7 @@ macro_rules! imports {
             $({
                 let namespace = $crate::import_namespace!($ns);
 
-                import_object.register_namespace($ns_name, namespace);
+                import_object.register_namespace(::core::convert::AsRef::<str>::as_ref(&$ns_name), namespace);
             })*
 
             import_object
@@ -384,6 +709,277 @@ mod test {
         };
     }
 
+    #[test]
+    fn imports_macro_accepts_computed_names() {
+        use crate::{Function, Instance, Module};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module (import "plugin:foo@1.2.0" "bar" (func)))"#,
+        )
+        .unwrap();
+
+        let version = String::from("1.2.0");
+        let namespace = format!("plugin:foo@{version}");
+        let name = "bar".to_string();
+        let import_object = imports! {
+            namespace => {
+                name => Function::new_typed(&mut store, || {}),
+            },
+        };
+        // A borrowed namespace keeps working too.
+        let _ = imports! {
+            &namespace => {
+                "bar" => Function::new_typed(&mut store, || {}),
+            },
+        };
+
+        Instance::new(&mut store, &module, &import_object).unwrap();
+    }
+
+    #[test]
+    fn list_unsatisfied() {
+        use crate::{ExternType, FunctionType, Module};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "f" (func (param i32)))
+                (import "env" "g" (global i32))
+                (import "other" "h" (func)))"#,
+        )
+        .unwrap();
+
+        let imports = imports! {
+            "env" => {
+                "g" => Global::new(&mut store, Value::I32(0)),
+            },
+        };
+        assert_eq!(
+            imports.list_unsatisfied(&module),
+            vec![
+                (
+                    "env".to_string(),
+                    "f".to_string(),
+                    ExternType::Function(FunctionType::new(vec![Type::I32], vec![]))
+                ),
+                (
+                    "other".to_string(),
+                    "h".to_string(),
+                    ExternType::Function(FunctionType::new(vec![], vec![]))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn chain_falls_back_to_base() {
+        use crate::error::LinkError;
+        use crate::{Function, Instance, InstantiationError, Module};
+        use std::sync::Arc;
+
+        let mut store = Store::default();
+        let base = Arc::new(imports! {
+            "env" => {
+                "a" => Function::new_typed(&mut store, || 1),
+                "b" => Function::new_typed(&mut store, || 2),
+            },
+        });
+        let tenant = imports! {
+            "env" => {
+                "a" => Function::new_typed(&mut store, || 10),
+            },
+        }
+        .chain(base.clone());
+        assert_eq!(tenant.iter().count(), 2);
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "a" (func $a (result i32)))
+                (import "env" "b" (func $b (result i32)))
+                (func (export "sum") (result i32) (i32.add (call $a) (call $b))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &tenant).unwrap();
+        let sum = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "sum")
+            .unwrap();
+        assert_eq!(sum.call(&mut store).unwrap(), 12);
+
+        let module = Module::new(&store, r#"(module (import "env" "c" (func)))"#).unwrap();
+        match Instance::new(&mut store, &module, &tenant) {
+            Err(InstantiationError::Link(LinkError::Unresolved { issues, layers })) => {
+                assert_eq!(layers, 2);
+                assert_eq!(
+                    (issues[0].module.as_str(), issues[0].name.as_str()),
+                    ("env", "c")
+                );
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn all_link_issues_are_reported() {
+        use crate::error::{LinkError, LinkIssue};
+        use crate::{ExternType, Function, FunctionType, Instance, InstantiationError, Module};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "a" (func (param i32)))
+                (import "env" "b" (func))
+                (import "env" "c" (global i32))
+                (import "other" "d" (memory 1)))"#,
+        )
+        .unwrap();
+        let imports = imports! {
+            "env" => {
+                "a" => Function::new_typed(&mut store, |_: i64| {}),
+            },
+        };
+
+        match Instance::new(&mut store, &module, &imports) {
+            Err(InstantiationError::Link(LinkError::Unresolved { issues, layers })) => {
+                assert_eq!(layers, 1);
+                assert_eq!(issues.len(), 4);
+                assert_eq!(
+                    issues[0],
+                    LinkIssue {
+                        module: "env".to_string(),
+                        name: "a".to_string(),
+                        expected: ExternType::Function(FunctionType::new(vec![Type::I32], vec![])),
+                        provided: Some(ExternType::Function(FunctionType::new(
+                            vec![Type::I64],
+                            vec![]
+                        ))),
+                        differences: vec!["param 0: expected i32, got i64".to_string()],
+                    }
+                );
+                let missing = issues[1..]
+                    .iter()
+                    .map(|issue| (issue.name.as_str(), issue.provided.is_none()))
+                    .collect::<Vec<_>>();
+                assert_eq!(missing, [("b", true), ("c", true), ("d", true)]);
+
+                let message = LinkError::Unresolved { issues, layers }.to_string();
+                assert!(message.starts_with("4 unresolved import(s)"));
+                assert_eq!(message.lines().count(), 5);
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn register_instance() {
+        use crate::{imports, Imports, Instance, LinkerError, Module};
+
+        let mut store = Store::default();
+        let a = Module::new(
+            &store,
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "store") (param i32 i32)
+                    (i32.store8 (local.get 0) (local.get 1))))"#,
+        )
+        .unwrap();
+        let a = Instance::new(&mut store, &a, &imports! {}).unwrap();
+
+        let mut imports = Imports::new();
+        imports.register_instance("a", &a).unwrap();
+        assert!(matches!(
+            imports.register_instance("a", &a),
+            Err(LinkerError::Shadowing(ns, _)) if ns == "a"
+        ));
+
+        let b = Module::new(
+            &store,
+            r#"(module
+                (import "a" "memory" (memory 1))
+                (import "a" "store" (func $store (param i32 i32)))
+                (func (export "run") (result i32)
+                    (call $store (i32.const 8) (i32.const 42))
+                    (i32.store8 (i32.const 9) (i32.const 7))
+                    (i32.load8_u (i32.const 8))))"#,
+        )
+        .unwrap();
+        let b = Instance::new(&mut store, &b, &imports).unwrap();
+        let run = b
+            .exports
+            .get_typed_function::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store).unwrap(), 42);
+
+        // Both instances see the same memory.
+        let memory = a.exports.get_memory("memory").unwrap();
+        let mut bytes = [0; 2];
+        memory.view(&store).read(8, &mut bytes).unwrap();
+        assert_eq!(bytes, [42, 7]);
+    }
+
+    #[test]
+    fn replace_and_remove() {
+        use crate::{imports, Function, Imports, Instance, Module};
+        use std::sync::Arc;
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "answer" (func $answer (result i32)))
+                (func (export "run") (result i32) (call $answer)))"#,
+        )
+        .unwrap();
+        let run = |store: &mut Store, imports: &Imports| {
+            let instance = Instance::new(store, &module, imports).unwrap();
+            let run = instance
+                .exports
+                .get_typed_function::<(), i32>(&*store, "run")
+                .unwrap();
+            run.call(store).unwrap()
+        };
+
+        let stub = Function::new_typed(&mut store, || 0);
+        let mut imports = imports! {
+            "env" => {
+                "answer" => stub.clone(),
+            },
+        };
+        assert_eq!(run(&mut store, &imports), 0);
+
+        let real = Function::new_typed(&mut store, || 42);
+        let previous = imports.replace("env", "answer", real.clone());
+        assert_eq!(previous, Some(Extern::Function(stub.clone())));
+        assert_eq!(imports.get("env", "answer"), Some(&Extern::Function(real)));
+        assert_eq!(imports.iter().count(), 1);
+        assert_eq!(run(&mut store, &imports), 42);
+
+        // Removing a definition of the top layer uncovers the fallback.
+        let base = Arc::new(imports! {
+            "env" => {
+                "answer" => stub.clone(),
+            },
+        });
+        let mut chained = imports.clone().chain(base.clone());
+        assert_eq!(run(&mut store, &chained), 42);
+        assert!(chained.remove("env", "answer").is_some());
+        assert_eq!(chained.remove("env", "answer"), None);
+        assert_eq!(run(&mut store, &chained), 0);
+
+        // Replacing a definition of a fallback only shadows it.
+        let mut chained = Imports::new().chain(base.clone());
+        let doubled = Function::new_typed(&mut store, || 84);
+        let previous = chained.replace("env", "answer", doubled);
+        assert_eq!(previous, Some(Extern::Function(stub)));
+        assert_eq!(run(&mut store, &chained), 84);
+        assert_eq!(run(&mut store, &base), 0);
+    }
+
     #[test]
     fn chaining_works() {
         let mut store = Store::default();
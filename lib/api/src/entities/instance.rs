@@ -1,7 +1,13 @@
 use crate::{
-    error::InstantiationError, exports::Exports, imports::Imports, macros::backend::gen_rt_ty,
-    module::Module, store::AsStoreMut, Extern,
+    error::{BackendMismatchError, InstantiationError, LinkError, RuntimeError},
+    exports::Exports,
+    imports::{DynamicResolver, Imports},
+    macros::backend::gen_rt_ty,
+    module::Module,
+    store::{AsStoreMut, AsStoreRef},
+    Extern, ExternType, Function, Memory,
 };
+use wasmer_types::MemoryIndex;
 
 /// A WebAssembly Instance is a stateful, executable
 /// instance of a WebAssembly [`Module`].
@@ -56,6 +62,11 @@ impl Instance {
         module: &Module,
         imports: &Imports,
     ) -> Result<Self, InstantiationError> {
+        let used = module
+            .imports()
+            .filter_map(|import| imports.get_export(import.module(), import.name()))
+            .collect::<Vec<_>>();
+        check_backends(store, module, &used)?;
         let (_inner, exports) = match &store.as_store_mut().inner.store {
             #[cfg(feature = "sys")]
             crate::BackendStore::Sys(_) => {
@@ -99,6 +110,167 @@ impl Instance {
         })
     }
 
+    /// Creates a new `Instance` of a `wamr` store like [`Instance::new`],
+    /// with the stack and heap sizes of `config` instead of the ones of the
+    /// store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InstantiationError::InsufficientResources`] if WAMR can't
+    /// allocate the stack or the heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `store` is not a `wamr` store.
+    #[cfg(feature = "wamr")]
+    pub fn new_wamr_with_config(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        imports: &Imports,
+        config: crate::backend::wamr::InstanceConfig,
+    ) -> Result<Self, InstantiationError> {
+        assert!(
+            store.as_store_ref().inner.store.is_wamr(),
+            "Not a `wamr` store!"
+        );
+        let used = module
+            .imports()
+            .filter_map(|import| imports.get_export(import.module(), import.name()))
+            .collect::<Vec<_>>();
+        check_backends(store, module, &used)?;
+        let (inner, exports) = crate::backend::wamr::instance::Instance::new_with_config(
+            store, module, imports, config,
+        )?;
+        Ok(Self {
+            _inner: crate::BackendInstance::Wamr(inner),
+            module: module.clone(),
+            exports,
+        })
+    }
+
+    /// Creates a new `Instance` like [`Instance::new`], asking `resolver`
+    /// for every import that `imports` does not define.
+    ///
+    /// Definitions from `imports` always take precedence. The externs the
+    /// resolver returns are type-checked like the ones from `imports`.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Extern, ExternType, Function, Instance, Module, Store, StoreMut};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, r#"(module (import "host" "nop" (func)))"#)?;
+    /// let resolver = |store: &mut StoreMut<'_>, _: &str, _: &str, ty: &ExternType| match ty {
+    ///     ExternType::Function(ty) => {
+    ///         Some(Extern::from(Function::new(store, ty, |_| Ok(vec![]))))
+    ///     }
+    ///     _ => None,
+    /// };
+    /// let instance = Instance::new_with_resolver(&mut store, &module, &imports! {}, &resolver)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The function can return [`InstantiationError`]s, as
+    /// [`Instance::new`] does. Imports the resolver leaves unresolved are
+    /// reported as link errors.
+    #[allow(clippy::result_large_err)]
+    pub fn new_with_resolver(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        imports: &Imports,
+        resolver: &dyn DynamicResolver,
+    ) -> Result<Self, InstantiationError> {
+        let mut resolved = Imports::new();
+        for import in module.imports() {
+            if imports.exists(import.module(), import.name()) {
+                continue;
+            }
+            let ext = resolver.resolve(
+                &mut store.as_store_mut(),
+                import.module(),
+                import.name(),
+                import.ty(),
+            );
+            if let Some(ext) = ext {
+                resolved.define(import.module(), import.name(), ext);
+            }
+        }
+        if resolved.map.is_empty() {
+            return Self::new(store, module, imports);
+        }
+        Self::new(store, module, &resolved.chain(imports.clone()))
+    }
+
+    /// Creates a new `Instance` like [`Instance::new`], leaving the function
+    /// imports missing from `imports` to be linked later with
+    /// [`Instance::link_with`].
+    ///
+    /// Until they are linked, the missing functions trap when called. Other
+    /// kinds of imports must all be provided.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Function, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(
+    ///     &store,
+    ///     r#"(module
+    ///         (import "plugin" "answer" (func $answer (result i32)))
+    ///         (func (export "run") (result i32) call $answer))"#,
+    /// )?;
+    /// let instance = Instance::new_allow_unresolved(&mut store, &module, &imports! {})?;
+    /// let run = instance.exports.get_typed_function::<(), i32>(&store, "run")?;
+    /// assert!(run.call(&mut store).is_err());
+    ///
+    /// let answer = Function::new_typed(&mut store, || 42);
+    /// instance.link_with(&mut store, "plugin", "answer", answer.into())?;
+    /// assert_eq!(run.call(&mut store)?, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Only the `sys` backend can link imports after instantiation; the
+    /// other backends return [`InstantiationError::Unsupported`].
+    #[allow(clippy::result_large_err)]
+    pub fn new_allow_unresolved(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        imports: &Imports,
+    ) -> Result<Self, InstantiationError> {
+        match &store.as_store_mut().inner.store {
+            #[cfg(feature = "sys")]
+            crate::BackendStore::Sys(_) => {}
+            _ => {
+                return Err(InstantiationError::Unsupported(
+                    LINK_AFTER_INSTANTIATION.to_string(),
+                ))
+            }
+        }
+
+        let unresolved = std::cell::RefCell::new(Vec::new());
+        let placeholder = |store: &mut crate::StoreMut<'_>,
+                           module: &str,
+                           name: &str,
+                           ty: &wasmer_types::ExternType| {
+            let wasmer_types::ExternType::Function(ty) = ty else {
+                return None;
+            };
+            unresolved
+                .borrow_mut()
+                .push((module.to_string(), name.to_string()));
+            let message = format!("import `{module}`.`{name}` is not linked yet");
+            let func = Function::new(store, ty, move |_| Err(RuntimeError::new(message.clone())));
+            Some(Extern::Function(func))
+        };
+        let instance = Self::new_with_resolver(store, module, imports, &placeholder)?;
+        for (module, name) in unresolved.into_inner() {
+            instance._inner.mark_unlinked(store, &module, &name);
+        }
+        Ok(instance)
+    }
+
     /// Creates a new `Instance` from a WebAssembly [`Module`] and a
     /// vector of imports.
     ///
@@ -115,6 +287,7 @@ impl Instance {
         module: &Module,
         externs: &[Extern],
     ) -> Result<Self, InstantiationError> {
+        check_backends(store, module, externs)?;
         let (_inner, exports) = match &store.as_store_mut().inner.store {
             #[cfg(feature = "sys")]
             crate::BackendStore::Sys(_) => {
@@ -164,10 +337,257 @@ impl Instance {
         })
     }
 
+    /// Creates a new `Instance` like [`Instance::new`], but without running
+    /// the module's `start` function.
+    ///
+    /// The start function, if the module defines one, is returned so it
+    /// can be invoked later with [`StartFunction::call`], or never. This
+    /// lets tools inspect or patch the instance's memories and tables
+    /// before any guest code runs.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, "(module (func $start) (start $start))")?;
+    /// let (instance, start) = Instance::new_without_start(&mut store, &module, &imports! {})?;
+    /// // Inspect `instance` here.
+    /// if let Some(start) = start {
+    ///     start.call(&mut store)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The function can return link errors, as [`Instance::new`] does.
+    ///
+    /// Only the `sys` backend can defer the start function; the other
+    /// backends return [`InstantiationError::Unsupported`].
+    #[allow(clippy::result_large_err)]
+    pub fn new_without_start(
+        store: &mut impl AsStoreMut,
+        module: &Module,
+        imports: &Imports,
+    ) -> Result<(Self, Option<StartFunction>), InstantiationError> {
+        let used = module
+            .imports()
+            .filter_map(|import| imports.get_export(import.module(), import.name()))
+            .collect::<Vec<_>>();
+        check_backends(store, module, &used)?;
+        let (_inner, exports) = match &store.as_store_mut().inner.store {
+            #[cfg(feature = "sys")]
+            crate::BackendStore::Sys(_) => {
+                let externs = imports
+                    .resolve_for_module(store, module)
+                    .map_err(InstantiationError::Link)?;
+                let (i, e) = crate::backend::sys::instance::Instance::new_without_start(
+                    store, module, &externs,
+                )?;
+                (crate::BackendInstance::Sys(i), e)
+            }
+            _ => {
+                return Err(InstantiationError::Unsupported(
+                    "deferring the start function is only supported by the `sys` backend"
+                        .to_string(),
+                ))
+            }
+        };
+
+        let instance = Self {
+            _inner,
+            module: module.clone(),
+            exports,
+        };
+        let start = instance.start_function(store).map(StartFunction);
+        Ok((instance, start))
+    }
+
     /// Gets the [`Module`] associated with this instance.
     pub fn module(&self) -> &Module {
         &self.module
     }
+
+    /// Returns the main memory of this instance, following the usual
+    /// conventions of WASI-style modules.
+    ///
+    /// The memory is looked up in this order:
+    ///  1. the export named `memory`;
+    ///  2. the only exported memory, if the instance exports exactly one;
+    ///  3. the only imported memory, if the module imports exactly one (see
+    ///     [`Module::memory_import_requirements`]).
+    ///
+    /// Returns `None` if none of these applies, for instance if the module
+    /// has no memory or exports several, none of them named `memory`.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, r#"(module (memory (export "mem") 1))"#)?;
+    /// let instance = Instance::new(&mut store, &module, &imports! {})?;
+    /// let memory = instance.main_memory(&mut store).unwrap();
+    /// assert_eq!(memory.view(&store).size().0, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Note: only the `sys` backend can reach an imported memory that isn't
+    /// exported; the other backends stop at the second step.
+    pub fn main_memory(&self, store: &mut impl AsStoreMut) -> Option<Memory> {
+        if let Ok(memory) = self.exports.get_memory("memory") {
+            return Some(memory.clone());
+        }
+        let mut exported = self.exports.iter().memories();
+        match (exported.next(), exported.next()) {
+            (Some((_, memory)), None) => return Some(memory.clone()),
+            (Some(_), Some(_)) => return None,
+            _ => {}
+        }
+        // Imported memories come first in the memory index space.
+        self.module.memory_import_requirements()?;
+        self._inner.memory(store, MemoryIndex::from_u32(0))
+    }
+
+    /// Returns the first memory exported by this instance, whatever its
+    /// name.
+    ///
+    /// Most modules export a single memory, usually named `memory`. See
+    /// [`Instance::main_memory`] to look it up by the usual conventions
+    /// instead.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, r#"(module (memory (export "heap") 1))"#)?;
+    /// let instance = Instance::new(&mut store, &module, &imports! {})?;
+    /// let memory = instance.memory().unwrap();
+    /// assert_eq!(memory.view(&store).size().0, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memory(&self) -> Option<Memory> {
+        self.exports
+            .iter()
+            .memories()
+            .next()
+            .map(|(_, memory)| memory.clone())
+    }
+
+    /// Returns the type of the export `name` of this instance, or `None` if
+    /// it has no such export.
+    ///
+    /// The type is read from the module, without creating the [`Extern`]
+    /// or needing the store.
+    ///
+    /// ```
+    /// # use wasmer::{imports, ExternType, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, r#"(module (memory (export "heap") 1))"#)?;
+    /// let instance = Instance::new(&mut store, &module, &imports! {})?;
+    /// assert!(matches!(instance.get_export_type("heap"), Some(ExternType::Memory(_))));
+    /// assert!(instance.get_export_type("missing").is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_export_type(&self, name: &str) -> Option<ExternType> {
+        self.module
+            .exports()
+            .find(|export| export.name() == name)
+            .map(|export| export.ty().clone())
+    }
+
+    /// Links the function import `module_name`.`name` of an instance created
+    /// with [`Instance::new_allow_unresolved`] to `extern_val`.
+    ///
+    /// Calls the instance makes to the import from then on go to
+    /// `extern_val`. References to the import taken before linking, for
+    /// instance stored in a table, keep pointing to the placeholder.
+    ///
+    /// Only functions with a static signature can be linked: functions
+    /// created with [`Function::new_typed`] and friends, or exported by
+    /// another instance.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`LinkError::AlreadyLinked`] if the import was satisfied when
+    /// instantiating, or by an earlier call, and [`LinkError::Import`] if
+    /// the module has no such import or `extern_val` has the wrong type.
+    ///
+    /// Only the `sys` backend can link imports after instantiation; the
+    /// other backends return [`LinkError::Unsupported`].
+    #[allow(clippy::result_large_err)]
+    pub fn link_with(
+        &self,
+        store: &mut impl AsStoreMut,
+        module_name: &str,
+        name: &str,
+        extern_val: Extern,
+    ) -> Result<(), LinkError> {
+        self._inner.link_with(store, module_name, name, extern_val)
+    }
+
+    /// Returns the module's `start` function, if it defines one.
+    ///
+    /// The function is returned even if it isn't exported, so it can be
+    /// called again with [`Instance::call_start`].
+    ///
+    /// Note: only the `sys` backend can reach a start function that isn't
+    /// exported; the other backends always return `None`.
+    pub fn start_function(&self, store: &impl AsStoreRef) -> Option<Function> {
+        self._inner.start_function(store)
+    }
+
+    /// Invokes the module's `start` function, if it defines one.
+    ///
+    /// The start function already runs as part of [`Instance::new`]; this
+    /// allows calling it again, for instance to retry after a trap the host
+    /// has recovered from.
+    ///
+    /// ```
+    /// # use wasmer::{imports, Instance, Module, Store};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut store = Store::default();
+    /// let module = Module::new(&store, "(module (func $start) (start $start))")?;
+    /// let instance = Instance::new(&mut store, &module, &imports! {})?;
+    /// assert!(instance.start_function(&store).is_some());
+    /// instance.call_start(&mut store)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Only the `sys` backend can call the start function again; the other
+    /// backends return an error.
+    pub fn call_start(&self, store: &mut impl AsStoreMut) -> Result<(), RuntimeError> {
+        match &self._inner {
+            #[cfg(feature = "sys")]
+            BackendInstance::Sys(_) => {}
+            #[allow(unreachable_patterns)]
+            _ => return Err(RuntimeError::new(CALL_START)),
+        }
+
+        match self.start_function(store) {
+            Some(start) => start.call(store, &[]).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Checks that `module` and `externs` come from the backend of `store`,
+/// which the backends otherwise assume and panic on.
+fn check_backends(
+    store: &impl AsStoreRef,
+    module: &Module,
+    externs: &[Extern],
+) -> Result<(), InstantiationError> {
+    let expected = store.as_store_ref().inner.store.backend_name();
+    std::iter::once(module.0.backend_name())
+        .chain(externs.iter().map(Extern::backend_name))
+        .try_for_each(|found| BackendMismatchError::check(expected, found))
+        .map_err(InstantiationError::BackendMismatch)
 }
 
 impl std::fmt::Debug for Instance {
@@ -178,5 +598,490 @@ impl std::fmt::Debug for Instance {
     }
 }
 
+/// The `start` function of an instance created with
+/// [`Instance::new_without_start`], left to be invoked by the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartFunction(Function);
+
+impl StartFunction {
+    /// Returns the start function as a regular [`Function`].
+    pub fn function(&self) -> &Function {
+        &self.0
+    }
+
+    /// Invokes the start function.
+    ///
+    /// A trap raised by the start function is returned as a regular
+    /// [`RuntimeError`].
+    pub fn call(&self, store: &mut impl AsStoreMut) -> Result<(), RuntimeError> {
+        self.0.call(store, &[]).map(|_| ())
+    }
+}
+
 /// An enumeration of all the possible instances kind supported by the runtimes.
 gen_rt_ty!(Instance @derives Clone, PartialEq, Eq);
+
+/// The reason the backends other than `sys` fail to link imports after
+/// instantiation.
+const LINK_AFTER_INSTANTIATION: &str =
+    "linking imports after instantiation is only supported by the `sys` backend";
+
+/// The reason the backends other than `sys` fail to call the start function
+/// again.
+const CALL_START: &str = "calling the start function again is only supported by the `sys` backend";
+
+impl BackendInstance {
+    fn start_function(&self, store: &impl AsStoreRef) -> Option<Function> {
+        match self {
+            #[cfg(feature = "sys")]
+            Self::Sys(s) => s.start_function(store),
+            _ => None,
+        }
+    }
+
+    fn mark_unlinked(&self, store: &mut impl AsStoreMut, module: &str, name: &str) {
+        match self {
+            #[cfg(feature = "sys")]
+            Self::Sys(s) => s.mark_unlinked(store, module, name),
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn link_with(
+        &self,
+        store: &mut impl AsStoreMut,
+        module: &str,
+        name: &str,
+        extern_val: Extern,
+    ) -> Result<(), LinkError> {
+        match self {
+            #[cfg(feature = "sys")]
+            Self::Sys(s) => s.link_with(store, module, name, extern_val),
+            _ => Err(LinkError::Unsupported(LINK_AFTER_INSTANTIATION.to_string())),
+        }
+    }
+
+    fn memory(&self, store: &mut impl AsStoreMut, index: MemoryIndex) -> Option<Memory> {
+        match self {
+            #[cfg(feature = "sys")]
+            Self::Sys(s) => s.memory(store, index),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{imports, Instance, InstantiationError, Module, Store};
+
+    #[test]
+    fn first_exported_memory() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "f"))
+                (memory (export "heap") 2))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let memory = instance.memory().unwrap();
+        assert_eq!(memory.view(&store).size().0, 2);
+
+        let module = Module::new(&store, r#"(module (func (export "f")))"#).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        assert!(instance.memory().is_none());
+    }
+
+    #[test]
+    fn export_types() {
+        use crate::{ExternType, FunctionType, GlobalType, Mutability, Type};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "f") (param i32) (result i64) (i64.const 0))
+                (global (export "g") (mut f32) (f32.const 0))
+                (table (export "t") 1 funcref)
+                (memory (export "m") 1 2))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+
+        assert_eq!(
+            instance.get_export_type("f"),
+            Some(ExternType::Function(FunctionType::new(
+                [Type::I32],
+                [Type::I64]
+            )))
+        );
+        assert_eq!(
+            instance.get_export_type("g"),
+            Some(ExternType::Global(GlobalType::new(
+                Type::F32,
+                Mutability::Var
+            )))
+        );
+        assert!(matches!(
+            instance.get_export_type("t"),
+            Some(ExternType::Table(ty)) if ty.ty == Type::FuncRef
+        ));
+        assert!(matches!(
+            instance.get_export_type("m"),
+            Some(ExternType::Memory(ty)) if ty.maximum.map(|pages| pages.0) == Some(2)
+        ));
+        assert_eq!(instance.get_export_type("missing"), None);
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_instance_config() {
+        use crate::{wamr::InstanceConfig, TypedFunction};
+
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        assert_eq!(store.wamr_instance_config(), InstanceConfig::default());
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func $depth (export "depth") (param i32) (result i32)
+                    local.get 0
+                    i32.eqz
+                    if (result i32)
+                        i32.const 0
+                    else
+                        local.get 0
+                        i32.const 1
+                        i32.sub
+                        call $depth
+                        i32.const 1
+                        i32.add
+                    end))"#,
+        )
+        .unwrap();
+
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let depth: TypedFunction<i32, i32> = instance
+            .exports
+            .get_typed_function(&store, "depth")
+            .unwrap();
+        assert_eq!(depth.call(&mut store, 2000).unwrap(), 2000);
+
+        let small = InstanceConfig {
+            stack_size: 4 * 1024,
+            ..Default::default()
+        };
+        let instance =
+            Instance::new_wamr_with_config(&mut store, &module, &imports! {}, small).unwrap();
+        let depth: TypedFunction<i32, i32> = instance
+            .exports
+            .get_typed_function(&store, "depth")
+            .unwrap();
+        assert!(depth.call(&mut store, 2000).is_err());
+
+        store.set_wamr_instance_config(small);
+        assert_eq!(store.wamr_instance_config(), small);
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let depth: TypedFunction<i32, i32> = instance
+            .exports
+            .get_typed_function(&store, "depth")
+            .unwrap();
+        assert!(depth.call(&mut store, 2000).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_insufficient_resources() {
+        use crate::wamr::InstanceConfig;
+
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        // The start function needs a stack, which WAMR can't allocate.
+        let module = Module::new(&store, "(module (func $start) (start $start))").unwrap();
+        let huge = InstanceConfig {
+            stack_size: u32::MAX,
+            ..Default::default()
+        };
+        match Instance::new_wamr_with_config(&mut store, &module, &imports! {}, huge) {
+            Err(InstantiationError::InsufficientResources(message)) => {
+                assert!(message.ends_with("allocate memory failed"), "{message}")
+            }
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "sys", feature = "wamr"))]
+    fn backend_mismatch() {
+        use crate::{
+            sys::NativeEngineExt, BackendMismatchError, Engine, Function, InstantiationError, Value,
+        };
+
+        let mut wamr = Store::new(crate::backend::wamr::Wamr::new());
+        let mut sys = Store::new(Engine::headless());
+        let expected = BackendMismatchError {
+            expected: "sys",
+            found: "wamr",
+        };
+
+        let answer = Function::new_typed(&mut wamr, || 42);
+        let error = answer.call(&mut sys, &[]).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<BackendMismatchError>(),
+            Some(&expected)
+        );
+        assert_eq!(answer.call(&mut wamr, &[]).unwrap()[0], Value::I32(42));
+        let typed = answer.typed::<(), i32>(&wamr).unwrap();
+        assert!(typed
+            .call(&mut sys)
+            .unwrap_err()
+            .is::<BackendMismatchError>());
+
+        let module = Module::new(
+            &wamr,
+            r#"(module (import "env" "answer" (func (result i32))))"#,
+        )
+        .unwrap();
+        let imports = imports! { "env" => { "answer" => answer } };
+        match Instance::new(&mut sys, &module, &imports) {
+            Err(InstantiationError::BackendMismatch(error)) => assert_eq!(error, expected),
+            other => panic!("expected a backend mismatch, got {other:?}"),
+        }
+    }
+
+    const WAT: &str = r#"(module
+        (memory (export "memory") 1)
+        (func $start (i32.store8 (i32.const 0) (i32.const 42)))
+        (start $start))"#;
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only `sys` can defer the start function"
+    )]
+    fn new_without_start() {
+        let mut store = Store::default();
+        let module = Module::new(&store, WAT).unwrap();
+        let (instance, start) =
+            Instance::new_without_start(&mut store, &module, &imports! {}).unwrap();
+        let memory = instance.exports.get_memory("memory").unwrap();
+
+        let mut byte = [0u8];
+        memory.view(&store).read(0, &mut byte).unwrap();
+        assert_eq!(byte[0], 0);
+
+        start.unwrap().call(&mut store).unwrap();
+        memory.view(&store).read(0, &mut byte).unwrap();
+        assert_eq!(byte[0], 42);
+
+        // The default path still runs the start function.
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let memory = instance.exports.get_memory("memory").unwrap();
+        memory.view(&store).read(0, &mut byte).unwrap();
+        assert_eq!(byte[0], 42);
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_new_allow_unresolved() {
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        let module = Module::new(&store, WAT).unwrap();
+        assert!(matches!(
+            Instance::new_allow_unresolved(&mut store, &module, &imports! {}),
+            Err(InstantiationError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_new_without_start() {
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        let module = Module::new(&store, WAT).unwrap();
+        assert!(matches!(
+            Instance::new_without_start(&mut store, &module, &imports! {}),
+            Err(InstantiationError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_call_start() {
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        let module = Module::new(&store, WAT).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        assert!(instance.start_function(&store).is_none());
+        assert!(instance.call_start(&mut store).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only `sys` can defer the start function"
+    )]
+    fn deferred_start_trap() {
+        let mut store = Store::default();
+        let module =
+            Module::new(&store, "(module (func $start unreachable) (start $start))").unwrap();
+        let (_instance, start) =
+            Instance::new_without_start(&mut store, &module, &imports! {}).unwrap();
+        let err = start.unwrap().call(&mut store).unwrap_err();
+        assert_eq!(
+            err.to_trap(),
+            Some(wasmer_types::TrapCode::UnreachableCodeReached)
+        );
+    }
+
+    #[test]
+    fn dynamic_resolver() {
+        use crate::{Extern, ExternType, Function, StoreMut, Value};
+        use std::sync::{Arc, Mutex};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "double" (func $double (param i32) (result i32)))
+                (import "host:log" "info" (func $info (param i32)))
+                (import "host:log" "warn" (func $warn (param i32 i32)))
+                (func (export "run")
+                    (call $info (call $double (i32.const 21)))
+                    (call $warn (i32.const 1) (i32.const 2))))"#,
+        )
+        .unwrap();
+        let imports = imports! {
+            "env" => {
+                "double" => Function::new_typed(&mut store, |x: i32| x * 2),
+            },
+        };
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        let resolver = {
+            let (log, asked) = (log.clone(), asked.clone());
+            move |store: &mut StoreMut<'_>, module: &str, name: &str, ty: &ExternType| {
+                asked.lock().unwrap().push(format!("{module}.{name}"));
+                let (ExternType::Function(ty), true) = (ty, module.starts_with("host:")) else {
+                    return None;
+                };
+                let (log, name) = (log.clone(), name.to_string());
+                let func = Function::new(store, ty, move |args| {
+                    let args = args.iter().map(Value::unwrap_i32).collect::<Vec<_>>();
+                    log.lock().unwrap().push(format!("{name}{args:?}"));
+                    Ok(vec![])
+                });
+                Some(Extern::Function(func))
+            }
+        };
+
+        let instance =
+            Instance::new_with_resolver(&mut store, &module, &imports, &resolver).unwrap();
+        assert_eq!(*asked.lock().unwrap(), ["host:log.info", "host:log.warn"]);
+
+        let run = instance.exports.get_function("run").unwrap();
+        run.call(&mut store, &[]).unwrap();
+        assert_eq!(*log.lock().unwrap(), ["info[42]", "warn[1, 2]"]);
+
+        // Imports outside of `host:` are still reported as missing.
+        let module = Module::new(&store, r#"(module (import "env" "missing" (func)))"#).unwrap();
+        let err =
+            Instance::new_with_resolver(&mut store, &module, &imports, &resolver).unwrap_err();
+        assert!(matches!(err, crate::InstantiationError::Link(_)));
+    }
+
+    #[test]
+    fn main_memory() {
+        let mut store = Store::default();
+
+        let module = Module::new(&store, r#"(module (memory (export "memory") 2))"#).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let memory = instance.main_memory(&mut store).unwrap();
+        assert_eq!(memory.view(&store).size().0, 2);
+
+        let module = Module::new(&store, r#"(module (memory (export "heap") 3))"#).unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let memory = instance.main_memory(&mut store).unwrap();
+        assert_eq!(memory.view(&store).size().0, 3);
+
+        let module = Module::new(&store, "(module)").unwrap();
+        assert!(module.memory_import_requirements().is_none());
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        assert!(instance.main_memory(&mut store).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only `sys` can reach unexported imported memories"
+    )]
+    fn main_memory_imported() {
+        use crate::{Memory, MemoryType};
+
+        let mut store = Store::default();
+        let module = Module::new(&store, r#"(module (import "env" "memory" (memory 1)))"#).unwrap();
+        let requirements = module.memory_import_requirements().unwrap();
+        assert_eq!(requirements.name(), "memory");
+
+        let host = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+        let imports = imports! { "env" => { "memory" => host.clone() } };
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+        let memory = instance.main_memory(&mut store).unwrap();
+        memory.view(&store).write(0, &[42]).unwrap();
+        let mut byte = [0];
+        host.view(&store).read(0, &mut byte).unwrap();
+        assert_eq!(byte, [42]);
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only `sys` can link imports after instantiation"
+    )]
+    fn link_with() {
+        use crate::{Function, LinkError};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "base" (func $base (result i32)))
+                (import "plugin" "add" (func $add (param i32 i32) (result i32)))
+                (func (export "run") (result i32)
+                    (call $add (call $base) (i32.const 2))))"#,
+        )
+        .unwrap();
+        let base = Function::new_typed(&mut store, || 40);
+        let imports = imports! { "env" => { "base" => base.clone() } };
+        let instance = Instance::new_allow_unresolved(&mut store, &module, &imports).unwrap();
+        let run = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "run")
+            .unwrap();
+        let err = run.call(&mut store).unwrap_err();
+        assert!(err.message().contains("not linked"), "{err}");
+
+        let wrong = Function::new_typed(&mut store, |a: i32| a);
+        assert!(matches!(
+            instance.link_with(&mut store, "plugin", "add", wrong.into()),
+            Err(LinkError::Import(..))
+        ));
+        assert!(matches!(
+            instance.link_with(&mut store, "plugin", "missing", base.clone().into()),
+            Err(LinkError::Import(..))
+        ));
+        assert!(matches!(
+            instance.link_with(&mut store, "env", "base", base.into()),
+            Err(LinkError::AlreadyLinked(..))
+        ));
+
+        let add = Function::new_typed(&mut store, |a: i32, b: i32| a + b);
+        instance
+            .link_with(&mut store, "plugin", "add", add.clone().into())
+            .unwrap();
+        assert_eq!(run.call(&mut store).unwrap(), 42);
+        assert!(matches!(
+            instance.link_with(&mut store, "plugin", "add", add.into()),
+            Err(LinkError::AlreadyLinked(..))
+        ));
+    }
+}
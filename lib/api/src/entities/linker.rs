@@ -0,0 +1,522 @@
+//! The [`Linker`] resolves module imports from definitions collected over
+//! time, rather than from a single [`Imports`] object built at one call site.
+
+use crate::{
+    AsStoreMut, Extern, Function, HostFunction, Imports, Instance, InstantiationError, Module,
+    StoreMut, WasmTypeList, WithoutEnv,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+use wasmer_types::ExternType;
+
+/// The error returned by the [`Linker`] methods.
+#[derive(Error, Debug, Clone)]
+pub enum LinkerError {
+    /// A definition already exists for this name and shadowing is disabled.
+    #[error("import `{0}`.`{1}` is already defined")]
+    Shadowing(String, String),
+    /// The definition being aliased does not exist.
+    #[error("import `{0}`.`{1}` is not defined")]
+    Undefined(String, String),
+    /// The namespace was already defined as a whole, such as by a previous
+    /// call registering a WASI version.
+    #[error("namespace `{0}` is already defined")]
+    NamespaceDefined(String),
+    /// The module requires imports the linker has no definition for.
+    ///
+    /// All the unresolved imports are reported at once, in the order the
+    /// module declares them.
+    #[error("unresolved imports: {}", format_missing(.0))]
+    MissingImports(Vec<(String, String, ExternType)>),
+    /// All the imports were resolved, but instantiating the module failed.
+    #[error(transparent)]
+    Instantiation(#[from] InstantiationError),
+}
+
+fn format_missing(missing: &[(String, String, ExternType)]) -> String {
+    missing
+        .iter()
+        .map(|(module, name, ty)| format!("`{module}`.`{name}` ({ty:?})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+type LazyDefinition = Arc<dyn Fn(&mut StoreMut<'_>) -> Extern>;
+
+#[derive(Clone)]
+enum Definition {
+    Extern(Extern),
+    Lazy(LazyDefinition),
+}
+
+impl fmt::Debug for Definition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Extern(ext) => ext.fmt(f),
+            Self::Lazy(_) => f.write_str("<lazy>"),
+        }
+    }
+}
+
+/// A `Linker` collects definitions for module imports one by one and
+/// instantiates modules against them.
+///
+/// Unlike [`Imports`], a `Linker` can be filled in from several places,
+/// re-export the exports of an existing [`Instance`], alias definitions
+/// under another name, and defer the creation of a definition until a
+/// module actually imports it.
+///
+/// By default, defining the same name twice is an error; see
+/// [`Linker::allow_shadowing`].
+///
+/// # Usage
+///
+/// ```no_run
+/// # use wasmer::{Function, Linker, Module, Store};
+/// # fn foo_test(store: &mut Store, module: Module) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut linker = Linker::new();
+/// linker.define("env", "log", Function::new_typed(store, |x: i32| println!("{x}")))?;
+/// linker.define_func(store, "env", "answer", || 42)?;
+/// linker.define_lazy("env", "abort", |store| Function::new_typed(store, || {}))?;
+/// linker.alias("env", "log", "console", "log")?;
+///
+/// let instance = linker.instantiate(store, &module)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Linker {
+    map: HashMap<(String, String), Definition>,
+    apis: HashSet<String>,
+    allow_shadowing: bool,
+}
+
+impl Linker {
+    /// Creates a new, empty `Linker`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Configures whether a definition may replace an existing one with the
+    /// same module and name.
+    ///
+    /// Shadowing is disabled by default.
+    pub fn allow_shadowing(&mut self, allow: bool) -> &mut Self {
+        self.allow_shadowing = allow;
+        self
+    }
+
+    /// Returns true if a definition exists for the given module and name.
+    pub fn contains(&self, module: &str, name: &str) -> bool {
+        self.map
+            .contains_key(&(module.to_string(), name.to_string()))
+    }
+
+    /// Returns true if at least one definition exists in the namespace
+    /// `module`.
+    pub fn defines_namespace(&self, module: &str) -> bool {
+        self.map.keys().any(|(m, _)| m == module)
+    }
+
+    /// Records that the host API `name`, such as a WASI version, was
+    /// defined in this linker.
+    ///
+    /// Returns false if it already was. The linker doesn't look at the
+    /// definitions: the APIs are the ones recorded by the code defining
+    /// them, see [`Linker::has_api`].
+    pub fn record_api(&mut self, name: &str) -> bool {
+        self.apis.insert(name.to_string())
+    }
+
+    /// Returns true if the host API `name` was recorded with
+    /// [`Linker::record_api`].
+    pub fn has_api(&self, name: &str) -> bool {
+        self.apis.contains(name)
+    }
+
+    fn insert(&mut self, module: &str, name: &str, def: Definition) -> Result<(), LinkerError> {
+        let key = (module.to_string(), name.to_string());
+        if !self.allow_shadowing && self.map.contains_key(&key) {
+            return Err(LinkerError::Shadowing(key.0, key.1));
+        }
+        self.map.insert(key, def);
+        Ok(())
+    }
+
+    /// Defines `module`.`name` as the given extern.
+    pub fn define(
+        &mut self,
+        module: &str,
+        name: &str,
+        item: impl Into<Extern>,
+    ) -> Result<&mut Self, LinkerError> {
+        self.insert(module, name, Definition::Extern(item.into()))?;
+        Ok(self)
+    }
+
+    /// Defines `module`.`name` as a new host function of `store`, created
+    /// from `func` like [`Function::new_typed`].
+    pub fn define_func<F, Args, Rets>(
+        &mut self,
+        store: &mut impl AsStoreMut,
+        module: &str,
+        name: &str,
+        func: F,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        F: HostFunction<(), Args, Rets, WithoutEnv> + 'static + Send + Sync,
+        Args: WasmTypeList,
+        Rets: WasmTypeList,
+    {
+        // Check first, to not create a function that is thrown away.
+        if !self.allow_shadowing && self.contains(module, name) {
+            return Err(LinkerError::Shadowing(module.to_string(), name.to_string()));
+        }
+        self.define(module, name, Function::new_typed(store, func))
+    }
+
+    /// Defines `module`.`name` with a closure creating the extern.
+    ///
+    /// The closure is only called when instantiating a module that imports
+    /// this name, once per instantiation, with the store being instantiated
+    /// into.
+    pub fn define_lazy<F, E>(
+        &mut self,
+        module: &str,
+        name: &str,
+        func: F,
+    ) -> Result<&mut Self, LinkerError>
+    where
+        F: Fn(&mut StoreMut<'_>) -> E + 'static,
+        E: Into<Extern>,
+    {
+        let lazy: LazyDefinition = Arc::new(move |store| func(store).into());
+        self.insert(module, name, Definition::Lazy(lazy))?;
+        Ok(self)
+    }
+
+    /// Defines every export of `instance` under the namespace `module`.
+    ///
+    /// Nothing is defined if any of the exports would shadow an existing
+    /// definition while shadowing is disabled.
+    pub fn define_instance(
+        &mut self,
+        module: &str,
+        instance: &Instance,
+    ) -> Result<&mut Self, LinkerError> {
+        if !self.allow_shadowing {
+            if let Some((name, _)) = instance
+                .exports
+                .iter()
+                .find(|(name, _)| self.contains(module, name))
+            {
+                return Err(LinkerError::Shadowing(module.to_string(), name.to_string()));
+            }
+        }
+        for (name, ext) in instance.exports.iter() {
+            self.insert(module, name, Definition::Extern(ext.clone()))?;
+        }
+        Ok(self)
+    }
+
+    /// Defines every extern of `imports` under its module and name.
+    ///
+    /// Nothing is defined if any of them would shadow an existing
+    /// definition while shadowing is disabled.
+    pub fn define_imports(&mut self, imports: &Imports) -> Result<&mut Self, LinkerError> {
+        if !self.allow_shadowing {
+            if let Some(((module, name), _)) = imports
+                .into_iter()
+                .find(|((module, name), _)| self.contains(module, name))
+            {
+                return Err(LinkerError::Shadowing(module, name));
+            }
+        }
+        for ((module, name), ext) in imports {
+            self.insert(&module, &name, Definition::Extern(ext))?;
+        }
+        Ok(self)
+    }
+
+    /// Makes the definition of `module`.`name` also available as
+    /// `as_module`.`as_name`.
+    ///
+    /// Lazy definitions stay lazy: each name creates its own extern.
+    pub fn alias(
+        &mut self,
+        module: &str,
+        name: &str,
+        as_module: &str,
+        as_name: &str,
+    ) -> Result<&mut Self, LinkerError> {
+        let def = self
+            .map
+            .get(&(module.to_string(), name.to_string()))
+            .cloned()
+            .ok_or_else(|| LinkerError::Undefined(module.to_string(), name.to_string()))?;
+        self.insert(as_module, as_name, def)?;
+        Ok(self)
+    }
+
+    /// Resolves the imports of `module` and instantiates it.
+    ///
+    /// Lazy definitions are only created for the names `module` imports.
+    ///
+    /// # Errors
+    ///
+    /// If some imports have no definition, none are created and
+    /// [`LinkerError::MissingImports`] lists all of them.
+    pub fn instantiate(
+        &self,
+        store: &mut impl AsStoreMut,
+        module: &Module,
+    ) -> Result<Instance, LinkerError> {
+        let mut defs = Vec::new();
+        let mut missing = Vec::new();
+        for import in module.imports() {
+            match self
+                .map
+                .get(&(import.module().to_string(), import.name().to_string()))
+            {
+                Some(def) => defs.push(def),
+                None => missing.push((
+                    import.module().to_string(),
+                    import.name().to_string(),
+                    import.ty().clone(),
+                )),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(LinkerError::MissingImports(missing));
+        }
+
+        let externs = defs
+            .into_iter()
+            .map(|def| match def {
+                Definition::Extern(ext) => ext.clone(),
+                Definition::Lazy(func) => func(&mut store.as_store_mut()),
+            })
+            .collect::<Vec<_>>();
+        Ok(Instance::new_by_index(store, module, &externs)?)
+    }
+}
+
+impl From<Imports> for Linker {
+    fn from(imports: Imports) -> Self {
+        Self {
+            map: (&imports)
+                .into_iter()
+                .map(|(key, ext)| (key, Definition::Extern(ext)))
+                .collect(),
+            apis: HashSet::new(),
+            allow_shadowing: false,
+        }
+    }
+}
+
+impl fmt::Debug for Linker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Linker")
+            .field("definitions", &self.map)
+            .field("apis", &self.apis)
+            .field("allow_shadowing", &self.allow_shadowing)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{imports, Function, Global, Store, Value};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn shadowing() {
+        let mut store = Store::default();
+        let one = Global::new(&mut store, Value::I32(1));
+        let two = Global::new(&mut store, Value::I32(2));
+
+        let mut linker = Linker::new();
+        linker.define("env", "g", one).unwrap();
+        assert!(matches!(
+            linker.define("env", "g", two.clone()),
+            Err(LinkerError::Shadowing(module, name)) if module == "env" && name == "g"
+        ));
+
+        linker.allow_shadowing(true);
+        linker.define("env", "g", two).unwrap();
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (global $g (import "env" "g") i32)
+                (func (export "get") (result i32) global.get $g))"#,
+        )
+        .unwrap();
+        let instance = linker.instantiate(&mut store, &module).unwrap();
+        let get = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "get")
+            .unwrap();
+        assert_eq!(get.call(&mut store).unwrap(), 2);
+    }
+
+    #[test]
+    fn alias() {
+        let mut store = Store::default();
+        let mut linker = Linker::from(imports! {
+            "env" => {
+                "answer" => Function::new_typed(&mut store, || 42),
+            },
+        });
+
+        assert!(matches!(
+            linker.alias("env", "unknown", "host", "answer"),
+            Err(LinkerError::Undefined(_, _))
+        ));
+        linker.alias("env", "answer", "host", "answer").unwrap();
+        assert!(matches!(
+            linker.alias("env", "answer", "env", "answer"),
+            Err(LinkerError::Shadowing(_, _))
+        ));
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "host" "answer" (func $answer (result i32)))
+                (func (export "run") (result i32) call $answer))"#,
+        )
+        .unwrap();
+        let instance = linker.instantiate(&mut store, &module).unwrap();
+        let run = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store).unwrap(), 42);
+    }
+
+    #[test]
+    fn define_imports() {
+        let mut store = Store::default();
+        let mut linker = Linker::new();
+        linker
+            .define("env", "one", Global::new(&mut store, Value::I32(1)))
+            .unwrap();
+        assert!(linker.defines_namespace("env"));
+        assert!(!linker.defines_namespace("host"));
+
+        let clashing = imports! {
+            "host" => {
+                "two" => Global::new(&mut store, Value::I32(2)),
+            },
+            "env" => {
+                "one" => Global::new(&mut store, Value::I32(1)),
+            },
+        };
+        assert!(matches!(
+            linker.define_imports(&clashing),
+            Err(LinkerError::Shadowing(module, name)) if module == "env" && name == "one"
+        ));
+        // Nothing was defined.
+        assert!(!linker.defines_namespace("host"));
+
+        let imports = imports! {
+            "host" => {
+                "two" => Global::new(&mut store, Value::I32(2)),
+            },
+        };
+        linker.define_imports(&imports).unwrap();
+        assert!(linker.contains("host", "two"));
+    }
+
+    #[test]
+    fn record_api() {
+        let mut store = Store::default();
+        let mut linker = Linker::new();
+        linker
+            .define(
+                "wasi_unstable",
+                "one",
+                Global::new(&mut store, Value::I32(1)),
+            )
+            .unwrap();
+        // Definitions alone don't make an API.
+        assert!(!linker.has_api("wasi_unstable"));
+
+        assert!(linker.record_api("wasi_unstable"));
+        assert!(linker.has_api("wasi_unstable"));
+        assert!(!linker.record_api("wasi_unstable"));
+        assert!(linker.clone().has_api("wasi_unstable"));
+    }
+
+    #[test]
+    fn define_func() {
+        let mut store = Store::default();
+        let mut linker = Linker::new();
+        linker
+            .define_func(&mut store, "env", "add", |a: i32, b: i32| a + b)
+            .unwrap();
+        assert!(matches!(
+            linker.define_func(&mut store, "env", "add", || {}),
+            Err(LinkerError::Shadowing(_, _))
+        ));
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "add" (func $add (param i32 i32) (result i32)))
+                (func (export "run") (result i32) i32.const 40 i32.const 2 call $add))"#,
+        )
+        .unwrap();
+        let instance = linker.instantiate(&mut store, &module).unwrap();
+        let run = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "run")
+            .unwrap();
+        assert_eq!(run.call(&mut store).unwrap(), 42);
+    }
+
+    #[test]
+    fn lazy_definitions_and_missing_imports() {
+        let mut store = Store::default();
+        let created = Rc::new(Cell::new(0));
+
+        let mut linker = Linker::new();
+        for name in ["used", "unused"] {
+            let created = created.clone();
+            linker
+                .define_lazy("env", name, move |store| {
+                    created.set(created.get() + 1);
+                    Function::new_typed(store, || {})
+                })
+                .unwrap();
+        }
+
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "used" (func))
+                (import "env" "first" (func))
+                (import "other" "second" (global i32)))"#,
+        )
+        .unwrap();
+        match linker.instantiate(&mut store, &module) {
+            Err(LinkerError::MissingImports(missing)) => {
+                let names = missing
+                    .iter()
+                    .map(|(module, name, _)| (module.as_str(), name.as_str()))
+                    .collect::<Vec<_>>();
+                assert_eq!(names, [("env", "first"), ("other", "second")]);
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert_eq!(created.get(), 0);
+
+        let module = Module::new(&store, r#"(module (import "env" "used" (func)))"#).unwrap();
+        linker.instantiate(&mut store, &module).unwrap();
+        assert_eq!(created.get(), 1);
+    }
+}
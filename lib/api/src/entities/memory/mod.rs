@@ -3,7 +3,7 @@ use wasmer_types::{MemoryError, MemoryType, Pages};
 
 use crate::{
     vm::{VMExtern, VMExternMemory, VMMemory},
-    AsStoreMut, AsStoreRef, ExportError, Exportable, Extern, StoreMut, StoreRef,
+    AsStoreMut, AsStoreRef, ExportError, Exportable, Extern, MemoryAccessError, StoreMut, StoreRef,
 };
 
 pub(crate) mod buffer;
@@ -122,6 +122,49 @@ impl Memory {
         self.0.grow(store, delta)
     }
 
+    /// Grow memory to exactly `target` WebAssembly [`Pages`] and return the
+    /// previous memory size.
+    ///
+    /// Unlike [`Memory::grow`], the caller doesn't need to know the current
+    /// size. Growing to the current size is a no-op.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::{Memory, MemoryType, Pages, Store};
+    /// # let mut store = Store::default();
+    /// #
+    /// let m = Memory::new(&mut store, MemoryType::new(1, Some(3), false)).unwrap();
+    /// let p = m.grow_to_pages(&mut store, 3).unwrap();
+    ///
+    /// assert_eq!(p, Pages(1));
+    /// assert_eq!(m.view(&mut store).size(), Pages(3));
+    ///
+    /// // Memories can't shrink, nor grow past their maximum.
+    /// assert!(m.grow_to_pages(&mut store, 2).is_err());
+    /// assert!(m.grow_to_pages(&mut store, 4).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MemoryError::CouldNotShrink`] if `target` is smaller than
+    /// the current size, or an error if memory can't be grown to `target`.
+    pub fn grow_to_pages<IntoPages>(
+        &self,
+        store: &mut impl AsStoreMut,
+        target: IntoPages,
+    ) -> Result<Pages, MemoryError>
+    where
+        IntoPages: Into<Pages>,
+    {
+        let target = target.into();
+        let current = self.view(store).size();
+        if target < current {
+            return Err(MemoryError::CouldNotShrink { current, target });
+        }
+        self.grow(store, Pages(target.0 - current.0))
+    }
+
     /// Grows the memory to at least a minimum size.
     ///
     /// # Note
@@ -140,6 +183,46 @@ impl Memory {
         self.0.reset(store)
     }
 
+    /// Copies `src` into the memory, starting at `offset`.
+    ///
+    /// The whole range is bounds-checked first: if it doesn't fit in the
+    /// memory, nothing is written and a [`MemoryAccessError`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::{Memory, MemoryType, Store};
+    /// # let mut store = Store::default();
+    /// let m = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+    /// m.copy_from_slice(&mut store, 16, b"hello").unwrap();
+    ///
+    /// let mut buf = [0; 5];
+    /// m.copy_to_slice(&store, 16, &mut buf).unwrap();
+    /// assert_eq!(&buf, b"hello");
+    /// ```
+    pub fn copy_from_slice(
+        &self,
+        store: &mut impl AsStoreMut,
+        offset: u32,
+        src: &[u8],
+    ) -> Result<(), MemoryAccessError> {
+        self.view(store).write(offset.into(), src)
+    }
+
+    /// Fills `dst` with the contents of the memory, starting at `offset`.
+    ///
+    /// The whole range is bounds-checked first: if it doesn't fit in the
+    /// memory, `dst` is left untouched and a [`MemoryAccessError`] is
+    /// returned.
+    pub fn copy_to_slice(
+        &self,
+        store: &impl AsStoreRef,
+        offset: u32,
+        dst: &mut [u8],
+    ) -> Result<(), MemoryAccessError> {
+        self.view(store).read(offset.into(), dst)
+    }
+
     /// Attempts to duplicate this memory (if its clonable) in a new store
     /// (copied memory)
     pub fn copy_to_store(
@@ -203,3 +286,157 @@ impl<'a> Exportable<'a> for Memory {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{Memory, MemoryAccessError, MemoryType, Store, WASM_PAGE_SIZE};
+
+    #[test]
+    fn copy_slices() {
+        let mut store = Store::default();
+        let memory = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+
+        memory.copy_from_slice(&mut store, 8, &[1, 2, 3]).unwrap();
+        let mut buf = [0; 4];
+        memory.copy_to_slice(&store, 7, &mut buf).unwrap();
+        assert_eq!(buf, [0, 1, 2, 3]);
+
+        // The last bytes of the memory are reachable, one more is not.
+        let end = WASM_PAGE_SIZE as u32 - 3;
+        memory.copy_from_slice(&mut store, end, &[4, 5, 6]).unwrap();
+        assert!(matches!(
+            memory.copy_from_slice(&mut store, end, &[7, 8, 9, 10]),
+            Err(MemoryAccessError::HeapOutOfBounds)
+        ));
+        let mut buf = [0; 4];
+        assert!(matches!(
+            memory.copy_to_slice(&store, end, &mut buf),
+            Err(MemoryAccessError::HeapOutOfBounds)
+        ));
+        assert_eq!(buf, [0; 4]);
+
+        // A failed copy leaves the memory untouched.
+        let mut buf = [0; 3];
+        memory.copy_to_slice(&store, end, &mut buf).unwrap();
+        assert_eq!(buf, [4, 5, 6]);
+
+        assert!(matches!(
+            memory.copy_from_slice(&mut store, u32::MAX, &[1]),
+            Err(MemoryAccessError::HeapOutOfBounds)
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "sys", feature = "wamr"))]
+    fn grow_matches_between_sys_and_wamr() {
+        use crate::{imports, Engine, Instance, Module, TypedFunction};
+        use wasmer_types::{MemoryError, Pages};
+
+        let probe = |store: &mut Store, limits: &str| {
+            let module = Module::new(
+                &*store,
+                format!(
+                    r#"(module
+                        (memory (export "memory") {limits})
+                        (func (export "grow") (param i32) (result i32)
+                            (memory.grow (local.get 0))))"#
+                ),
+            )
+            .unwrap();
+            let instance = Instance::new(store, &module, &imports! {}).unwrap();
+            let memory = instance.exports.get_memory("memory").unwrap();
+            let grow: TypedFunction<i32, i32> = instance
+                .exports
+                .get_typed_function(&*store, "grow")
+                .unwrap();
+            let maximum = memory.ty(&*store).maximum;
+            let grown = [0, 1, 1, 1, 0x10000].map(|delta| grow.call(store, delta).unwrap());
+            // Growing past the maximum fails on the host too.
+            let host = match memory.grow(store, Pages(0x10000)) {
+                Err(MemoryError::CouldNotGrow { current, .. }) => Some(current),
+                _ => None,
+            };
+            (maximum, grown, host)
+        };
+
+        let mut sys = Store::new(Engine::from(
+            crate::backend::sys::entities::engine::default_engine(),
+        ));
+        let mut wamr = Store::new(crate::backend::wamr::Wamr::new());
+        // The app heap of WAMR would add pages to the memories.
+        wamr.set_wamr_instance_config(crate::wamr::InstanceConfig {
+            heap_size: 0,
+            ..Default::default()
+        });
+        for limits in ["1 3", "1"] {
+            assert_eq!(
+                probe(&mut sys, limits),
+                probe(&mut wamr, limits),
+                "{limits}"
+            );
+        }
+        assert_eq!(
+            probe(&mut wamr, "1 3"),
+            (Some(Pages(3)), [1, 1, 2, -1, -1], Some(Pages(3)))
+        );
+        assert_eq!(
+            probe(&mut wamr, "1"),
+            (None, [1, 1, 2, 3, -1], Some(Pages(4)))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_shared_memory() {
+        use crate::{imports, Instance, Module, TypedFunction};
+
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        // The host can't create shared memories, a module defines it.
+        let exporter =
+            Module::new(&store, r#"(module (memory (export "memory") 1 1 shared))"#).unwrap();
+        let memory = Instance::new(&mut store, &exporter, &imports! {})
+            .unwrap()
+            .exports
+            .get_memory("memory")
+            .unwrap()
+            .clone();
+        assert!(memory.ty(&store).shared);
+        // The type is known for every handle to the memory.
+        let existing = Memory::new_from_existing(&mut store, memory.try_clone(&store).unwrap());
+        assert_eq!(existing.ty(&store), memory.ty(&store));
+
+        let importer = Module::new(
+            &store,
+            r#"(module
+                (import "env" "memory" (memory 1 1 shared))
+                (func (export "add") (param i32) (result i32)
+                    i32.const 0
+                    local.get 0
+                    i32.atomic.rmw.add))"#,
+        )
+        .unwrap();
+        let imports = imports! { "env" => { "memory" => memory.clone() } };
+        let first = Instance::new(&mut store, &importer, &imports).unwrap();
+        let second = Instance::new(&mut store, &importer, &imports).unwrap();
+        let add = |instance: &Instance| -> TypedFunction<i32, i32> {
+            instance.exports.get_typed_function(&store, "add").unwrap()
+        };
+        let (add_first, add_second) = (add(&first), add(&second));
+
+        // A write through one instance is visible through the other, and
+        // from the host.
+        assert_eq!(add_first.call(&mut store, 2).unwrap(), 0);
+        assert_eq!(add_second.call(&mut store, 3).unwrap(), 2);
+        assert_eq!(add_first.call(&mut store, 0).unwrap(), 5);
+        let mut bytes = [0u8; 4];
+        memory.view(&store).read(0, &mut bytes).unwrap();
+        assert_eq!(i32::from_le_bytes(bytes), 5);
+
+        assert!(matches!(
+            Memory::new(&mut store, MemoryType::new(1, Some(1), true)),
+            Err(wasmer_types::MemoryError::UnsupportedOperation { .. })
+        ));
+        let memory = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+        assert!(!memory.ty(&store).shared);
+    }
+}
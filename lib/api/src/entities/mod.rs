@@ -52,3 +52,9 @@ pub use exports::*;
 
 pub(crate) mod imports;
 pub use imports::*;
+
+pub(crate) mod linker;
+pub use linker::*;
+
+pub(crate) mod host_module;
+pub use host_module::*;
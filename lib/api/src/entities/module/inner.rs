@@ -1,15 +1,15 @@
 //! Defines the [`BackendModule`] data type and various useful traits and data types to interact with
 //! a concrete module from a backend.
 
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, io::Read, path::Path};
 
 use bytes::Bytes;
 use thiserror::Error;
 #[cfg(feature = "wat")]
 use wasmer_types::WasmError;
 use wasmer_types::{
-    CompileError, DeserializeError, ExportType, ExportsIterator, ImportType, ImportsIterator,
-    ModuleInfo, SerializeError,
+    strip_custom_sections, CompileError, DeserializeError, ExportType, ExportsIterator, ImportType,
+    ImportsIterator, ModuleHash, ModuleInfo, SerializeError, StripMode,
 };
 
 use crate::{
@@ -36,13 +36,30 @@ impl BackendModule {
     pub fn new(engine: &impl AsEngineRef, bytes: impl AsRef<[u8]>) -> Result<Self, CompileError> {
         #[cfg(feature = "wat")]
         let bytes = wat::parse_bytes(bytes.as_ref()).map_err(|e| {
-            CompileError::Wasm(WasmError::Generic(format!(
-                "Error when converting wat: {e}",
-            )))
+            CompileError::Wasm(WasmError::Parse(format!("Error when converting wat: {e}",)))
         })?;
         Self::from_binary(engine, bytes.as_ref())
     }
 
+    /// Creates a new WebAssembly module from bytes, applying the given
+    /// [`CompileOptions`](super::CompileOptions) before compiling it.
+    #[inline]
+    pub fn new_with_options(
+        engine: &impl AsEngineRef,
+        bytes: impl AsRef<[u8]>,
+        options: super::CompileOptions,
+    ) -> Result<Self, CompileError> {
+        #[cfg(feature = "wat")]
+        let bytes = wat::parse_bytes(bytes.as_ref()).map_err(|e| {
+            CompileError::Wasm(WasmError::Parse(format!("Error when converting wat: {e}",)))
+        })?;
+        if options.strip_custom_sections == StripMode::None {
+            return Self::from_binary(engine, bytes.as_ref());
+        }
+        let bytes = strip_custom_sections(bytes.as_ref(), &options.strip_custom_sections)?;
+        Self::from_binary(engine, &bytes)
+    }
+
     /// Creates a new WebAssembly module from a file path.
     #[inline]
     pub fn from_file(
@@ -60,6 +77,35 @@ impl BackendModule {
         Ok(module)
     }
 
+    /// Creates a new WebAssembly module from the bytes produced by `reader`.
+    ///
+    /// The bytes are read in full before compiling, so an IO error in the
+    /// middle of the stream never reaches the engine.
+    #[inline]
+    pub fn new_from_reader(
+        engine: &impl AsEngineRef,
+        mut reader: impl Read,
+    ) -> Result<Self, super::IoCompileError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(Self::new(engine, bytes)?)
+    }
+
+    /// Creates a new WebAssembly module from the bytes produced by an
+    /// asynchronous `reader`.
+    #[cfg(feature = "async-reader")]
+    #[inline]
+    pub async fn new_from_async_reader(
+        engine: &impl AsEngineRef,
+        mut reader: impl tokio::io::AsyncRead + Unpin,
+    ) -> Result<Self, super::IoCompileError> {
+        use tokio::io::AsyncReadExt;
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(Self::new(engine, bytes)?)
+    }
+
     /// Creates a new WebAssembly module from a Wasm binary.
     ///
     /// Opposed to [`Self::new`], this function is not compatible with
@@ -545,6 +591,23 @@ impl BackendModule {
         })
     }
 
+    /// Returns a content hash of the WebAssembly binary the module was
+    /// compiled from, if it is known.
+    #[inline]
+    pub fn hash(&self) -> Option<ModuleHash> {
+        match_rt!(on self => s {
+            s.hash()
+        })
+    }
+
+    /// Renames the module part of the imports according to `remapping`.
+    #[inline]
+    pub fn with_imports_override(self, remapping: &HashMap<String, String>) -> Self {
+        match_rt!(on self => s {
+            s.with_imports_override(remapping).into()
+        })
+    }
+
     /// Returns an iterator over the imported types in the Module.
     ///
     /// The order of the imports is guaranteed to be the same as in the
@@ -620,6 +683,14 @@ impl BackendModule {
         })
     }
 
+    /// Get the active data segments of the module.
+    #[inline]
+    pub fn data_initializers(&self) -> Vec<wasmer_types::DataInitializer<'_>> {
+        match_rt!(on self => s {
+            s.data_initializers()
+        })
+    }
+
     /// The ABI of the [`ModuleInfo`] is very unstable, we refactor it very often.
     /// This function is public because in some cases it can be useful to get some
     /// extra information from the module.
@@ -4,18 +4,37 @@
 pub(crate) mod inner;
 pub(crate) use inner::*;
 
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, io::Read, path::Path};
 
 use bytes::Bytes;
 use thiserror::Error;
+#[cfg(feature = "detect-wasm-features")]
+use wasmer_types::Features;
 #[cfg(feature = "wat")]
 use wasmer_types::WasmError;
 use wasmer_types::{
-    CompileError, DeserializeError, ExportType, ExportsIterator, ImportType, ImportsIterator,
-    ModuleInfo, SerializeError,
+    CompileError, DeserializeError, ExportType, ExportsIterator, FunctionIndex, FunctionType,
+    ImportIndex, ImportType, ImportsIterator, InstantiationCost, MemoryType, ModuleHash,
+    ModuleInfo, SerializeError, StripMode, Type,
 };
 
-use crate::{macros::backend::match_rt, utils::IntoBytes, AsEngineRef};
+use crate::{
+    error::{ImportTypeMismatch, LinkError},
+    macros::backend::match_rt,
+    utils::IntoBytes,
+    AsEngineRef, AsStoreRef, Imports,
+};
+
+/// Options for [`Module::new_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// Custom sections to remove from the binary before compiling it.
+    ///
+    /// Stripped sections are absent from [`Module::custom_sections`] and
+    /// from the serialized artifact. Keep the `name` section around to
+    /// preserve function names in backtraces.
+    pub strip_custom_sections: StripMode,
+}
 
 /// IO errors that can happen while compiling a [`Module`].
 #[derive(Error, Debug)]
@@ -28,6 +47,40 @@ pub enum IoCompileError {
     Compile(#[from] CompileError),
 }
 
+/// A data segment of a [`Module`], see [`Module::data_segments`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataSegment<'a> {
+    /// The index of the memory the segment initializes, or `None` for
+    /// passive segments.
+    pub memory_index: Option<u32>,
+    /// The constant offset the segment is written at, or `None` for
+    /// passive segments.
+    pub offset: Option<u64>,
+    /// The index of the global added to `offset`, when the offset
+    /// expression reads a global.
+    pub global_base: Option<u32>,
+    /// The bytes of the segment.
+    pub data: &'a [u8],
+}
+
+/// An element segment of a [`Module`], see [`Module::element_segments`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementSegment {
+    /// The index of the table the segment initializes, or `None` for
+    /// passive segments.
+    pub table_index: Option<u32>,
+    /// The constant offset the segment is written at, or `None` for
+    /// passive segments.
+    pub offset: Option<u64>,
+    /// The index of the global added to `offset`, when the offset
+    /// expression reads a global.
+    pub global_base: Option<u32>,
+    /// The type of the elements.
+    pub type_: Type,
+    /// The indices of the functions in the segment.
+    pub items: Vec<u32>,
+}
+
 /// A WebAssembly Module contains stateless WebAssembly
 /// code that has already been compiled and can be instantiated
 /// multiple times.
@@ -113,7 +166,44 @@ impl Module {
         BackendModule::new(engine, bytes).map(Self)
     }
 
+    /// Creates a new WebAssembly module from bytes, applying the given
+    /// [`CompileOptions`] before compiling it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let store = Store::default();
+    /// let options = CompileOptions {
+    ///     strip_custom_sections: StripMode::AllExcept(vec!["name".into()]),
+    ///     ..Default::default()
+    /// };
+    /// let module = Module::new_with_options(&store, "(module $foo)", options)?;
+    /// assert_eq!(module.name(), Some("foo"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_options(
+        engine: &impl AsEngineRef,
+        bytes: impl AsRef<[u8]>,
+        options: CompileOptions,
+    ) -> Result<Self, CompileError> {
+        BackendModule::new_with_options(engine, bytes, options).map(Self)
+    }
+
     /// Creates a new WebAssembly module from a file path.
+    ///
+    /// Like [`Module::new`], the format is detected from the content of the
+    /// file rather than from its extension: files starting with the `\0asm`
+    /// magic number are compiled as binaries, others are parsed with the
+    /// text format if the "wat" feature is enabled. The module is named
+    /// after the canonical path of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IoCompileError::Io`] if the file can't be read, and
+    /// [`IoCompileError::Compile`] if it doesn't hold a valid module.
     pub fn from_file(
         engine: &impl AsEngineRef,
         file: impl AsRef<Path>,
@@ -121,6 +211,47 @@ impl Module {
         BackendModule::from_file(engine, file).map(Self)
     }
 
+    /// Creates a new WebAssembly module reading its bytes from `reader`.
+    ///
+    /// This is useful when the module comes from a pipe or from the
+    /// network. Like [`Module::new`], the text format is accepted if the
+    /// "wat" feature is enabled.
+    ///
+    /// # Note
+    ///
+    /// None of the backends can compile function bodies as they arrive yet,
+    /// so the whole stream is buffered before compilation starts. If reading
+    /// fails, nothing is handed to the engine.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let store = Store::default();
+    /// let wat: &[u8] = b"(module)";
+    /// let module = Module::new_from_reader(&store, wat)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_from_reader(
+        engine: &impl AsEngineRef,
+        reader: impl Read,
+    ) -> Result<Self, IoCompileError> {
+        BackendModule::new_from_reader(engine, reader).map(Self)
+    }
+
+    /// Asynchronous version of [`Module::new_from_reader`].
+    #[cfg(feature = "async-reader")]
+    pub async fn new_from_async_reader(
+        engine: &impl AsEngineRef,
+        reader: impl tokio::io::AsyncRead + Unpin,
+    ) -> Result<Self, IoCompileError> {
+        BackendModule::new_from_async_reader(engine, reader)
+            .await
+            .map(Self)
+    }
+
     /// Creates a new WebAssembly module from a Wasm binary.
     ///
     /// Opposed to [`Module::new`], this function is not compatible with
@@ -130,6 +261,41 @@ impl Module {
         BackendModule::from_binary(engine, binary).map(Self)
     }
 
+    /// Creates a new WebAssembly module from the WebAssembly text format.
+    ///
+    /// Opposed to [`Module::new`], `wat` can't be a Wasm binary.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let store = Store::default();
+    /// let module = Module::from_wat(&store, r#"(module (func (export "run")))"#)?;
+    /// assert!(module.exports().any(|export| export.name() == "run"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "wat")]
+    pub fn from_wat(engine: &impl AsEngineRef, wat: &str) -> Result<Self, CompileError> {
+        let binary = wat::parse_str(wat).map_err(|e| {
+            CompileError::Wasm(WasmError::Parse(format!("Error when converting wat: {e}",)))
+        })?;
+        Self::from_binary(engine, &binary)
+    }
+
+    /// Creates a new WebAssembly module from the WebAssembly text format,
+    /// given as UTF-8 bytes.
+    ///
+    /// See [`Module::from_wat`].
+    #[cfg(feature = "wat")]
+    pub fn from_wat_bytes(engine: &impl AsEngineRef, bytes: &[u8]) -> Result<Self, CompileError> {
+        let wat = std::str::from_utf8(bytes).map_err(|e| {
+            CompileError::Wasm(WasmError::Parse(format!("Error when converting wat: {e}",)))
+        })?;
+        Self::from_wat(engine, wat)
+    }
+
     /// Creates a new WebAssembly module from a Wasm binary,
     /// skipping any kind of validation on the WebAssembly file.
     ///
@@ -156,6 +322,28 @@ impl Module {
         Ok(())
     }
 
+    /// Returns the WebAssembly proposals the given binary requires in
+    /// order to validate.
+    ///
+    /// This can be used to pick the right [`Engine`](crate::Engine) or
+    /// feature set up front, instead of finding out at compile time.
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let wasm = wat2wasm(b"(module (memory 1 1 shared))")?;
+    /// let features = Module::required_features(&wasm)?;
+    /// assert!(features.threads);
+    /// assert!(!features.simd);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "detect-wasm-features")]
+    pub fn required_features(binary: impl AsRef<[u8]>) -> Result<Features, CompileError> {
+        Features::required_by_wasm(binary.as_ref())
+            .map_err(|e| CompileError::Validate(e.to_string()))
+    }
+
     /// Serializes a module into a binary representation that the `Engine`
     /// can later process via [`Module::deserialize`].
     ///
@@ -365,6 +553,92 @@ impl Module {
         self.0.set_name(name)
     }
 
+    /// Returns the resources instantiating this module allocates: memory
+    /// pages, table elements, globals and so on.
+    ///
+    /// Hosts can check the result against their [`StoreLimits`](crate::StoreLimits) with
+    /// [`InstantiationCost::fits_within`] to reject a module before paying
+    /// for its instantiation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let module = Module::new(&store, "(module (memory 16384))")?;
+    /// let cost = module.instantiation_requirements();
+    /// assert_eq!(cost.min_memory_pages, 16384);
+    ///
+    /// let limits = StoreLimits {
+    ///     max_memory_pages: Some(1024),
+    ///     ..Default::default()
+    /// };
+    /// assert!(cost.fits_within(&limits).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn instantiation_requirements(&self) -> InstantiationCost {
+        InstantiationCost::from_module_info(self.info())
+    }
+
+    /// Returns a content hash of the WebAssembly binary the module was
+    /// compiled from.
+    ///
+    /// The hash is computed from the Wasm bytes when the module is
+    /// compiled. A deserialized module only knows it if the artifact
+    /// recorded it: on the `sys` backend, when the engine compiling the
+    /// artifact had a hash algorithm set. Returns `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let a = Module::new(&store, "(module)")?;
+    /// let b = Module::new(&store, "(module)")?;
+    /// assert_eq!(a.hash(), b.hash());
+    /// if let Some(hash) = a.hash() {
+    ///     println!("module hash: {hash}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn hash(&self) -> Option<ModuleHash> {
+        self.0.hash()
+    }
+
+    /// Renames the module part of the module's imports, so they can be
+    /// provided under a different namespace than the one the module was
+    /// compiled against.
+    ///
+    /// Every import whose module name is a key of `remapping` is renamed to
+    /// the corresponding value; other imports are left untouched. The
+    /// renaming only affects how imports are looked up by name (e.g. in
+    /// [`Instance::new`](crate::Instance::new)); it is not part of the
+    /// serialized module.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let module = Module::new(&store, r#"(module (import "env" "f" (func)))"#)?;
+    /// let module = module.with_imports_override(&HashMap::from([(
+    ///     "env".to_string(),
+    ///     "host".to_string(),
+    /// )]));
+    /// assert_eq!(module.imports().next().unwrap().module(), "host");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_imports_override(self, remapping: &HashMap<String, String>) -> Self {
+        Self(self.0.with_imports_override(remapping))
+    }
+
     /// Returns an iterator over the imported types in the Module.
     ///
     /// The order of the imports is guaranteed to be the same as in the
@@ -420,6 +694,123 @@ impl Module {
         self.0.exports()
     }
 
+    /// Returns the imports of the module as a list of [`ImportType`]s, each
+    /// with its module name, name and full type.
+    ///
+    /// The order of the imports is the same as in the WebAssembly bytecode,
+    /// as for [`Module::imports`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let store = Store::default();
+    /// let module = Module::new(&store, r#"(module (import "host" "mem" (memory 1)))"#)?;
+    /// let imports = module.import_types();
+    /// assert_eq!(imports.len(), 1);
+    /// assert_eq!(imports[0].module(), "host");
+    /// assert_eq!(imports[0].name(), "mem");
+    /// assert!(matches!(imports[0].ty(), ExternType::Memory(_)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn import_types(&self) -> Vec<ImportType> {
+        self.imports().collect()
+    }
+
+    /// Returns the exports of the module as a list of [`ExportType`]s, each
+    /// with its name and full type.
+    ///
+    /// The order of the exports is the same as in the WebAssembly bytecode,
+    /// as for [`Module::exports`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let store = Store::default();
+    /// let module = Module::new(&store, r#"(module (global (export "g") i32 (i32.const 0)))"#)?;
+    /// let exports = module.export_types();
+    /// assert_eq!(exports.len(), 1);
+    /// assert_eq!(exports[0].name(), "g");
+    /// assert!(matches!(exports[0].ty(), ExternType::Global(_)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_types(&self) -> Vec<ExportType> {
+        self.exports().collect()
+    }
+
+    /// Checks that `imports` satisfies every import of this module, without
+    /// instantiating it.
+    ///
+    /// Each mismatch lists the import, the expected and provided types and,
+    /// in [`LinkIssue::differences`](crate::LinkIssue::differences), what
+    /// exactly differs. [`Instance::new`](crate::Instance::new) reports the
+    /// same issues in its [`LinkError::Unresolved`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module (import "env" "log" (func (param i32 i64))))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let imports = imports! {
+    ///     "env" => {
+    ///         "log" => Function::new_typed(&mut store, |_: i32, _: i32| {}),
+    ///     },
+    /// };
+    /// let mismatches = module.check_imports(&store, &imports).unwrap_err();
+    /// assert_eq!(mismatches[0].differences, ["param 1: expected i64, got i32"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_imports(
+        &self,
+        store: &impl AsStoreRef,
+        imports: &Imports,
+    ) -> Result<(), Vec<ImportTypeMismatch>> {
+        match imports.resolve_for_module(store, self) {
+            Ok(_) => Ok(()),
+            Err(LinkError::Unresolved { issues, .. }) => Err(issues),
+            Err(_) => unreachable!("resolving imports only reports unresolved imports"),
+        }
+    }
+
+    /// Returns the memory the host must provide to this module, if it
+    /// imports exactly one memory.
+    ///
+    /// The returned [`ImportType`] holds the namespace, name and the limits
+    /// the provided memory must satisfy. When it isn't `None`, this memory
+    /// is also the one [`Instance::main_memory`](crate::Instance::main_memory)
+    /// resolves to, unless the module exports a memory.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module (import "env" "memory" (memory 1 16)))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let memory = module.memory_import_requirements().unwrap();
+    /// assert_eq!((memory.module(), memory.name()), ("env", "memory"));
+    /// assert_eq!(memory.ty().maximum, Some(Pages(16)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memory_import_requirements(&self) -> Option<ImportType<MemoryType>> {
+        let mut memories = self.imports().memories();
+        match (memories.next(), memories.next()) {
+            (Some(memory), None) => Some(memory),
+            _ => None,
+        }
+    }
+
     /// Get the custom sections of the module given a `name`.
     ///
     /// # Important
@@ -431,6 +822,176 @@ impl Module {
         self.0.custom_sections(name)
     }
 
+    /// Iterates over the data segments of the module.
+    ///
+    /// Active segments come first, in the order they appear in the module,
+    /// followed by the passive segments.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module
+    ///     (memory 1)
+    ///     (data (i32.const 16) "hello")
+    ///     (data "world"))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let segments = module.data_segments().collect::<Vec<_>>();
+    /// assert_eq!(segments[0].memory_index, Some(0));
+    /// assert_eq!(segments[0].offset, Some(16));
+    /// assert_eq!(segments[0].data, b"hello");
+    /// assert_eq!(segments[1].memory_index, None);
+    /// assert_eq!(segments[1].offset, None);
+    /// assert_eq!(segments[1].data, b"world");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn data_segments(&self) -> impl Iterator<Item = DataSegment<'_>> + '_ {
+        let info = self.info();
+        let active = self
+            .0
+            .data_initializers()
+            .into_iter()
+            .map(|init| DataSegment {
+                memory_index: Some(init.location.memory_index.as_u32()),
+                offset: Some(init.location.offset as u64),
+                global_base: init.location.base.map(|base| base.as_u32()),
+                data: init.data,
+            });
+
+        let mut passive = info.passive_data.iter().collect::<Vec<_>>();
+        passive.sort_by_key(|(index, _)| **index);
+        let passive = passive.into_iter().map(|(_, data)| DataSegment {
+            memory_index: None,
+            offset: None,
+            global_base: None,
+            data,
+        });
+
+        active.chain(passive)
+    }
+
+    /// Iterates over the element segments of the module, that is the
+    /// functions placed in tables at instantiation or made available to
+    /// `table.init`.
+    ///
+    /// Active segments come first, in the order they appear in the module,
+    /// followed by the passive segments.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module
+    ///     (table 4 funcref)
+    ///     (func $a) (func $b)
+    ///     (elem (i32.const 1) $b $a)
+    ///     (elem func $b))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let segments = module.element_segments().collect::<Vec<_>>();
+    /// assert_eq!(segments[0].table_index, Some(0));
+    /// assert_eq!(segments[0].offset, Some(1));
+    /// assert_eq!(segments[0].items, [1, 0]);
+    /// assert_eq!(segments[1].table_index, None);
+    /// assert_eq!(segments[1].items, [1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn element_segments(&self) -> impl Iterator<Item = ElementSegment> + '_ {
+        let info = self.info();
+        let active = info.table_initializers.iter().map(|init| ElementSegment {
+            table_index: Some(init.table_index.as_u32()),
+            offset: Some(init.offset as u64),
+            global_base: init.base.map(|base| base.as_u32()),
+            type_: info
+                .tables
+                .get(init.table_index)
+                .map_or(Type::FuncRef, |table| table.ty),
+            items: init.elements.iter().map(|index| index.as_u32()).collect(),
+        });
+
+        let mut passive = info.passive_elements.iter().collect::<Vec<_>>();
+        passive.sort_by_key(|(index, _)| **index);
+        let passive = passive.into_iter().map(|(_, elements)| ElementSegment {
+            table_index: None,
+            offset: None,
+            global_base: None,
+            type_: Type::FuncRef,
+            items: elements.iter().map(|index| index.as_u32()).collect(),
+        });
+
+        active.chain(passive)
+    }
+
+    /// Iterates over the functions of the module with their signature,
+    /// imported functions first, in the order of the function index space.
+    ///
+    /// This only reads the module's metadata: no runtime object is created.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module
+    ///     (import "env" "log" (func (param i32)))
+    ///     (func (export "add") (param i32 i32) (result i32)
+    ///         (i32.add (local.get 0) (local.get 1))))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let functions = module.functions().collect::<Vec<_>>();
+    /// assert_eq!(functions.len(), 2);
+    /// assert_eq!(functions[1].0, FunctionIndex::from_u32(1));
+    /// assert_eq!(functions[1].1.params(), [Type::I32, Type::I32]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn functions(&self) -> impl Iterator<Item = (FunctionIndex, &FunctionType)> + '_ {
+        let info = self.info();
+        info.functions
+            .iter()
+            .map(move |(index, signature)| (index, &info.signatures[*signature]))
+    }
+
+    /// Iterates over the functions the module imports, with the module
+    /// and field names they are imported from and their signature.
+    ///
+    /// Like [`Module::functions`], this doesn't create any runtime object.
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::*;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut store = Store::default();
+    /// let wat = r#"(module
+    ///     (import "env" "log" (func (param i32)))
+    ///     (import "env" "memory" (memory 1)))"#;
+    /// let module = Module::new(&store, wat)?;
+    /// let (_, module_name, field, ty) = module.host_functions().next().unwrap();
+    /// assert_eq!((module_name, field), ("env", "log"));
+    /// assert_eq!(ty.params(), [Type::I32]);
+    /// assert_eq!(module.host_functions().count(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn host_functions(
+        &self,
+    ) -> impl Iterator<Item = (ImportIndex, &str, &str, &FunctionType)> + '_ {
+        let info = self.info();
+        info.imports
+            .iter()
+            .filter_map(move |(key, index)| match index {
+                ImportIndex::Function(function) => Some((
+                    index.clone(),
+                    key.module.as_str(),
+                    key.field.as_str(),
+                    &info.signatures[info.functions[*function]],
+                )),
+                _ => None,
+            })
+    }
+
     /// The ABI of the [`ModuleInfo`] is very unstable, we refactor it very often.
     /// This function is public because in some cases it can be useful to get some
     /// extra information from the module.
@@ -456,3 +1017,399 @@ impl From<Module> for wasm_bindgen::JsValue {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use crate::{Module, Store};
+
+    /// A reader handing out at most 4KB per call.
+    struct ChunkedReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(4096).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn check_imports() {
+        use crate::{
+            imports, Function, Global, Instance, InstantiationError, Memory, MemoryType, Value,
+        };
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "arity" (func (param i32 i32)))
+                (import "env" "param" (func (param i32 f64 i64)))
+                (import "env" "memory" (memory 2 4))
+                (import "env" "counter" (global (mut i32)))
+                (import "env" "ok" (func (result i32))))"#,
+        )
+        .unwrap();
+        let memory = Memory::new(&mut store, MemoryType::new(1, Some(8), false)).unwrap();
+        let imports = imports! {
+            "env" => {
+                "arity" => Function::new_typed(&mut store, |_: i32| {}),
+                "param" => Function::new_typed(&mut store, |_: i32, _: f64, _: i32| {}),
+                "memory" => memory,
+                "counter" => Global::new(&mut store, Value::I32(0)),
+                "ok" => Function::new_typed(&mut store, || 0),
+            },
+        };
+
+        let mismatches = module.check_imports(&store, &imports).unwrap_err();
+        let differences = mismatches
+            .iter()
+            .map(|mismatch| (mismatch.name.as_str(), mismatch.differences.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            differences,
+            [
+                ("arity", vec!["expected 2 params, got 1".to_string()]),
+                ("param", vec!["param 2: expected i64, got i32".to_string()]),
+                (
+                    "memory",
+                    vec![
+                        "minimum: expected at least 2, got 1".to_string(),
+                        "maximum: expected at most 4, got 8".to_string(),
+                    ]
+                ),
+                (
+                    "counter",
+                    vec!["mutability: expected mutable, got immutable".to_string()]
+                ),
+            ]
+        );
+
+        // Instantiating reports the same differences.
+        let err = Instance::new(&mut store, &module, &imports).unwrap_err();
+        let InstantiationError::Link(err) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+        assert!(err.to_string().contains("(param 2: expected i64, got i32)"));
+    }
+
+    #[test]
+    fn new_from_chunked_reader() {
+        let store = Store::default();
+        // Enough functions for the binary to span several chunks.
+        let funcs: String = (0..512)
+            .map(|i| format!("(func (export \"f{i}\") (result i32) i32.const {i})"))
+            .collect();
+        let wasm = wat::parse_str(format!("(module {funcs})")).unwrap();
+        assert!(wasm.len() > 4096);
+
+        let buffered = Module::new(&store, &wasm).unwrap();
+        let streamed = Module::new_from_reader(&store, ChunkedReader { data: &wasm }).unwrap();
+
+        assert_eq!(
+            buffered.exports().collect::<Vec<_>>(),
+            streamed.exports().collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "detect-wasm-features")]
+    #[test]
+    fn required_features() {
+        let detect = |wat: &str| Module::required_features(wat::parse_str(wat).unwrap()).unwrap();
+
+        let none = detect("(module (func (export \"f\") (result i32) i32.const 0))");
+        assert!(!none.threads && !none.simd && !none.reference_types && !none.bulk_memory);
+        assert!(!none.exceptions && !none.memory64 && !none.tail_call);
+
+        assert!(detect("(module (memory 1 1 shared))").threads);
+        assert!(detect("(module (func (result v128) v128.const i32x4 0 0 0 0))").simd);
+        assert!(detect("(module (table 1 externref))").reference_types);
+        assert!(detect("(module (table 1 funcref) (table 1 funcref))").reference_types);
+        assert!(
+            detect("(module (memory 1) (func i32.const 0 i32.const 0 i32.const 0 memory.fill))")
+                .bulk_memory
+        );
+        assert!(detect("(module (tag) (func throw 0))").exceptions);
+        assert!(detect("(module (memory i64 1))").memory64);
+        assert!(detect("(module (func return_call 0))").tail_call);
+        assert!(detect("(module (func (result i32 i32) i32.const 0 i32.const 1))").multi_value);
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        let store = Store::default();
+        let a = Module::new(&store, "(module (func (export \"f\")))").unwrap();
+        let b = Module::new(&store, "(module (func (export \"f\")))").unwrap();
+        let c = Module::new(&store, "(module (func (export \"g\")))").unwrap();
+        assert!(a.hash().is_some());
+        assert_eq!(a.hash(), b.hash());
+        assert_ne!(a.hash(), c.hash());
+
+        // The hash is the one of the Wasm bytes.
+        let wasm = wat::parse_str("(module (func (export \"f\")))").unwrap();
+        assert_eq!(a.hash(), Some(crate::ModuleHash::xxhash(wasm)));
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn hash_survives_serialization() {
+        use crate::sys::NativeEngineExt;
+
+        let mut engine = crate::Engine::default();
+        engine.set_hash_algorithm(Some(wasmer_types::HashAlgorithm::XXHash));
+        let store = Store::new(engine);
+        let module = Module::new(&store, "(module (func (export \"f\")))").unwrap();
+        let serialized = module.serialize().unwrap();
+        let deserialized = unsafe { Module::deserialize(&store, serialized) }.unwrap();
+        assert!(deserialized.hash().is_some());
+        assert_eq!(module.hash(), deserialized.hash());
+    }
+
+    #[test]
+    fn new_with_stripped_custom_sections() {
+        use crate::{imports, CompileOptions, Instance, StripMode, TypedFunction};
+
+        let store = &mut Store::default();
+        let mut wasm =
+            wat::parse_str("(module $m (func (export \"f\") (result i32) i32.const 42))").unwrap();
+        for name in [".debug_info", "producers"] {
+            // Keep the section small enough for its size to fit in one LEB128 byte.
+            let payload = [0u8; 100];
+            wasm.push(0);
+            wasm.push((1 + name.len() + payload.len()) as u8);
+            wasm.push(name.len() as u8);
+            wasm.extend_from_slice(name.as_bytes());
+            wasm.extend_from_slice(&payload);
+        }
+
+        let full = Module::new(store, &wasm).unwrap();
+        let options = CompileOptions {
+            strip_custom_sections: StripMode::AllExcept(vec!["name".into()]),
+        };
+        let stripped = Module::new_with_options(store, &wasm, options).unwrap();
+
+        assert_eq!(full.custom_sections(".debug_info").count(), 1);
+        assert_eq!(stripped.custom_sections(".debug_info").count(), 0);
+        assert_eq!(stripped.custom_sections("producers").count(), 0);
+        assert_eq!(stripped.name(), Some("m"));
+        if let (Ok(full), Ok(stripped)) = (full.serialize(), stripped.serialize()) {
+            assert!(stripped.len() < full.len());
+        }
+
+        let instance = Instance::new(store, &stripped, &imports! {}).unwrap();
+        let f: TypedFunction<(), i32> = instance.exports.get_typed_function(store, "f").unwrap();
+        assert_eq!(f.call(store).unwrap(), 42);
+    }
+
+    #[test]
+    fn instantiate_with_imports_override() {
+        use std::collections::HashMap;
+
+        use crate::{imports, Function, Instance};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "f" (func))
+                (import "other" "g" (func)))"#,
+        )
+        .unwrap();
+        let module = module.with_imports_override(&HashMap::from([(
+            "env".to_string(),
+            "wasi_snapshot_preview1".to_string(),
+        )]));
+
+        let imports = imports! {
+            "wasi_snapshot_preview1" => { "f" => Function::new_typed(&mut store, || {}) },
+            "other" => { "g" => Function::new_typed(&mut store, || {}) },
+        };
+        Instance::new(&mut store, &module, &imports).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn link_with_imports_override() {
+        use std::collections::HashMap;
+
+        use crate::{imports, Function, Instance, LinkError, TypedFunction};
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "answer" (func $answer (result i32)))
+                (func (export "run") (result i32) call $answer))"#,
+        )
+        .unwrap();
+        let module =
+            module.with_imports_override(&HashMap::from([("env".to_string(), "host".to_string())]));
+
+        let instance = Instance::new_allow_unresolved(&mut store, &module, &imports! {}).unwrap();
+        let answer = Function::new_typed(&mut store, || 42);
+        // The import is only known under its new name.
+        assert!(matches!(
+            instance.link_with(&mut store, "env", "answer", answer.clone().into()),
+            Err(LinkError::Import(..))
+        ));
+        instance
+            .link_with(&mut store, "host", "answer", answer.into())
+            .unwrap();
+
+        let run: TypedFunction<(), i32> =
+            instance.exports.get_typed_function(&store, "run").unwrap();
+        assert_eq!(run.call(&mut store).unwrap(), 42);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn from_file_detects_the_format() {
+        use crate::IoCompileError;
+
+        let store = Store::default();
+        let dir = tempfile::tempdir().unwrap();
+        let wasm = wat::parse_str(r#"(module (func (export "run")))"#).unwrap();
+        // The extension doesn't matter, only the content does.
+        let binary = dir.path().join("binary.wat");
+        std::fs::write(&binary, &wasm).unwrap();
+        let module = Module::from_file(&store, &binary).unwrap();
+        assert!(module.exports().any(|export| export.name() == "run"));
+        assert!(module.name().unwrap().ends_with("binary.wat"));
+
+        #[cfg(feature = "wat")]
+        {
+            let text = dir.path().join("text.wasm");
+            std::fs::write(&text, r#"(module (func (export "run")))"#).unwrap();
+            let module = Module::from_file(&store, &text).unwrap();
+            assert!(module.exports().any(|export| export.name() == "run"));
+        }
+
+        let missing = Module::from_file(&store, dir.path().join("missing.wasm"));
+        assert!(matches!(missing, Err(IoCompileError::Io(_))));
+        let invalid = dir.path().join("invalid.wasm");
+        std::fs::write(&invalid, b"\0asm\x01\0\0\0\xff").unwrap();
+        assert!(matches!(
+            Module::from_file(&store, &invalid),
+            Err(IoCompileError::Compile(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "wat")]
+    fn from_wat() {
+        use crate::CompileError;
+
+        let store = Store::default();
+        let module = Module::from_wat(&store, r#"(module (func (export "run")))"#).unwrap();
+        assert!(module.exports().any(|export| export.name() == "run"));
+        let module = Module::from_wat_bytes(&store, br#"(module (func (export "run")))"#).unwrap();
+        assert!(module.exports().any(|export| export.name() == "run"));
+
+        assert!(matches!(
+            Module::from_wat(&store, "(module (func"),
+            Err(CompileError::Wasm(_))
+        ));
+        // Wasm binaries are not text.
+        let wasm = wat::parse_str("(module)").unwrap();
+        assert!(Module::from_wat_bytes(&store, &wasm).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_serialization() {
+        use crate::Engine;
+
+        let engine: Engine = crate::wamr::Wamr::new().into();
+        let module = Module::new(&engine, r#"(module $m (func (export "run")))"#).unwrap();
+        let bytes = module.serialize().unwrap();
+        let module = unsafe { Module::deserialize(&engine, bytes) }.unwrap();
+        assert_eq!(module.name(), Some("m"));
+        assert!(module.exports().any(|export| export.name() == "run"));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_data_segments() {
+        use crate::Engine;
+
+        let engine: Engine = crate::wamr::Wamr::new().into();
+        let module = Module::new(
+            &engine,
+            r#"(module
+                (import "env" "base" (global $base i32))
+                (memory 1)
+                (data (i32.const 16) "hello")
+                (data "world")
+                (data (global.get $base) "!"))"#,
+        )
+        .unwrap();
+        let segments = module.data_segments().collect::<Vec<_>>();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0].memory_index, Some(0));
+        assert_eq!(segments[0].offset, Some(16));
+        assert_eq!(segments[0].data, b"hello");
+        assert_eq!(segments[1].global_base, Some(0));
+        assert_eq!(segments[1].data, b"!");
+        assert_eq!(segments[2].memory_index, None);
+        assert_eq!(segments[2].offset, None);
+        assert_eq!(segments[2].data, b"world");
+    }
+
+    #[test]
+    #[cfg(feature = "v8")]
+    fn v8_serialization() {
+        use crate::{DeserializeError, Engine};
+
+        let engine: Engine = crate::v8::V8::new().into();
+        let module = Module::new(&engine, r#"(module $m (func (export "run")))"#).unwrap();
+        let bytes = module.serialize().unwrap();
+        let module = unsafe { Module::deserialize(&engine, bytes) }.unwrap();
+        assert_eq!(module.name(), Some("m"));
+        assert!(module.exports().any(|export| export.name() == "run"));
+
+        assert!(matches!(
+            unsafe { Module::deserialize(&engine, vec![1, 2]) },
+            Err(DeserializeError::CorruptedBinary(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_execution_modes() {
+        use crate::{wamr::ExecutionMode, CompileError, Engine};
+
+        let engine: Engine = crate::wamr::Wamr::new().into();
+        let wasm = wat::parse_str(r#"(module (func (export "run")))"#).unwrap();
+        let module =
+            Module::new_wamr_with_mode(&engine, &wasm, ExecutionMode::Interpreter).unwrap();
+        assert!(module.exports().any(|export| export.name() == "run"));
+
+        assert!(!ExecutionMode::FastJit.is_supported());
+        assert!(matches!(
+            Module::new_wamr_with_mode(&engine, &wasm, ExecutionMode::FastJit),
+            Err(CompileError::UnsupportedFeature(_))
+        ));
+
+        // Wasm bytes are not an AOT module.
+        assert!(matches!(
+            Module::new_wamr_with_mode(&engine, &wasm, ExecutionMode::Aot),
+            Err(CompileError::Validate(_))
+        ));
+        // The bundled WAMR is built without AOT support, `.aot` files are
+        // detected and rejected cleanly.
+        let aot = b"\0aot\x03\0\0\0";
+        assert!(matches!(
+            Module::new_wamr_with_mode(&engine, aot, ExecutionMode::Aot),
+            Err(CompileError::UnsupportedFeature(_))
+        ));
+        assert!(matches!(
+            Module::from_binary(&engine, aot),
+            Err(CompileError::UnsupportedFeature(_))
+        ));
+    }
+}
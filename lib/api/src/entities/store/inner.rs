@@ -17,6 +17,8 @@ pub(crate) struct StoreInner {
     pub(crate) objects: StoreObjects,
     pub(crate) store: BackendStore,
     pub(crate) on_called: Option<OnCalledHandler>,
+    /// Only ever referenced weakly, by the [`StoreWeak`](crate::StoreWeak)s of the store.
+    pub(crate) liveness: std::sync::Arc<()>,
 }
 
 impl std::fmt::Debug for StoreInner {
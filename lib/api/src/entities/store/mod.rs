@@ -11,9 +11,14 @@ pub use store_ref::*;
 mod obj;
 pub use obj::*;
 
-use crate::{AsEngineRef, BackendEngine, Engine, EngineRef};
+use crate::{
+    error::RuntimeError, AsEngineRef, BackendEngine, Engine, EngineRef, Extern, Global, Memory,
+    Table, Tag, Value,
+};
 pub(crate) use inner::*;
-use wasmer_types::StoreId;
+use std::sync::{Arc, Weak};
+use thiserror::Error;
+use wasmer_types::{MemoryError, StoreId};
 
 #[cfg(feature = "sys")]
 use wasmer_vm::TrapHandlerFn;
@@ -68,11 +73,20 @@ impl Store {
             inner: Box::new(StoreInner {
                 objects: StoreObjects::from_store_ref(&store),
                 on_called: None,
+                liveness: Arc::new(()),
                 store,
             }),
         }
     }
 
+    /// Creates a new `Store` with an already-built [`Engine`].
+    ///
+    /// Engines are cheap to clone, so a single engine can back many stores,
+    /// for example one per thread, without compiling modules more than once.
+    pub fn with_engine(engine: Engine) -> Self {
+        Self::new(engine)
+    }
+
     #[cfg(feature = "sys")]
     /// Set the [`TrapHandlerFn`] for this store.
     ///
@@ -88,11 +102,101 @@ impl Store {
         }
     }
 
+    #[cfg(feature = "sys")]
+    /// Set the [`TrapHandlerFn`] for the store behind any store handle,
+    /// such as a [`StoreMut`] or a [`FunctionEnvMut`](crate::FunctionEnvMut).
+    ///
+    /// The `sys` backend catches traps with a process-wide signal handler.
+    /// When a signal is raised while executing code of this store, it is
+    /// first passed to `handler`: if it returns `true`, the signal is
+    /// considered handled and execution resumes. Otherwise it falls
+    /// through to the default handling, which turns faults in Wasm code
+    /// into traps and forwards the others to the previously installed
+    /// handler. Other stores are not affected.
+    ///
+    /// Stores of other backends don't use signals, and are left untouched.
+    pub fn set_signal_handler(store: &mut impl AsStoreMut, handler: Box<TrapHandlerFn<'static>>) {
+        use crate::backend::sys::entities::store::NativeStoreExt;
+        #[allow(irrefutable_let_patterns)]
+        if let BackendStore::Sys(ref mut s) = store.as_store_mut().inner.store {
+            s.set_trap_handler(Some(handler))
+        }
+    }
+
+    #[cfg(feature = "sys")]
+    /// Sets the size in bytes of the stack Wasm code of this store runs on,
+    /// overriding the one of the engine's tunables. `None` goes back to the
+    /// engine's.
+    ///
+    /// Stack overflows report this size, see [`RuntimeError::stack_size`].
+    ///
+    /// Stores of other backends don't use a separate stack, and are left
+    /// untouched.
+    ///
+    /// [`RuntimeError::stack_size`]: crate::RuntimeError::stack_size
+    pub fn set_wasm_stack_size(&mut self, size: Option<usize>) {
+        #[allow(irrefutable_let_patterns)]
+        if let BackendStore::Sys(ref mut s) = self.inner.store {
+            s.wasm_stack_size = size;
+        }
+    }
+
+    /// Releases the host objects of the [`ExternRef`]s of this store that
+    /// are not used anymore, and returns how many were released.
+    ///
+    /// An object is released when no [`ExternRef`] to it is left, and no
+    /// table or global of the store references it. [`WeakExternRef`]s to
+    /// it can't be upgraded anymore.
+    ///
+    /// Only the `sys` backend releases objects: the other ones keep them
+    /// until the store is dropped, and this returns 0.
+    ///
+    /// [`ExternRef`]: crate::ExternRef
+    /// [`WeakExternRef`]: crate::WeakExternRef
+    pub fn gc(&mut self) -> usize {
+        #[cfg(feature = "sys")]
+        #[allow(irrefutable_let_patterns)]
+        if let BackendStore::Sys(_) = self.inner.store {
+            // SAFETY: the store is borrowed mutably, so none of its Wasm
+            // code is running.
+            return unsafe { self.objects_mut().as_sys_mut().release_extern_objs() };
+        }
+        0
+    }
+
+    /// Returns how many objects of each extern type live in the given store,
+    /// e.g. to monitor its growth.
+    ///
+    /// Only the `sys` backend keeps these objects in the store: the other
+    /// ones leave them to the underlying engine, which doesn't count them,
+    /// and this returns `None`.
+    pub fn object_count(store: &impl AsStoreRef) -> Option<ObjectCounts> {
+        #[cfg(feature = "sys")]
+        #[allow(irrefutable_let_patterns)]
+        if let crate::StoreObjects::Sys(objects) = store.as_store_ref().objects() {
+            return Some(ObjectCounts {
+                functions: objects.count::<wasmer_vm::VMFunction>(),
+                memories: objects.count::<wasmer_vm::VMMemory>(),
+                globals: objects.count::<wasmer_vm::VMGlobal>(),
+                tables: objects.count::<wasmer_vm::VMTable>(),
+                extern_refs: objects.live_extern_objs(),
+            });
+        }
+        let _ = store;
+        None
+    }
+
     /// Returns the [`Engine`].
     pub fn engine(&self) -> &Engine {
         self.inner.store.engine()
     }
 
+    /// Returns the [`Engine`] of any store handle, such as a [`StoreMut`]
+    /// or a [`FunctionEnvMut`](crate::FunctionEnvMut).
+    pub fn engine_of(store: &impl AsStoreRef) -> &Engine {
+        store.as_store_ref().engine()
+    }
+
     /// Returns mutable reference to [`Engine`].
     pub fn engine_mut(&mut self) -> &mut Engine {
         self.inner.store.engine_mut()
@@ -108,6 +212,171 @@ impl Store {
     pub fn id(&self) -> StoreId {
         self.inner.objects.id()
     }
+
+    /// Creates a [`StoreWeak`] tracking whether this store is still alive,
+    /// without keeping it alive.
+    pub fn downgrade(&self) -> StoreWeak {
+        StoreWeak {
+            id: self.id(),
+            liveness: Arc::downgrade(&self.inner.liveness),
+        }
+    }
+
+    /// Moves an [`Extern`] living in the store `from` into the store `to`.
+    ///
+    /// The backing data of the extern is deep-copied and registered in the
+    /// destination store: memories are copied page by page, globals keep their
+    /// value and mutability and tables keep their type and entries. Tags are
+    /// re-created with the same signature.
+    ///
+    /// The original extern is left untouched in `from`.
+    ///
+    /// # Errors
+    ///
+    /// Functions are bound to the store they were created in and cannot be
+    /// transferred: [`TransferError::CannotTransferFunction`] is returned for
+    /// them, as well as for globals and tables holding a non-null `funcref`.
+    /// Other non-null references can't be transferred either.
+    pub fn transfer_extern(
+        from: &mut impl AsStoreMut,
+        to: &mut impl AsStoreMut,
+        extern_val: Extern,
+    ) -> Result<Extern, TransferError> {
+        if !extern_val.is_from_store(from) {
+            return Err(TransferError::WrongStore);
+        }
+
+        match extern_val {
+            Extern::Function(_) => Err(TransferError::CannotTransferFunction),
+            Extern::Memory(memory) => Ok(Extern::Memory(memory.copy_to_store(from, to)?)),
+            Extern::Global(global) => {
+                let ty = global.ty(from);
+                let value = transfer_value(global.get(from))?;
+                let global = if ty.mutability.is_mutable() {
+                    Global::new_mut(to, value)
+                } else {
+                    Global::new(to, value)
+                };
+                Ok(Extern::Global(global))
+            }
+            Extern::Table(table) => {
+                let ty = table.ty(from);
+                let size = table.size(from);
+                // Check every element before allocating anything in `to`, so
+                // that a failure doesn't leave a half-filled table behind.
+                let values = (0..size)
+                    .map(|index| table.get(from, index).map(transfer_value).transpose())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let null = Value::default_for(ty.ty);
+                let new_table = Table::new(to, ty, null.clone())?;
+                if size > ty.minimum {
+                    new_table.grow(to, size - ty.minimum, null)?;
+                }
+                for (index, value) in (0..size).zip(values) {
+                    if let Some(value) = value {
+                        new_table.set(to, index, value)?;
+                    }
+                }
+                Ok(Extern::Table(new_table))
+            }
+            Extern::Tag(tag) => {
+                let ty = tag.ty(from);
+                Ok(Extern::Tag(Tag::new(to, ty.params())))
+            }
+        }
+    }
+}
+
+/// Checks that a value is not tied to its store, so that it can be moved in another one.
+fn transfer_value(value: Value) -> Result<Value, TransferError> {
+    match value {
+        Value::FuncRef(Some(_)) => Err(TransferError::CannotTransferFunction),
+        Value::ExternRef(Some(_)) | Value::ExceptionRef(Some(_)) => {
+            Err(TransferError::CannotTransferReference)
+        }
+        value => Ok(value),
+    }
+}
+
+/// An error that can happen while moving an [`Extern`] between stores with
+/// [`Store::transfer_extern`].
+#[derive(Debug, Error)]
+pub enum TransferError {
+    /// Functions are bound to the store they were created in.
+    #[error("functions cannot be transferred between stores")]
+    CannotTransferFunction,
+    /// A non-null reference (other than a `funcref`) was found in the extern.
+    #[error("non-null references cannot be transferred between stores")]
+    CannotTransferReference,
+    /// The extern does not belong to the source store.
+    #[error("the extern does not belong to the source store")]
+    WrongStore,
+    /// The memory could not be copied in the destination store.
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+    /// The table could not be created or filled in the destination store.
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
+
+/// Number of objects of each extern type living in a store, as returned by
+/// [`Store::object_count`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectCounts {
+    /// Number of functions, host and Wasm ones.
+    pub functions: usize,
+    /// Number of memories.
+    pub memories: usize,
+    /// Number of globals.
+    pub globals: usize,
+    /// Number of tables.
+    pub tables: usize,
+    /// Number of [`ExternRef`](crate::ExternRef) objects not yet released by
+    /// [`Store::gc`].
+    pub extern_refs: usize,
+}
+
+/// A non-owning reference to a [`Store`], created with [`Store::downgrade`].
+///
+/// A [`Store`] is only ever accessed through its owner, so a `StoreWeak`
+/// can't give access to it. It lets long-lived structures holding objects
+/// of a store, such as a pool of instances, know whether the store is
+/// still alive and which store the objects belong to, without preventing
+/// the store from being dropped.
+///
+/// # Example
+/// ```
+/// # use wasmer::Store;
+/// let store = Store::default();
+/// let weak = store.downgrade();
+/// assert!(weak.is_alive());
+/// assert_eq!(weak.id(), store.id());
+///
+/// drop(store);
+/// assert!(!weak.is_alive());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StoreWeak {
+    id: StoreId,
+    liveness: Weak<()>,
+}
+
+impl StoreWeak {
+    /// Returns the ID of the store.
+    pub fn id(&self) -> StoreId {
+        self.id
+    }
+
+    /// Returns true if the store has not been dropped yet.
+    pub fn is_alive(&self) -> bool {
+        self.liveness.strong_count() > 0
+    }
+
+    /// Returns true if `store` is the store this `StoreWeak` refers to.
+    pub fn is(&self, store: &impl AsStoreRef) -> bool {
+        self.is_alive() && store.as_store_ref().objects().id() == self.id
+    }
 }
 
 impl PartialEq for Store {
@@ -159,3 +428,282 @@ impl AsStoreMut for Store {
         &mut self.inner.objects
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{imports, AsStoreMut, Engine, Instance, Module, Store};
+
+    #[test]
+    fn stores_share_an_engine() {
+        let engine = Engine::default();
+        let module = Module::new(&engine, "(module (func (export \"run\")))").unwrap();
+
+        let handles = (0..2)
+            .map(|_| {
+                let engine = engine.clone();
+                let module = module.clone();
+                std::thread::spawn(move || {
+                    let mut store = Store::with_engine(engine.clone());
+                    assert_eq!(Store::engine_of(&store.as_store_mut()).id(), engine.id());
+                    let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+                    let run = instance.exports.get_function("run").unwrap();
+                    run.call(&mut store, &[]).unwrap();
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "sys", unix))]
+    fn store_signal_handler() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let engine = Engine::default();
+        let module = Module::new(
+            &engine,
+            r#"(module
+                (memory 1)
+                (func (export "load") (result i32)
+                    (i32.load (i32.const 0xdeadbeef))))"#,
+        )
+        .unwrap();
+        let load = |store: &mut Store| {
+            let instance = Instance::new(store, &module, &imports! {}).unwrap();
+            let load = instance.exports.get_function("load").unwrap();
+            load.call(store, &[]).unwrap_err()
+        };
+
+        let signals = Arc::new(AtomicUsize::new(0));
+        let mut store = Store::new(engine.clone());
+        Store::set_signal_handler(&mut store.as_store_mut(), {
+            let signals = signals.clone();
+            Box::new(move |_, _, _| {
+                signals.fetch_add(1, Ordering::SeqCst);
+                false
+            })
+        });
+
+        // Unhandled signals fall through to the default trap handling.
+        let error = load(&mut store);
+        assert_eq!(
+            error.to_trap(),
+            Some(wasmer_types::TrapCode::HeapAccessOutOfBounds)
+        );
+        assert_eq!(signals.load(Ordering::SeqCst), 1);
+
+        // Other stores keep the default handler only.
+        load(&mut Store::new(engine));
+        assert_eq!(signals.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn object_count() {
+        use crate::{
+            ExternRef, Function, Global, Memory, MemoryType, ObjectCounts, Table, TableType, Value,
+        };
+        use wasmer_types::Type;
+
+        let mut store = Store::default();
+        assert_eq!(Store::object_count(&store), Some(ObjectCounts::default()));
+
+        Function::new_typed(&mut store, || {});
+        Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+        Global::new(&mut store, Value::I32(0));
+        Global::new_mut(&mut store, Value::I64(0));
+        Table::new(
+            &mut store,
+            TableType::new(Type::FuncRef, 1, None),
+            Value::FuncRef(None),
+        )
+        .unwrap();
+        let kept = ExternRef::new(&mut store, 1u32);
+        drop(ExternRef::new(&mut store, 2u32));
+
+        let counts = ObjectCounts {
+            functions: 1,
+            memories: 1,
+            globals: 2,
+            tables: 1,
+            extern_refs: 2,
+        };
+        assert_eq!(Store::object_count(&store.as_store_mut()), Some(counts));
+
+        assert_eq!(store.gc(), 1);
+        assert_eq!(Store::object_count(&store).unwrap().extern_refs, 1);
+        drop(kept);
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn transfer_table_with_a_function() {
+        use crate::{Extern, Function, ObjectCounts, Table, TableType, TransferError, Value};
+        use wasmer_types::Type;
+
+        let mut from = Store::default();
+        let mut to = Store::default();
+        let table = Table::new(
+            &mut from,
+            TableType::new(Type::FuncRef, 2, None),
+            Value::FuncRef(None),
+        )
+        .unwrap();
+        let function = Function::new_typed(&mut from, || {});
+        table
+            .set(&mut from, 1, Value::FuncRef(Some(function)))
+            .unwrap();
+
+        assert!(matches!(
+            Store::transfer_extern(&mut from, &mut to, Extern::Table(table)),
+            Err(TransferError::CannotTransferFunction)
+        ));
+        // Nothing was allocated in the destination store.
+        assert_eq!(Store::object_count(&to), Some(ObjectCounts::default()));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_object_count() {
+        use crate::Function;
+
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        Function::new_typed(&mut store, || {});
+        // The objects are not counted, rather than reported as none.
+        assert_eq!(Store::object_count(&store), None);
+    }
+
+    #[test]
+    fn weak_store() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let store = Store::default();
+        let other = Store::default();
+        let weak = store.downgrade();
+        assert_send_sync(&weak);
+        assert!(weak.is_alive());
+        assert!(weak.is(&store));
+        assert!(!weak.is(&other));
+
+        let clone = weak.clone();
+        drop(store);
+        assert!(!weak.is_alive());
+        assert!(!clone.is_alive());
+        assert!(!other.downgrade().is(&Store::default()));
+    }
+
+    #[test]
+    #[cfg(feature = "v8")]
+    fn v8_stores_on_two_threads() {
+        use std::sync::{Arc, Barrier};
+
+        let barrier = Arc::new(Barrier::new(2));
+        let handles = (0..2)
+            .map(|_| {
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    let mut store = Store::new(crate::backend::v8::V8::new());
+                    let module = Module::new(
+                        &store,
+                        r#"(module
+                            (func (export "sum") (param i32) (result i32)
+                                (local i32)
+                                (block
+                                    (loop
+                                        (br_if 1 (i32.eqz (local.get 0)))
+                                        (local.set 1 (i32.add (local.get 1) (local.get 0)))
+                                        (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                                        (br 0)))
+                                (local.get 1)))"#,
+                    )
+                    .unwrap();
+                    let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+                    let sum = instance
+                        .exports
+                        .get_typed_function::<i32, i32>(&store, "sum")
+                        .unwrap();
+
+                    // Both stores run their guests at the same time.
+                    barrier.wait();
+                    for _ in 0..100 {
+                        assert_eq!(sum.call(&mut store, 10_000).unwrap(), 50_005_000);
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "v8")]
+    fn v8_memory_exhaustion_is_contained() {
+        use std::sync::{Arc, Barrier};
+
+        let barrier = Arc::new(Barrier::new(2));
+
+        // A guest growing its memory until the isolate of its store refuses.
+        let exhausted = std::thread::spawn({
+            let barrier = barrier.clone();
+            move || {
+                let mut store = Store::new(crate::backend::v8::V8::new());
+                let module = Module::new(
+                    &store,
+                    r#"(module
+                        (memory 1)
+                        (func (export "exhaust") (result i32)
+                            (loop
+                                (br_if 0 (i32.ne (memory.grow (i32.const 0x1000)) (i32.const -1))))
+                            (memory.size)))"#,
+                )
+                .unwrap();
+                let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+                let exhaust = instance
+                    .exports
+                    .get_typed_function::<(), i32>(&store, "exhaust")
+                    .unwrap();
+                barrier.wait();
+                // The isolate refuses to grow the memory, rather than
+                // trapping or aborting: the memory kept its last size.
+                let pages = exhaust.call(&mut store).unwrap();
+                assert!((1..=0x10000).contains(&pages));
+                assert_eq!(pages % 0x1000, 1);
+            }
+        });
+
+        let mut store = Store::new(crate::backend::v8::V8::new());
+        let module = Module::new(
+            &store,
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "store") (param i32)
+                    (i32.store (i32.const 0) (local.get 0)))
+                (func (export "load") (result i32)
+                    (i32.load (i32.const 0))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let store_fn = instance
+            .exports
+            .get_typed_function::<i32, ()>(&store, "store")
+            .unwrap();
+        let load = instance
+            .exports
+            .get_typed_function::<(), i32>(&store, "load")
+            .unwrap();
+        barrier.wait();
+        for i in 0..1_000 {
+            store_fn.call(&mut store, i).unwrap();
+            assert_eq!(load.call(&mut store).unwrap(), i);
+        }
+        exhausted.join().unwrap();
+
+        // The store keeps working after the other one ran out of memory.
+        store_fn.call(&mut store, 42).unwrap();
+        assert_eq!(load.call(&mut store).unwrap(), 42);
+    }
+}
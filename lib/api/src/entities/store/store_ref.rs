@@ -20,7 +20,7 @@ impl<'a> StoreRef<'a> {
     }
 
     /// Returns the [`Engine`].
-    pub fn engine(&self) -> &Engine {
+    pub fn engine(&self) -> &'a Engine {
         self.inner.store.engine()
     }
 
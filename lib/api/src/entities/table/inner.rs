@@ -77,6 +77,15 @@ impl BackendTable {
         })
     }
 
+    /// Checks whether the element at the provided `index` is a null reference,
+    /// without materializing it as a [`Value`].
+    #[inline]
+    pub fn is_null_at(&self, store: &impl AsStoreRef, index: u32) -> Result<bool, RuntimeError> {
+        match_rt!(on self => s {
+            s.is_null_at(store, index)
+        })
+    }
+
     /// Sets an element `val` in the Table at the provided `index`.
     #[inline]
     pub fn set(
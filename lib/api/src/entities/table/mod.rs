@@ -47,6 +47,18 @@ impl Table {
         self.0.get(store, index)
     }
 
+    /// Checks whether the element at the provided `index` is a null reference.
+    ///
+    /// Unlike [`Table::get`], this does not materialize the element as a
+    /// [`Value`], so it's cheap to call when scanning a table for empty slots.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of bounds.
+    pub fn is_null_at(&self, store: &impl AsStoreRef, index: u32) -> Result<bool, RuntimeError> {
+        self.0.is_null_at(store, index)
+    }
+
     /// Sets an element `val` in the Table at the provided `index`.
     pub fn set(
         &self,
@@ -121,6 +133,12 @@ impl<'a> Exportable<'a> for Table {
     }
 }
 
+/// The error of [`Table::is_null_at`] for an out-of-bounds `index`, the
+/// same on every backend.
+pub(crate) fn out_of_bounds(index: u32) -> RuntimeError {
+    RuntimeError::new(format!("table index {index} is out of bounds"))
+}
+
 #[cfg(test)]
 mod test {
     /// Check the example from <https://github.com/wasmerio/wasmer/issues/3197>.
@@ -150,4 +168,18 @@ mod test {
         let imports = imports! {"env" => {"table" => table}};
         let _instance = Instance::new(&mut store, &module, &imports).unwrap();
     }
+
+    #[test]
+    fn table_is_null_at() {
+        use crate::{RuntimeErrorKind, Store, Table, TableType, Type, Value};
+
+        let mut store = Store::default();
+        let ty = TableType::new(Type::FuncRef, 2, None);
+        let table = Table::new(&mut store, ty, Value::FuncRef(None)).unwrap();
+        assert!(table.is_null_at(&store, 0).unwrap());
+        assert!(table.is_null_at(&store, 1).unwrap());
+        let err = table.is_null_at(&store, 2).unwrap_err();
+        assert_eq!(err.kind(), RuntimeErrorKind::Message);
+        assert_eq!(err.message(), "table index 2 is out of bounds");
+    }
 }
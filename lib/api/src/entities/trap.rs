@@ -1,6 +1,6 @@
 use std::{any::Any, error::Error, fmt::Debug};
 
-use crate::{macros::backend::match_rt, RuntimeError};
+use crate::{error::TypedUserError, macros::backend::match_rt, RuntimeError};
 
 /// An enumeration of all the trap kinds supported by the runtimes.
 #[derive(Debug, derive_more::From)]
@@ -87,6 +87,61 @@ impl BackendTrap {
             s.is::<T>()
         })
     }
+
+    /// Unwraps an error boxed with [`crate::error::box_user_error`],
+    /// returning the trap of the error itself and the name of its type.
+    pub(crate) fn take_user_type_name(self) -> (Self, Option<&'static str>) {
+        match self {
+            #[cfg(feature = "sys")]
+            Self::Sys(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::Sys(crate::backend::sys::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::Sys(s), None),
+            },
+            #[cfg(feature = "wamr")]
+            Self::Wamr(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::Wamr(crate::backend::wamr::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::Wamr(s), None),
+            },
+            #[cfg(feature = "wasmi")]
+            Self::Wasmi(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::Wasmi(crate::backend::wasmi::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::Wasmi(s), None),
+            },
+            #[cfg(feature = "v8")]
+            Self::V8(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::V8(crate::backend::v8::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::V8(s), None),
+            },
+            #[cfg(feature = "js")]
+            Self::Js(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::Js(crate::backend::js::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::Js(s), None),
+            },
+            #[cfg(feature = "jsc")]
+            Self::Jsc(s) => match s.downcast::<TypedUserError>() {
+                Ok(e) => (
+                    Self::Jsc(crate::backend::jsc::vm::Trap::user(e.error)),
+                    Some(e.type_name),
+                ),
+                Err(s) => (Self::Jsc(s), None),
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for BackendTrap {
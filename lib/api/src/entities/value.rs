@@ -3,7 +3,7 @@ use wasmer_types::{RawValue, Type};
 use crate::{
     entities::{ExceptionRef, ExternRef, Function},
     vm::{VMExceptionRef, VMExternRef, VMFuncRef},
-    AsStoreRef, Tag,
+    AsStoreRef, NotAReferenceTypeError, Tag, TypeError,
 };
 
 /// WebAssembly computations manipulate values of basic value types:
@@ -68,12 +68,67 @@ macro_rules! accessors {
     )*)
 }
 
+macro_rules! checked_accessors {
+    ($(($variant:ident($ty:ty) $get:ident $as:ident $try_as:ident))*) => ($(
+        /// Returns the underlying value of this `Value`, or `None` if it is
+        /// not the correct type.
+        pub fn $as(&self) -> Option<$ty> {
+            self.$get()
+        }
+
+        /// Returns the underlying value of this `Value`, or a [`TypeError`]
+        /// with the expected and actual types if it is not the correct type.
+        pub fn $try_as(&self) -> Result<$ty, TypeError> {
+            self.$get().ok_or(TypeError {
+                expected: Type::$variant,
+                actual: self.ty(),
+            })
+        }
+    )*)
+}
+
 impl Value {
     /// Returns a null `externref` value.
     pub fn null() -> Self {
         Self::ExternRef(None)
     }
 
+    /// Returns the null reference of the reference type `ty`, or an error if
+    /// `ty` is a numeric type.
+    pub fn null_ref(ty: Type) -> Result<Self, NotAReferenceTypeError> {
+        if ty.is_ref() {
+            Ok(Self::default_for(ty))
+        } else {
+            Err(NotAReferenceTypeError(ty))
+        }
+    }
+
+    /// Returns true if this is a null reference, of any reference type.
+    pub fn is_null_ref(&self) -> bool {
+        matches!(
+            self,
+            Self::ExternRef(None) | Self::FuncRef(None) | Self::ExceptionRef(None)
+        )
+    }
+
+    /// Returns the default value of the given [`Type`]: zero for numeric
+    /// types and `null` for reference types.
+    ///
+    /// This is the value locals, globals and table slots hold before being
+    /// initialized.
+    pub fn default_for(ty: Type) -> Self {
+        match ty {
+            Type::I32 => Self::I32(0),
+            Type::I64 => Self::I64(0),
+            Type::F32 => Self::F32(0.0),
+            Type::F64 => Self::F64(0.0),
+            Type::V128 => Self::V128(0),
+            Type::ExternRef => Self::ExternRef(None),
+            Type::FuncRef => Self::FuncRef(None),
+            Type::ExceptionRef => Self::ExceptionRef(None),
+        }
+    }
+
     /// Returns the corresponding [`Type`] for this [`Value`].
     pub fn ty(&self) -> Type {
         match self {
@@ -105,10 +160,42 @@ impl Value {
         }
     }
 
+    /// Converts the `Value` into a `RawValue`, the untyped representation
+    /// compiled code exchanges values in.
+    ///
+    /// This is [`Value::as_raw`], for use with [`Value::from_raw_value`].
+    ///
+    /// # Safety
+    ///
+    /// For references, the raw value points into `store` without keeping
+    /// the referenced object alive or tracking where the pointer goes: it
+    /// must only be handed to code running against the same store, and
+    /// must not be used once the store is dropped.
+    pub unsafe fn to_raw_value(&self, store: &impl AsStoreRef) -> RawValue {
+        self.as_raw(store)
+    }
+
+    /// Converts a `RawValue` of type `ty` back to a `Value`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must hold a valid value of type `ty`. For references, it
+    /// must come from [`Value::to_raw_value`] or compiled code running
+    /// against the same, still alive `store`, since the pointer it holds
+    /// is dereferenced as an object of `store`.
+    pub unsafe fn from_raw_value(
+        store: &mut impl crate::entities::store::AsStoreMut,
+        ty: Type,
+        raw: RawValue,
+    ) -> Self {
+        Self::from_raw(store, ty, raw)
+    }
+
     /// Converts a `RawValue` to a `Value`.
     ///
     /// # Safety
     ///
+    /// See [`Value::from_raw_value`].
     pub unsafe fn from_raw(
         store: &mut impl crate::entities::store::AsStoreMut,
         ty: Type,
@@ -273,6 +360,14 @@ impl Value {
         (FuncRef(&Option<Function>) funcref unwrap_funcref e)
         (V128(u128) v128 unwrap_v128 *e)
     }
+
+    checked_accessors! {
+        (I32(i32) i32 as_i32 try_as_i32)
+        (I64(i64) i64 as_i64 try_as_i64)
+        (F32(f32) f32 as_f32 try_as_f32)
+        (F64(f64) f64 as_f64 try_as_f64)
+        (V128(u128) v128 as_v128 try_as_v128)
+    }
 }
 
 impl std::fmt::Debug for Value {
@@ -492,6 +587,33 @@ impl TryFrom<Value> for Option<ExceptionRef> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn raw_value_round_trip() {
+        let mut store = crate::Store::default();
+        let values = [
+            Value::I32(-7),
+            Value::I64(i64::MAX),
+            Value::F32(1.5),
+            Value::F64(-0.25),
+            Value::V128(u128::MAX - 1),
+            Value::ExternRef(None),
+            Value::ExternRef(Some(ExternRef::new(&mut store, 42u64))),
+        ];
+        for value in values {
+            let raw = unsafe { value.to_raw_value(&store) };
+            let back = unsafe { Value::from_raw_value(&mut store, value.ty(), raw) };
+            assert_eq!(back.ty(), value.ty());
+            match (&value, &back) {
+                (Value::ExternRef(Some(a)), Value::ExternRef(Some(b))) => {
+                    assert_eq!(a.downcast::<u64>(&store), Some(&42));
+                    assert_eq!(b.downcast::<u64>(&store), Some(&42));
+                }
+                (Value::ExternRef(None), back) => assert!(matches!(back, Value::ExternRef(None))),
+                _ => assert_eq!(back, value),
+            }
+        }
+    }
+
     #[test]
     fn test_value_i32_from_u32() {
         let bytes = [0x00, 0x00, 0x00, 0x00];
@@ -619,4 +741,90 @@ mod tests {
         let result = f64::try_from(value);
         assert_eq!(result.unwrap_err(), "Value is not of Wasm type f64");
     }
+
+    #[test]
+    fn default_for_type() {
+        for ty in [
+            Type::I32,
+            Type::I64,
+            Type::F32,
+            Type::F64,
+            Type::V128,
+            Type::ExternRef,
+            Type::FuncRef,
+            Type::ExceptionRef,
+        ] {
+            let value = Value::default_for(ty);
+            assert_eq!(value.ty(), ty);
+            if ty.is_reference() {
+                assert!(matches!(
+                    value,
+                    Value::ExternRef(None) | Value::FuncRef(None) | Value::ExceptionRef(None)
+                ));
+            }
+        }
+        assert_eq!(Value::default_for(Type::I64), Value::I64(0));
+    }
+
+    #[test]
+    fn checked_accessors() {
+        assert_eq!(Value::I32(7).as_i32(), Some(7));
+        assert_eq!(Value::I32(7).as_i64(), None);
+        assert_eq!(Value::F64(1.5).try_as_f64(), Ok(1.5));
+        assert_eq!(
+            Value::I64(7).try_as_i32(),
+            Err(TypeError {
+                expected: Type::I32,
+                actual: Type::I64
+            })
+        );
+        assert_eq!(
+            Value::null().try_as_f32().unwrap_err().to_string(),
+            "expected a value of type F32, got ExternRef"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(not(feature = "sys"), ignore = "ExternRef is only supported by `sys`")]
+    fn null_refs() {
+        use crate::{imports, Instance, Module};
+
+        let mut store = crate::Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func (export "extern_is_null") (param externref) (result i32)
+                    (ref.is_null (local.get 0)))
+                (func (export "func_is_null") (param funcref) (result i32)
+                    (ref.is_null (local.get 0))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let is_null = |store: &mut crate::Store, name: &str, value: Value| {
+            let func = instance.exports.get_function(name).unwrap();
+            func.call(store, &[value]).unwrap()[0].unwrap_i32() == 1
+        };
+
+        let null_extern = Value::null_ref(Type::ExternRef).unwrap();
+        let null_func = Value::null_ref(Type::FuncRef).unwrap();
+        assert!(null_extern.is_null_ref() && null_func.is_null_ref());
+        assert!(is_null(&mut store, "extern_is_null", null_extern));
+        assert!(is_null(&mut store, "func_is_null", null_func));
+        assert_eq!(
+            Value::null_ref(Type::I32),
+            Err(NotAReferenceTypeError(Type::I32))
+        );
+        assert!(!Value::I32(0).is_null_ref());
+
+        let externref = ExternRef::new(&mut store, 7u32);
+        let value = Value::ExternRef(Some(externref.clone()));
+        assert!(!value.is_null_ref());
+        assert!(!is_null(&mut store, "extern_is_null", value));
+
+        assert!(ExternRef::ptr_eq(&externref, &externref.clone()));
+        assert!(!ExternRef::ptr_eq(
+            &externref,
+            &ExternRef::new(&mut store, 7u32)
+        ));
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use thiserror::Error;
-use wasmer_types::{FrameInfo, ImportError, TrapCode};
+use wasmer_types::{ExternType, FrameInfo, ImportError, TrapCode, Type};
 
 use crate::BackendTrap as Trap;
 
@@ -18,12 +18,90 @@ pub enum LinkError {
     #[cfg_attr(feature = "std", error("Error while importing {0:?}.{1:?}: {2}"))]
     Import(String, String, ImportError),
 
+    /// Some imports of the module are missing or have the wrong type.
+    ///
+    /// Every unresolved import is listed, in the order the module declares
+    /// them. `layers` is the number of chained [`Imports`](crate::Imports)
+    /// that were consulted, see [`Imports::chain`](crate::Imports::chain).
+    #[cfg_attr(feature = "std", error("{}", format_issues(issues, *layers)))]
+    Unresolved {
+        /// The imports that could not be resolved.
+        issues: Vec<LinkIssue>,
+        /// The number of import layers consulted.
+        layers: usize,
+    },
+
+    /// The import was already satisfied, see
+    /// [`Instance::link_with`](crate::Instance::link_with).
+    #[cfg_attr(feature = "std", error("Import {0:?}.{1:?} is already linked"))]
+    AlreadyLinked(String, String),
+
     /// A trap ocurred during linking.
     #[cfg_attr(feature = "std", error("RuntimeError occurred during linking: {0}"))]
     Trap(#[source] RuntimeError),
     /// Insufficient resources available for linking.
     #[cfg_attr(feature = "std", error("Insufficient resources: {0}"))]
     Resource(String),
+
+    /// The backend of the store does not support the requested linking.
+    #[cfg_attr(feature = "std", error("Unsupported by the backend: {0}"))]
+    Unsupported(String),
+}
+
+/// An import that could not be resolved while linking a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkIssue {
+    /// The module name of the import.
+    pub module: String,
+    /// The name of the import.
+    pub name: String,
+    /// The type the module expects.
+    pub expected: ExternType,
+    /// The type of the extern that was provided, or `None` if the import is
+    /// missing.
+    pub provided: Option<ExternType>,
+    /// How the provided type differs from the expected one, such as
+    /// `param 2: expected i64, got i32`. Empty if the import is missing.
+    pub differences: Vec<String>,
+}
+
+/// An import whose provided extern is missing or has the wrong type, as
+/// reported by [`Module::check_imports`](crate::Module::check_imports).
+pub type ImportTypeMismatch = LinkIssue;
+
+impl std::fmt::Display for LinkIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.provided {
+            None => write!(
+                f,
+                "missing import {:?}.{:?} of type {:?}",
+                self.module, self.name, self.expected
+            ),
+            Some(provided) => {
+                write!(
+                    f,
+                    "incompatible import {:?}.{:?}: expected {:?}, found {:?}",
+                    self.module, self.name, self.expected, provided
+                )?;
+                if !self.differences.is_empty() {
+                    write!(f, " ({})", self.differences.join("; "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn format_issues(issues: &[LinkIssue], layers: usize) -> String {
+    let mut out = format!("{} unresolved import(s)", issues.len());
+    if layers > 1 {
+        out.push_str(&format!(" (searched {layers} chained import layers)"));
+    }
+    for issue in issues {
+        out.push_str("\n  ");
+        out.push_str(&issue.to_string());
+    }
+    out
 }
 
 /// An error while instantiating a module.
@@ -59,8 +137,92 @@ pub enum InstantiationError {
     /// This error occurs when an import from a different store is used.
     #[cfg_attr(feature = "std", error("incorrect OS or architecture"))]
     DifferentArchOS,
+
+    /// The module or an import comes from another backend than the store.
+    #[cfg_attr(feature = "std", error(transparent))]
+    BackendMismatch(BackendMismatchError),
+
+    /// The runtime could not allocate the resources of the instance, such
+    /// as its stack or heap.
+    #[cfg_attr(feature = "std", error("insufficient resources: {0}"))]
+    InsufficientResources(String),
+
+    /// The backend of the store does not support the requested way of
+    /// instantiating the module.
+    #[cfg_attr(feature = "std", error("unsupported by the backend: {0}"))]
+    Unsupported(String),
+}
+
+/// An object of one backend was used with a store of another backend.
+///
+/// This can only happen when several backends are enabled, for example
+/// when a function created in a `wamr` store is called with a `sys` store.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("expected an object from the `{expected}` backend, found one from `{found}`")]
+pub struct BackendMismatchError {
+    /// The backend of the store.
+    pub expected: &'static str,
+    /// The backend of the object used with it.
+    pub found: &'static str,
+}
+
+impl BackendMismatchError {
+    /// Checks that an object of the `found` backend can be used with a
+    /// store of the `expected` one.
+    pub(crate) fn check(expected: &'static str, found: &'static str) -> Result<(), Self> {
+        if expected == found {
+            Ok(())
+        } else {
+            Err(Self { expected, found })
+        }
+    }
+}
+
+impl From<BackendMismatchError> for RuntimeError {
+    fn from(error: BackendMismatchError) -> Self {
+        Self::user(Box::new(error))
+    }
 }
 
+/// A [`FunctionEnv`](crate::FunctionEnv) was used with another store than
+/// the one it was created in.
+///
+/// Returned by [`Function::try_new_with_env`] and
+/// [`Function::try_new_typed_with_env`].
+///
+/// [`Function::try_new_with_env`]: crate::Function::try_new_with_env
+/// [`Function::try_new_typed_with_env`]: crate::Function::try_new_typed_with_env
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the function environment was created in another store")]
+pub struct FunctionEnvStoreError;
+
+/// The backend of the store does not support native functions.
+///
+/// Returned by [`Function::new_raw`](crate::Function::new_raw) with the
+/// stores of other backends than `sys`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("native functions are not supported by the `{0}` backend")]
+pub struct NativeFunctionUnsupportedError(pub &'static str);
+
+/// A [`Value`](crate::Value) was not of the expected type.
+///
+/// Returned by the `try_as_*` accessors of [`Value`](crate::Value), such
+/// as [`Value::try_as_i32`](crate::Value::try_as_i32).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("expected a value of type {expected}, got {actual}")]
+pub struct TypeError {
+    /// The type that was expected.
+    pub expected: Type,
+    /// The type of the value.
+    pub actual: Type,
+}
+
+/// A null reference was requested for a [`Type`] that is not a reference
+/// type, by [`Value::null_ref`](crate::Value::null_ref).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{0} is not a reference type")]
+pub struct NotAReferenceTypeError(pub Type);
+
 /// A struct representing an aborted instruction execution, with a message
 /// indicating the cause.
 #[derive(Clone)]
@@ -68,6 +230,20 @@ pub struct RuntimeError {
     pub(crate) inner: Arc<RuntimeErrorInner>,
 }
 
+/// What a [`RuntimeError`] was raised from, see [`RuntimeError::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RuntimeErrorKind {
+    /// A plain message, created with [`RuntimeError::new`].
+    Message,
+    /// An error raised by the host, for example returned by a host
+    /// function, and available through [`std::error::Error::source`].
+    User,
+    /// A trap of the Wasm code or the runtime, such as `unreachable` or an
+    /// out-of-bounds access.
+    Trap,
+}
+
 #[derive(Debug)]
 struct RuntimeStringError {
     details: String,
@@ -91,13 +267,57 @@ impl std::error::Error for RuntimeStringError {
     }
 }
 
+/// An error returned by a host function, boxed with the name of its type.
+///
+/// The name is lost once the error is boxed, so the backends box the
+/// errors of the host functions with [`box_user_error`], and
+/// [`RuntimeError::new_from_source`] unwraps them again.
+#[derive(Debug)]
+pub(crate) struct TypedUserError {
+    pub(crate) type_name: &'static str,
+    pub(crate) error: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl std::fmt::Display for TypedUserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl std::error::Error for TypedUserError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Boxes the error returned by a host function, recording the name of its
+/// type for [`RuntimeError::to_report`].
+///
+/// A [`RuntimeError`] is boxed as is, it is returned unchanged to the caller.
+pub(crate) fn box_user_error<E: std::error::Error + Send + Sync + 'static>(
+    error: E,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    let type_name = std::any::type_name::<E>();
+    let error: Box<dyn std::error::Error + Send + Sync> = Box::new(error);
+    if error.is::<RuntimeError>() {
+        return error;
+    }
+    Box::new(TypedUserError { type_name, error })
+}
+
 pub(crate) struct RuntimeErrorInner {
     /// The source error
     pub(crate) source: Trap,
     /// The trap code (if any)
-    trap_code: Option<TrapCode>,
+    pub(crate) trap_code: Option<TrapCode>,
     /// The reconstructed Wasm trace (from the native trace and the `GlobalFrameInfo`).
     wasm_trace: Vec<FrameInfo>,
+    /// The offset of the out-of-bounds memory access that trapped (if known)
+    memory_offset: Option<u64>,
+    /// The size of the Wasm stack, for stack overflows (if known)
+    stack_size: Option<usize>,
+    /// The name of the type of the user error (if known)
+    user_type_name: Option<&'static str>,
 }
 
 impl RuntimeError {
@@ -133,19 +353,44 @@ impl RuntimeError {
         wasm_trace: Vec<FrameInfo>,
         trap_code: Option<TrapCode>,
     ) -> Self {
+        let (source, user_type_name) = source.take_user_type_name();
         Self {
             inner: Arc::new(RuntimeErrorInner {
                 source,
                 wasm_trace,
                 trap_code,
+                memory_offset: None,
+                stack_size: None,
+                user_type_name,
             }),
         }
     }
 
+    /// Records the offset of the out-of-bounds memory access that caused
+    /// this error.
+    pub(crate) fn with_memory_offset(mut self, offset: Option<u64>) -> Self {
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.memory_offset = offset;
+        }
+        self
+    }
+
+    /// Records the size of the Wasm stack that overflowed.
+    pub(crate) fn with_stack_size(mut self, size: Option<usize>) -> Self {
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.stack_size = size;
+        }
+        self
+    }
+
     /// Creates a custom user Error.
     ///
     /// This error object can be passed through Wasm frames and later retrieved
     /// using the `downcast` method.
+    ///
+    /// The type of the error isn't known once boxed, so the
+    /// [report](Self::to_report) of the error has no type name. The errors
+    /// returned by host functions have one.
     pub fn user(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
         match error.downcast::<Self>() {
             Ok(err) => *err,
@@ -168,17 +413,63 @@ impl RuntimeError {
         &self.inner.wasm_trace
     }
 
-    /// Returns trap code, if it's a Trap
+    /// Returns the code of the trap, if the error is a WebAssembly trap.
+    ///
+    /// The codes are the same for every backend. The `wamr`, `wasmi` and
+    /// `v8` backends only report traps as text, so their code is
+    /// recognized from the message with [`TrapCode::from_message`].
     pub fn to_trap(self) -> Option<TrapCode> {
         self.inner.trap_code
     }
 
+    /// Returns the offset in the linear memory that an out-of-bounds memory
+    /// access tried to reach, for [`TrapCode::HeapAccessOutOfBounds`] traps.
+    ///
+    /// The offset is only known for accesses caught by the guard pages of
+    /// a memory, on the `sys` backend. Accesses rejected by an explicit
+    /// bounds check return `None`.
+    pub fn memory_offset(&self) -> Option<u64> {
+        self.inner.memory_offset
+    }
+
+    /// Returns the size in bytes of the Wasm stack, for
+    /// [`TrapCode::StackOverflow`] traps.
+    ///
+    /// Only the `sys` backend reports it: it is the stack size of the
+    /// store, see `Store::set_wasm_stack_size`, or else the one of the
+    /// engine.
+    pub fn stack_size(&self) -> Option<usize> {
+        self.inner.stack_size
+    }
+
     // /// Returns trap code, if it's a Trap
     // pub fn to_source(self) -> &'static Trap {
     //     &self.inner.as_ref().source
     // }
 
-    /// Attempts to downcast the `RuntimeError` to a concrete type.
+    /// Attempts to downcast the `RuntimeError` to a concrete type, taking
+    /// back by value the error a host function failed with.
+    ///
+    /// This sees through the way the error was raised: returning
+    /// `Err(e)` from a host function, `Err(RuntimeError::user(Box::new(e)))`
+    /// and errors which crossed Wasm frames all give `e` back, on every
+    /// backend.
+    ///
+    /// Returns `Err(self)` if the error is not a `T`, or if this
+    /// `RuntimeError` has been cloned and other clones are still alive;
+    /// use [`RuntimeError::downcast_ref`] to inspect a shared error.
+    ///
+    /// # Example
+    /// ```
+    /// # use wasmer::RuntimeError;
+    /// #[derive(Debug, thiserror::Error)]
+    /// #[error("exit code {0}")]
+    /// struct Exit(i32);
+    ///
+    /// let error = RuntimeError::user(Box::new(Exit(3)));
+    /// assert!(error.is_user_error());
+    /// assert_eq!(error.downcast::<Exit>().unwrap().0, 3);
+    /// ```
     pub fn downcast<T: std::error::Error + 'static>(self) -> Result<T, Self> {
         match Arc::try_unwrap(self.inner) {
             Ok(inner) if inner.source.is::<T>() => Ok(inner.source.downcast::<T>().unwrap()),
@@ -198,6 +489,65 @@ impl RuntimeError {
     pub fn is<T: std::error::Error + 'static>(&self) -> bool {
         self.inner.source.is::<T>()
     }
+
+    /// Returns what this error was raised from.
+    ///
+    /// # Example
+    /// ```
+    /// # use wasmer::{RuntimeError, RuntimeErrorKind};
+    /// let error = RuntimeError::new("out of fuel");
+    /// assert_eq!(error.kind(), RuntimeErrorKind::Message);
+    ///
+    /// let error = RuntimeError::user(Box::new(std::io::Error::other("closed")));
+    /// assert_eq!(error.kind(), RuntimeErrorKind::User);
+    /// ```
+    pub fn kind(&self) -> RuntimeErrorKind {
+        if self.inner.source.is::<RuntimeStringError>() {
+            RuntimeErrorKind::Message
+        } else if std::error::Error::source(&self.inner.source).is_some() {
+            // Only user errors have a source, on every backend.
+            RuntimeErrorKind::User
+        } else {
+            RuntimeErrorKind::Trap
+        }
+    }
+
+    /// Returns true if this error was raised by the host, for example
+    /// returned by a host function, rather than a trap of the Wasm code
+    /// or the runtime.
+    ///
+    /// This is the [`RuntimeErrorKind::User`] kind: errors created with
+    /// [`RuntimeError::new`] are neither user errors nor traps.
+    pub fn is_user_error(&self) -> bool {
+        self.kind() == RuntimeErrorKind::User
+    }
+
+    /// Returns true if this error is a trap raised by the Wasm code or the
+    /// runtime, such as `unreachable` or an out-of-bounds access.
+    ///
+    /// This is the [`RuntimeErrorKind::Trap`] kind.
+    pub fn is_trap(&self) -> bool {
+        self.kind() == RuntimeErrorKind::Trap
+    }
+
+    /// Returns a report of this error that can be sent to another process,
+    /// with the `enable-serde` feature, or displayed there.
+    ///
+    /// The error raised by the host is not part of the report, only its
+    /// type name and message.
+    pub fn to_report(&self) -> TrapReport {
+        let user_error = match self.kind() {
+            RuntimeErrorKind::User => std::error::Error::source(&self.inner.source)
+                .map(|error| UserErrorReport::new(error, self.inner.user_type_name)),
+            _ => None,
+        };
+        TrapReport {
+            trap_code: self.inner.trap_code,
+            message: self.message(),
+            frames: self.trace().iter().map(FrameReport::from).collect(),
+            user_error,
+        }
+    }
 }
 
 impl std::fmt::Debug for RuntimeError {
@@ -212,6 +562,12 @@ impl std::fmt::Debug for RuntimeError {
 impl std::fmt::Display for RuntimeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "RuntimeError: {}", self.message())?;
+        if let Some(offset) = self.memory_offset() {
+            write!(f, " at offset {offset:#x}")?;
+        }
+        if let Some(size) = self.stack_size() {
+            write!(f, " (wasm stack size: {size} bytes)")?;
+        }
         let trace = self.trace();
         if trace.is_empty() {
             return Ok(());
@@ -241,8 +597,20 @@ impl std::fmt::Display for RuntimeError {
 }
 
 impl std::error::Error for RuntimeError {
+    /// Returns the error raised by the host, or the code of the trap.
+    ///
+    /// Errors created with [`RuntimeError::new`] have no source: their
+    /// message is already the one displayed.
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        self.inner.source.source()
+        match self.kind() {
+            RuntimeErrorKind::Message => None,
+            RuntimeErrorKind::User => self.inner.source.source(),
+            RuntimeErrorKind::Trap => self
+                .inner
+                .trap_code
+                .as_ref()
+                .map(|code| code as &(dyn std::error::Error + 'static)),
+        }
     }
 }
 
@@ -256,6 +624,110 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for RuntimeError {
     }
 }
 
+/// A report of a [`RuntimeError`], see [`RuntimeError::to_report`].
+///
+/// With the `enable-serde` feature, it can be serialized to report the
+/// error to another process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "enable-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrapReport {
+    /// The code of the trap, see [`RuntimeError::to_trap`].
+    pub trap_code: Option<TrapCode>,
+    /// The message of the error, see [`RuntimeError::message`].
+    pub message: String,
+    /// The WebAssembly frames that led to the error, innermost first.
+    pub frames: Vec<FrameReport>,
+    /// The error raised by the host, if any.
+    pub user_error: Option<UserErrorReport>,
+}
+
+impl std::fmt::Display for TrapReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RuntimeError: {}", self.message)?;
+        if let Some(type_name) = self.user_error.as_ref().and_then(|e| e.type_name.as_ref()) {
+            write!(f, " ({type_name})")?;
+        }
+        for frame in &self.frames {
+            writeln!(f)?;
+            write!(f, "    at {frame}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A frame of a [`TrapReport`], see [`FrameInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "enable-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameReport {
+    /// The name of the module.
+    pub module_name: String,
+    /// The index of the function in the module.
+    pub func_index: u32,
+    /// The name of the function, if one is available.
+    pub function_name: Option<String>,
+    /// The offset of the instruction in the module.
+    pub module_offset: usize,
+    /// The offset of the instruction in the function.
+    pub func_offset: usize,
+}
+
+impl From<&FrameInfo> for FrameReport {
+    fn from(frame: &FrameInfo) -> Self {
+        Self {
+            module_name: frame.module_name().to_string(),
+            func_index: frame.func_index(),
+            function_name: frame.function_name().map(str::to_string),
+            module_offset: frame.module_offset(),
+            func_offset: frame.func_offset(),
+        }
+    }
+}
+
+impl std::fmt::Display for FrameReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.function_name {
+            Some(name) => write!(f, "{name}")?,
+            None => write!(f, "<unnamed>")?,
+        }
+        write!(
+            f,
+            " ({}[{}]:0x{:x})",
+            self.module_name, self.func_index, self.module_offset
+        )
+    }
+}
+
+/// The error raised by the host in a [`TrapReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "enable-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserErrorReport {
+    /// The name of the error type, as given by [`std::any::type_name`].
+    ///
+    /// It is known for the errors returned by host functions, but not for
+    /// the errors given already boxed to [`RuntimeError::user`].
+    pub type_name: Option<String>,
+    /// The message of the error.
+    pub message: String,
+    /// The messages of the sources of the error, outermost first.
+    pub sources: Vec<String>,
+}
+
+impl UserErrorReport {
+    fn new(error: &(dyn std::error::Error + 'static), type_name: Option<&str>) -> Self {
+        let mut sources = Vec::new();
+        let mut source = error.source();
+        while let Some(error) = source {
+            sources.push(error.to_string());
+            source = error.source();
+        }
+        Self {
+            type_name: type_name.map(str::to_string),
+            message: error.to_string(),
+            sources,
+        }
+    }
+}
+
 /// Error that can occur during atomic operations. (notify/wait)
 // Non-exhaustive to allow for future variants without breaking changes!
 #[derive(PartialEq, Eq, Debug, Error)]
@@ -278,3 +750,349 @@ impl std::fmt::Display for AtomicsError {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::RuntimeStringError;
+    use crate::{imports, Function, Instance, Module, RuntimeError, Store, Value};
+    use wasmer_types::TrapCode;
+
+    #[derive(Debug, PartialEq, thiserror::Error)]
+    #[error("exit code {code}")]
+    struct Exit {
+        code: i32,
+    }
+
+    #[test]
+    fn user_error_round_trip() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "exit" (func $exit (param i32)))
+                (func (export "run") (param i32)
+                    (call $exit (local.get 0)))
+                (func (export "crash") unreachable))"#,
+        )
+        .unwrap();
+        let imports = imports! {
+            "env" => {
+                "exit" => Function::new_typed(&mut store, |code: i32| -> Result<(), Exit> {
+                    Err(Exit { code })
+                }),
+            },
+        };
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+
+        let run = instance.exports.get_function("run").unwrap();
+        let error = run.call(&mut store, &[Value::I32(7)]).unwrap_err();
+        assert!(error.is_user_error());
+        assert!(!error.is_trap());
+        assert_eq!(error.downcast_ref::<Exit>(), Some(&Exit { code: 7 }));
+        let error = error.downcast::<RuntimeStringError>().unwrap_err();
+        assert_eq!(error.downcast::<Exit>().unwrap(), Exit { code: 7 });
+
+        // Errors wrapped in a `RuntimeError` by the host come back the same.
+        let exit = Function::new_typed(&mut store, |code: i32| -> Result<(), RuntimeError> {
+            Err(RuntimeError::user(Box::new(Exit { code })))
+        });
+        let error = exit.call(&mut store, &[Value::I32(9)]).unwrap_err();
+        assert_eq!(error.downcast::<Exit>().unwrap(), Exit { code: 9 });
+
+        let crash = instance.exports.get_function("crash").unwrap();
+        let error = crash.call(&mut store, &[]).unwrap_err();
+        assert!(error.is_trap());
+        assert!(!error.is_user_error());
+        assert!(error.downcast::<Exit>().is_err());
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only the sys backend reports faulting addresses"
+    )]
+    fn out_of_bounds_offset() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (memory 1)
+                (func (export "load") (param i32) (result i32)
+                    (i32.load (local.get 0))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let load = instance
+            .exports
+            .get_typed_function::<u32, i32>(&store, "load")
+            .unwrap();
+
+        assert_eq!(load.call(&mut store, 8).unwrap(), 0);
+        let error = load.call(&mut store, 0xdeadbeef).unwrap_err();
+        assert_eq!(error.memory_offset(), Some(0xdeadbeef));
+        assert!(error
+            .to_string()
+            .starts_with("RuntimeError: out of bounds memory access at offset 0xdeadbeef"));
+        assert_eq!(error.to_trap(), Some(TrapCode::HeapAccessOutOfBounds));
+    }
+
+    #[test]
+    #[cfg_attr(
+        not(feature = "sys"),
+        ignore = "only the sys backend records Wasm frames"
+    )]
+    fn trap_trace() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module $nested
+                (func $inner unreachable)
+                (func $outer (export "run") call $inner))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let run = instance.exports.get_function("run").unwrap();
+        let error = run.call(&mut store, &[]).unwrap_err();
+
+        let trace = error.trace();
+        let frames = trace
+            .iter()
+            .map(|frame| {
+                (
+                    frame.module_name(),
+                    frame.func_index(),
+                    frame.function_name(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            frames,
+            [("nested", 0, Some("inner")), ("nested", 1, Some("outer"))]
+        );
+        // `$inner` comes first in the code section.
+        assert!(trace[0].module_offset() < trace[1].module_offset());
+
+        let message = error.to_string();
+        let lines = message.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "RuntimeError: unreachable");
+        assert!(lines[1].starts_with("    at inner (nested[0]:0x"));
+        assert!(lines[2].starts_with("    at outer (nested[1]:0x"));
+    }
+
+    #[test]
+    fn source_chain() {
+        use crate::RuntimeErrorKind;
+        use std::error::Error;
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "read" (func $read))
+                (func (export "run") (call $read)))"#,
+        )
+        .unwrap();
+        let imports = imports! {
+            "env" => {
+                "read" => Function::new_typed(&mut store, || -> Result<(), std::io::Error> {
+                    Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no input"))
+                }),
+            },
+        };
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+        let run = instance.exports.get_function("run").unwrap();
+        let error = run.call(&mut store, &[]).unwrap_err();
+
+        assert_eq!(error.kind(), RuntimeErrorKind::User);
+        let io = error
+            .source()
+            .unwrap()
+            .downcast_ref::<std::io::Error>()
+            .unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::NotFound);
+        assert!(error.to_string().starts_with("RuntimeError: no input"));
+
+        let error = RuntimeError::new("out of fuel");
+        assert_eq!(error.kind(), RuntimeErrorKind::Message);
+        assert!(!error.is_user_error() && !error.is_trap());
+        assert!(error.source().is_none());
+
+        let module = Module::new(&store, "(module (func (export \"crash\") unreachable))").unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let crash = instance.exports.get_function("crash").unwrap();
+        let error = crash.call(&mut store, &[]).unwrap_err();
+        assert_eq!(error.kind(), RuntimeErrorKind::Trap);
+        assert_eq!(
+            error.source().unwrap().downcast_ref::<TrapCode>(),
+            Some(&TrapCode::UnreachableCodeReached)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sys")]
+    fn stack_overflow_size() {
+        let mut store = Store::default();
+        store.set_wasm_stack_size(Some(256 * 1024));
+        let module = Module::new(
+            &store,
+            r#"(module
+                (func $recurse (export "recurse") (param i64) (result i64)
+                    (i64.add (call $recurse (local.get 0)) (i64.const 1))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+        let recurse = instance
+            .exports
+            .get_typed_function::<i64, i64>(&store, "recurse")
+            .unwrap();
+
+        let error = recurse.call(&mut store, 0).unwrap_err();
+        assert_eq!(error.stack_size(), Some(256 * 1024));
+        assert!(error
+            .to_string()
+            .starts_with("RuntimeError: call stack exhausted (wasm stack size: 262144 bytes)"));
+        assert_eq!(error.to_trap(), Some(TrapCode::StackOverflow));
+    }
+
+    #[test]
+    #[cfg(feature = "wamr")]
+    fn wamr_traps() {
+        let mut store = Store::new(crate::backend::wamr::Wamr::new());
+        // The app heap of WAMR would add pages to the memory.
+        store.set_wamr_instance_config(crate::wamr::InstanceConfig {
+            heap_size: 0,
+            ..Default::default()
+        });
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "exit" (func $exit (param i32)))
+                (memory 1)
+                (func (export "run") (param i32) (call $exit (local.get 0)))
+                (func (export "out_of_bounds") (result i32)
+                    (i32.load (i32.const 0x10000))))"#,
+        )
+        .unwrap();
+        let exit = Function::new_typed(&mut store, |code: i32| -> Result<(), RuntimeError> {
+            Err(RuntimeError::user(Box::new(Exit { code })))
+        });
+        let imports = imports! { "env" => { "exit" => exit } };
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+
+        let run = instance.exports.get_function("run").unwrap();
+        for code in [1, 2] {
+            let error = run.call(&mut store, &[Value::I32(code)]).unwrap_err();
+            assert_eq!(error.downcast::<Exit>().unwrap(), Exit { code });
+        }
+
+        let out_of_bounds = instance.exports.get_function("out_of_bounds").unwrap();
+        let error = out_of_bounds.call(&mut store, &[]).unwrap_err();
+        assert_eq!(
+            error.clone().to_trap(),
+            Some(TrapCode::HeapAccessOutOfBounds)
+        );
+        assert!(!error.message().starts_with("Exception"), "{error}");
+        assert!(error.trace().iter().any(|frame| frame.func_index() == 2));
+    }
+
+    #[test]
+    fn trap_codes() {
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (memory 1)
+                (type $void (func))
+                (table 1 funcref)
+                (func (export "unreachable") unreachable)
+                (func (export "div_by_zero") (result i32)
+                    (i32.div_s (i32.const 1) (i32.const 0)))
+                (func (export "overflow") (result i32)
+                    (i32.div_s (i32.const 0x80000000) (i32.const -1)))
+                (func (export "bad_conversion") (result i32)
+                    (i32.trunc_f32_s (f32.const nan)))
+                (func (export "out_of_bounds") (result i32)
+                    (i32.load (i32.const 0x10000)))
+                (func (export "null_call")
+                    (call_indirect (type $void) (i32.const 0))))"#,
+        )
+        .unwrap();
+        let instance = Instance::new(&mut store, &module, &imports! {}).unwrap();
+
+        for (name, code) in [
+            ("unreachable", TrapCode::UnreachableCodeReached),
+            ("div_by_zero", TrapCode::IntegerDivisionByZero),
+            ("overflow", TrapCode::IntegerOverflow),
+            ("bad_conversion", TrapCode::BadConversionToInteger),
+            ("out_of_bounds", TrapCode::HeapAccessOutOfBounds),
+            ("null_call", TrapCode::IndirectCallToNull),
+        ] {
+            let func = instance.exports.get_function(name).unwrap();
+            let error = func.call(&mut store, &[]).unwrap_err();
+            assert_eq!(error.clone().to_trap(), Some(code), "{name}: {error}");
+        }
+
+        let error = RuntimeError::new("out of fuel");
+        assert_eq!(error.to_trap(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "enable-serde")]
+    fn trap_report_round_trip() {
+        use super::TrapReport;
+
+        #[derive(Debug, thiserror::Error)]
+        #[error("host call failed")]
+        struct HostFailure(#[source] std::io::Error);
+
+        let mut store = Store::default();
+        let module = Module::new(
+            &store,
+            r#"(module
+                (import "env" "fail" (func $fail))
+                (func $inner (call $fail))
+                (func (export "outer") (call $inner))
+                (func (export "crash") unreachable))"#,
+        )
+        .unwrap();
+        let imports = imports! {
+            "env" => {
+                "fail" => Function::new_typed(&mut store, || -> Result<(), HostFailure> {
+                    Err(HostFailure(std::io::Error::other("closed")))
+                }),
+            },
+        };
+        let instance = Instance::new(&mut store, &module, &imports).unwrap();
+
+        let outer = instance.exports.get_function("outer").unwrap();
+        let error = outer.call(&mut store, &[]).unwrap_err();
+        let report = error.to_report();
+        assert_eq!(report.trap_code, None);
+        assert_eq!(report.frames.len(), error.trace().len());
+        let user_error = report.user_error.as_ref().unwrap();
+        assert_eq!(
+            user_error.type_name.as_deref(),
+            Some(std::any::type_name::<HostFailure>())
+        );
+        assert_eq!(user_error.message, "host call failed");
+        assert_eq!(user_error.sources, ["closed"]);
+
+        let json = serde_json::to_string(&report).unwrap();
+        let back: TrapReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, report);
+        assert_eq!(back.to_string(), report.to_string());
+
+        let crash = instance.exports.get_function("crash").unwrap();
+        let report = crash.call(&mut store, &[]).unwrap_err().to_report();
+        assert_eq!(report.trap_code, Some(TrapCode::UnreachableCodeReached));
+        assert_eq!(report.user_error, None);
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<TrapReport>(&json).unwrap(), report);
+
+        let boxed = RuntimeError::user(Box::new(HostFailure(std::io::Error::other("closed"))));
+        let user_error = boxed.to_report().user_error.unwrap();
+        assert_eq!(user_error.type_name, None);
+        assert_eq!(user_error.message, "host call failed");
+    }
+}
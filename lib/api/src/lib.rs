@@ -439,10 +439,12 @@ pub use backend::*;
 mod vm;
 
 pub use wasmer_types::{
-    is_wasm, Bytes, CompileError, DeserializeError, ExportIndex, ExportType, ExternType, FrameInfo,
-    FunctionType, GlobalInit, GlobalType, ImportType, LocalFunctionIndex, MemoryError, MemoryStyle,
-    MemoryType, Mutability, OnCalledAction, Pages, ParseCpuFeatureError, SerializeError,
-    TableStyle, TableType, Type, ValueType, WasmError, WasmResult, WASM_MAX_PAGES, WASM_MIN_PAGES,
+    is_wasm, wasm_binary_version, Bytes, CompileError, DeserializeError, ExportIndex, ExportType,
+    ExternType, FrameInfo, FunctionIndex, FunctionType, GlobalInit, GlobalType, ImportIndex,
+    ImportType, InstantiationCost, LimitError, LocalFunctionIndex, MemoryError, MemoryStyle,
+    MemoryType, ModuleHash, Mutability, OnCalledAction, Pages, ParseCpuFeatureError, RawValue,
+    SerializeError, StoreLimits, StripMode, TableStyle, TableType, TagKind, TagType, TrapCode,
+    Type, ValueType, WasmError, WasmResult, WASM_BINARY_VERSION, WASM_MAX_PAGES, WASM_MIN_PAGES,
     WASM_PAGE_SIZE,
 };
 
@@ -454,6 +456,47 @@ pub use wat::parse_bytes as wat2wasm;
 
 pub use wasmer_derive::ValueType;
 
+/// Derives a `from_instance` constructor that looks up every field of a
+/// struct in an [`Instance`]'s exports.
+///
+/// Each field is looked up by its name, or by the name given with
+/// `#[wasm(name = "...")]`, and must implement [`FromExport`]. The kind of
+/// export can be spelled out with `#[wasm(func)]`, `#[wasm(memory)]`,
+/// `#[wasm(global)]` or `#[wasm(table)]`. Fields of type `Option<T>` are
+/// optional exports.
+///
+/// ```
+/// # use wasmer::{imports, Global, Instance, Memory, Module, Store, TypedFunction, WasmExports};
+/// # fn main() -> anyhow::Result<()> {
+/// #[derive(WasmExports)]
+/// struct MyAbi {
+///     #[wasm(func)]
+///     alloc: TypedFunction<i32, i32>,
+///     #[wasm(memory)]
+///     memory: Memory,
+///     #[wasm(global, name = "__heap_base")]
+///     heap_base: Option<Global>,
+/// }
+///
+/// let mut store = Store::default();
+/// let module = Module::new(&store, r#"(module
+///     (memory (export "memory") 1)
+///     (func (export "alloc") (param i32) (result i32) local.get 0))"#)?;
+/// let instance = Instance::new(&mut store, &module, &imports! {})?;
+/// let abi = MyAbi::from_instance(&store, &instance)?;
+/// assert!(abi.heap_base.is_none());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// All the missing or mismatched exports are reported at once, as an
+/// [`ExportError::Multiple`].
+pub use wasmer_derive::WasmExports;
+
+// Lets the code generated by `wasmer-derive` refer to `::wasmer` in tests.
+#[cfg(test)]
+extern crate self as wasmer;
+
 #[cfg(any(
     all(
         feature = "sys-default",
@@ -30,6 +30,7 @@ macro_rules! gen_rt_ty {
                 /// The implementation from the `jsc` backend.
                 Jsc(crate::backend::jsc::entities::[<$id:lower>]::$id),
             }
+            crate::macros::backend::impl_backend_name!([<Backend $id>]);
         }
     };
 
@@ -63,6 +64,7 @@ macro_rules! gen_rt_ty {
                 /// The implementation from the `jsc` backend.
                 Jsc(crate::backend::jsc::entities::[<$id:lower>]::$id$(<$lt>)?),
             }
+            crate::macros::backend::impl_backend_name!([<Backend $id>]$(<$lt>)?);
         }
     };
 
@@ -96,6 +98,7 @@ macro_rules! gen_rt_ty {
                 /// The implementation from the `jsc` backend.
                 Jsc(crate::backend::jsc::entities::$path::$id$(<$lt>)?),
             }
+            crate::macros::backend::impl_backend_name!([<Backend $id>]$(<$lt>)?);
         }
     };
 
@@ -129,6 +132,33 @@ macro_rules! gen_rt_ty {
                 /// The implementation from the `jsc` backend.
                 Jsc(crate::backend::jsc::entities::$path::$id),
             }
+            crate::macros::backend::impl_backend_name!([<Backend $id>]);
+        }
+    };
+}
+
+/// Implements `backend_name` on a type generated by [`gen_rt_ty`].
+macro_rules! impl_backend_name {
+    ($ty:ident$(<$lt:lifetime>)?) => {
+        impl$(<$lt>)? $ty$(<$lt>)? {
+            /// Returns the name of the backend this object comes from.
+            #[allow(dead_code)]
+            pub(crate) fn backend_name(&self) -> &'static str {
+                match self {
+                    #[cfg(feature = "sys")]
+                    Self::Sys(_) => "sys",
+                    #[cfg(feature = "wamr")]
+                    Self::Wamr(_) => "wamr",
+                    #[cfg(feature = "wasmi")]
+                    Self::Wasmi(_) => "wasmi",
+                    #[cfg(feature = "v8")]
+                    Self::V8(_) => "v8",
+                    #[cfg(feature = "js")]
+                    Self::Js(_) => "js",
+                    #[cfg(feature = "jsc")]
+                    Self::Jsc(_) => "jsc",
+                }
+            }
         }
     };
 }
@@ -171,4 +201,4 @@ macro_rules! match_rt {
     };
 }
 
-pub(crate) use {gen_rt_ty, match_rt};
+pub(crate) use {gen_rt_ty, impl_backend_name, match_rt};
@@ -38,9 +38,26 @@ where
         }
     }
 
-    pub(crate) fn into_function(self) -> Function {
+    /// Converts this typed function back into a dynamic [`Function`].
+    ///
+    /// # Usage
+    /// ```
+    /// # use wasmer::{Function, Store, TypedFunction};
+    /// # let mut store = Store::default();
+    /// let add = Function::new_typed(&mut store, |a: i32, b: i32| a + b);
+    /// let add: TypedFunction<(i32, i32), i32> = add.typed(&store).unwrap();
+    ///
+    /// let functions: Vec<Function> = vec![add.into_function()];
+    /// assert_eq!(functions[0].param_arity(&store), 2);
+    /// ```
+    pub fn into_function(self) -> Function {
         self.func
     }
+
+    /// Returns the dynamic [`Function`] this typed function wraps.
+    pub fn as_function(&self) -> &Function {
+        &self.func
+    }
 }
 
 macro_rules! impl_native_traits {
@@ -61,6 +78,7 @@ macro_rules! impl_native_traits {
                 $(
                     let [<p_ $x>] = $x;
                 )*
+                self.func.check_backend(store)?;
                 match store.as_store_mut().inner.store {
                     #[cfg(feature = "sys")]
                     BackendStore::Sys(_) => self.call_sys(store, $([<p_ $x>]),*),
@@ -83,6 +101,7 @@ macro_rules! impl_native_traits {
             #[allow(unused_mut)]
             #[allow(clippy::too_many_arguments)]
             pub fn call_raw(&self, store: &mut impl AsStoreMut, mut params_list: Vec<RawValue> ) -> Result<Rets, RuntimeError> {
+                self.func.check_backend(store)?;
                 match store.as_store_mut().inner.store {
                     #[cfg(feature = "sys")]
                     BackendStore::Sys(_) => self.call_raw_sys(store, params_list),
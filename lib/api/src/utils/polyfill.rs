@@ -8,15 +8,17 @@ use core::convert::TryFrom;
 use std::vec::Vec;
 use wasmer_types::entity::EntityRef;
 use wasmer_types::{
-    ExportIndex, FunctionIndex, FunctionType, GlobalIndex, GlobalType, ImportIndex, MemoryIndex,
-    MemoryType, ModuleInfo, Pages, SignatureIndex, TableIndex, TableType, TagIndex, TagType, Type,
+    DataIndex, DataInitializer, DataInitializerLocation, ExportIndex, FunctionIndex, FunctionType,
+    GlobalIndex, GlobalType, ImportIndex, MemoryIndex, MemoryType, ModuleHash, ModuleInfo, Pages,
+    SignatureIndex, TableIndex, TableType, TagIndex, TagType, Type,
 };
 
 use wasmparser::{
-    self, BinaryReaderError, Export, ExportSectionReader, ExternalKind, FunctionSectionReader,
-    GlobalSectionReader, GlobalType as WPGlobalType, ImportSectionReader, MemorySectionReader,
-    MemoryType as WPMemoryType, NameSectionReader, Parser, Payload, TableSectionReader,
-    TagType as WPTagType, TypeRef, TypeSectionReader,
+    self, BinaryReaderError, ConstExpr, DataKind, DataSectionReader, Export, ExportSectionReader,
+    ExternalKind, FunctionSectionReader, GlobalSectionReader, GlobalType as WPGlobalType,
+    ImportSectionReader, MemorySectionReader, MemoryType as WPMemoryType, NameSectionReader,
+    Operator, Parser, Payload, TableSectionReader, TagType as WPTagType, TypeRef,
+    TypeSectionReader,
 };
 
 pub type WasmResult<T> = Result<T, String>;
@@ -255,6 +257,15 @@ impl ModuleInfoPolyfill {
         self.info.name = Some(name.to_string());
         Ok(())
     }
+
+    pub(crate) fn declare_passive_data(
+        &mut self,
+        data_index: DataIndex,
+        data: &[u8],
+    ) -> WasmResult<()> {
+        self.info.passive_data.insert(data_index, data.into());
+        Ok(())
+    }
 }
 
 fn transform_err(err: BinaryReaderError) -> String {
@@ -265,6 +276,7 @@ fn transform_err(err: BinaryReaderError) -> String {
 /// parsed ModuleInfo `ModuleInfoPolyfill`.
 pub fn translate_module(data: &[u8]) -> WasmResult<ModuleInfoPolyfill> {
     let mut module_info: ModuleInfoPolyfill = Default::default();
+    module_info.info.hash = Some(ModuleHash::xxhash(data));
 
     for payload in Parser::new(0).parse_all(data) {
         match payload.map_err(transform_err)? {
@@ -300,6 +312,10 @@ pub fn translate_module(data: &[u8]) -> WasmResult<ModuleInfoPolyfill> {
                 parse_tag_section(tags, &mut module_info)?;
             }
 
+            Payload::DataSection(data) => {
+                parse_data_section(data, &mut module_info)?;
+            }
+
             Payload::CustomSection(sectionreader) => {
                 // We still add the custom section data, but also read it as name section reader
                 let name = sectionreader.name();
@@ -321,6 +337,51 @@ pub fn translate_module(data: &[u8]) -> WasmResult<ModuleInfoPolyfill> {
     Ok(module_info)
 }
 
+/// Reads the active data segments of a valid Wasm binary, in the order
+/// they appear in the module.
+///
+/// The passive ones are part of the [`ModuleInfo`] returned by
+/// [`translate_module`].
+pub fn data_initializers(data: &[u8]) -> WasmResult<Vec<DataInitializer<'_>>> {
+    let mut initializers = Vec::new();
+
+    for payload in Parser::new(0).parse_all(data) {
+        if let Payload::DataSection(data) = payload.map_err(transform_err)? {
+            for entry in data {
+                let entry = entry.map_err(transform_err)?;
+                if let DataKind::Active {
+                    memory_index,
+                    offset_expr,
+                } = entry.kind
+                {
+                    let (base, offset) = parse_offset_expr(&offset_expr)?;
+                    initializers.push(DataInitializer {
+                        location: DataInitializerLocation {
+                            memory_index: MemoryIndex::from_u32(memory_index),
+                            base,
+                            offset,
+                        },
+                        data: entry.data,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(initializers)
+}
+
+/// Reads the offset of an active segment, either a constant or a global.
+fn parse_offset_expr(expr: &ConstExpr) -> WasmResult<(Option<GlobalIndex>, usize)> {
+    let mut reader = expr.get_operators_reader();
+    match reader.read().map_err(transform_err)? {
+        Operator::I32Const { value } => Ok((None, value as u32 as usize)),
+        Operator::I64Const { value } => Ok((None, value as u64 as usize)),
+        Operator::GlobalGet { global_index } => Ok((Some(GlobalIndex::from_u32(global_index)), 0)),
+        operator => Err(format!("Unsupported offset expression: {:?}", operator)),
+    }
+}
+
 /// Helper function translating wasmparser types to Wasm Type.
 pub fn wptype_to_type(ty: wasmparser::ValType) -> WasmResult<Type> {
     match ty {
@@ -556,6 +617,24 @@ fn parse_tag_section(
     Ok(())
 }
 
+/// Parses the Data section of the wasm module.
+///
+/// Only the passive segments are recorded, see [`data_initializers`] for
+/// the active ones.
+fn parse_data_section(
+    data: DataSectionReader<'_>,
+    module_info: &mut ModuleInfoPolyfill,
+) -> WasmResult<()> {
+    for (index, entry) in data.into_iter().enumerate() {
+        let entry = entry.map_err(transform_err)?;
+        if let DataKind::Passive = entry.kind {
+            module_info.declare_passive_data(DataIndex::from_u32(index as u32), entry.data)?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Parses the Export section of the wasm module.
 pub fn parse_export_section(
     exports: ExportSectionReader<'_>,
@@ -91,6 +91,24 @@ pub unsafe extern "C" fn wasm_instance_new(
 
             return None;
         }
+
+        Err(e @ InstantiationError::BackendMismatch(_)) => {
+            crate::error::update_last_error(e);
+
+            return None;
+        }
+
+        Err(e @ InstantiationError::InsufficientResources(_)) => {
+            crate::error::update_last_error(e);
+
+            return None;
+        }
+
+        Err(e @ InstantiationError::Unsupported(_)) => {
+            crate::error::update_last_error(e);
+
+            return None;
+        }
     };
 
     Some(Box::new(wasm_instance_t {
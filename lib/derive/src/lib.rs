@@ -4,6 +4,7 @@ use proc_macro_error2::proc_macro_error;
 use syn::{parse_macro_input, DeriveInput};
 
 mod value_type;
+mod wasm_exports;
 
 #[proc_macro_error]
 #[proc_macro_derive(ValueType)]
@@ -12,3 +13,11 @@ pub fn derive_value_type(input: proc_macro::TokenStream) -> proc_macro::TokenStr
     let gen = value_type::impl_value_type(&input);
     gen.into()
 }
+
+#[proc_macro_error]
+#[proc_macro_derive(WasmExports, attributes(wasm))]
+pub fn derive_wasm_exports(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let gen = wasm_exports::impl_wasm_exports(&input);
+    gen.into()
+}
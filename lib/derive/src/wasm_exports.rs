@@ -0,0 +1,153 @@
+use proc_macro2::TokenStream;
+use proc_macro_error2::abort;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Field, Fields, GenericArgument, Lit, Meta, NestedMeta, PathArguments, Type,
+};
+
+/// The kind of export a field was annotated with.
+#[derive(Clone, Copy)]
+enum Kind {
+    Func,
+    Memory,
+    Global,
+    Table,
+}
+
+impl Kind {
+    fn from_ident(ident: &str) -> Option<Self> {
+        match ident {
+            "func" => Some(Self::Func),
+            "memory" => Some(Self::Memory),
+            "global" => Some(Self::Global),
+            "table" => Some(Self::Table),
+            _ => None,
+        }
+    }
+
+    /// Whether a field type named `ty` can hold this kind of export.
+    fn accepts(self, ty: &str) -> bool {
+        match self {
+            Self::Func => ty == "Function" || ty == "TypedFunction",
+            Self::Memory => ty == "Memory",
+            Self::Global => ty == "Global",
+            Self::Table => ty == "Table",
+        }
+    }
+}
+
+/// Returns the name of the last path segment of `ty`, looking through
+/// `Option<T>`.
+fn type_name(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident == "Option" {
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return type_name(inner);
+            }
+        }
+    }
+    Some(segment.ident.to_string())
+}
+
+/// Parses the `#[wasm(...)]` attributes of a field into the export name.
+fn export_name(field: &Field) -> String {
+    let ident = field.ident.as_ref().unwrap();
+    let mut name = ident.to_string().trim_start_matches("r#").to_string();
+
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("wasm")) {
+        let nested = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            _ => abort!(attr, "expected `#[wasm(...)]`"),
+        };
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let kind = path
+                        .get_ident()
+                        .and_then(|ident| Kind::from_ident(&ident.to_string()))
+                        .unwrap_or_else(|| {
+                            abort!(
+                                path,
+                                "unknown export kind, expected `func`, `memory`, `global` or `table`"
+                            )
+                        });
+                    // Only the wasmer types can be checked; aliases are left
+                    // to the type checks done when looking the export up.
+                    if let Some(ty) = type_name(&field.ty) {
+                        let known = ["Function", "TypedFunction", "Memory", "Global", "Table"];
+                        if known.contains(&ty.as_str()) && !kind.accepts(&ty) {
+                            abort!(field.ty, "`{}` can't hold this kind of export", ty);
+                        }
+                    }
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => match nv.lit {
+                    Lit::Str(s) => name = s.value(),
+                    lit => abort!(lit, "expected a string literal"),
+                },
+                meta => abort!(meta, "expected an export kind or `name = \"...\"`"),
+            }
+        }
+    }
+
+    name
+}
+
+pub fn impl_wasm_exports(input: &DeriveInput) -> TokenStream {
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = match &input.data {
+        Data::Struct(ds) => match &ds.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => abort!(
+                input,
+                "WasmExports can only be derived for structs with named fields"
+            ),
+        },
+        _ => abort!(input, "WasmExports can only be derived for structs"),
+    };
+
+    let idents: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+    // Prefixed so that fields can't shadow the arguments.
+    let locals: Vec<_> = (0..idents.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let lookups = fields.iter().zip(&locals).map(|(field, local)| {
+        let ty = &field.ty;
+        let name = export_name(field);
+        quote! {
+            let #local = match <#ty as ::wasmer::FromExport>::from_export(store, &instance.exports, #name) {
+                ::core::result::Result::Ok(value) => ::core::option::Option::Some(value),
+                ::core::result::Result::Err(error) => {
+                    errors.push((::std::string::String::from(#name), error));
+                    ::core::option::Option::None
+                }
+            };
+        }
+    });
+
+    quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Looks up every export of this struct in `instance`.
+            ///
+            /// All the missing or mismatched exports are reported at once,
+            /// in an [`ExportError::Multiple`](::wasmer::ExportError::Multiple).
+            pub fn from_instance(
+                store: &impl ::wasmer::AsStoreRef,
+                instance: &::wasmer::Instance,
+            ) -> ::core::result::Result<Self, ::wasmer::ExportError> {
+                let mut errors = ::std::vec::Vec::new();
+                #(#lookups)*
+                if !errors.is_empty() {
+                    return ::core::result::Result::Err(::wasmer::ExportError::Multiple(errors));
+                }
+                ::core::result::Result::Ok(Self {
+                    #(#idents: #locals.unwrap(),)*
+                })
+            }
+        }
+    }
+}
@@ -83,9 +83,17 @@ impl WasmMmap {
             });
         }
 
-        let delta_bytes = delta.bytes().0;
-        let prev_bytes = prev_pages.bytes().0;
-        let new_bytes = new_pages.bytes().0;
+        let (Some(delta_bytes), Some(prev_bytes), Some(new_bytes)) = (
+            delta.to_bytes_checked(),
+            prev_pages.to_bytes_checked(),
+            new_pages.to_bytes_checked(),
+        ) else {
+            // The new size is not addressable on this platform.
+            return Err(MemoryError::CouldNotGrow {
+                current: self.size,
+                attempted_delta: delta,
+            });
+        };
 
         if new_bytes > self.alloc.len() - conf.offset_guard_size {
             // If the new size is within the declared maximum, but needs more memory than we
@@ -119,7 +127,7 @@ impl WasmMmap {
         unsafe {
             let mut md_ptr = self.vm_memory_definition.as_ptr();
             let md = md_ptr.as_mut();
-            md.current_length = new_pages.bytes().0;
+            md.current_length = new_bytes;
             md.base = self.alloc.as_mut_ptr() as _;
         }
 
@@ -268,9 +276,18 @@ impl VMOwnedMemory {
                 *bound
             }
         };
-        let minimum_bytes = minimum_pages.bytes().0;
+        let minimum_bytes =
+            minimum_pages
+                .to_bytes_checked()
+                .ok_or_else(|| MemoryError::InvalidMemory {
+                    reason: format!(
+                        "{} pages are not addressable on this platform",
+                        minimum_pages.0
+                    ),
+                })?;
         let request_bytes = minimum_bytes.checked_add(offset_guard_bytes).unwrap();
         let mapped_pages = memory.minimum;
+        // Doesn't overflow: `mapped_pages` is at most `minimum_pages`.
         let mapped_bytes = mapped_pages.bytes();
 
         let mut alloc = FdMmap::accessible_reserved(mapped_bytes.0, request_bytes)
@@ -13,6 +13,10 @@ pub enum SerializeError {
     /// A generic serialization error
     #[error("{0}")]
     Generic(String),
+    /// The runtime of the module can't serialize it. The module can be
+    /// compiled again from its original bytes instead.
+    #[error("serialization is not supported by the runtime: {0}")]
+    UnsupportedByRuntime(String),
 }
 
 /// The Deserialize error can occur when loading a
@@ -61,6 +65,15 @@ pub enum MemoryError {
         /// The attempted amount to grow by in pages.
         attempted_delta: Pages,
     },
+    /// The memory is larger than the requested size, and memories can't
+    /// shrink.
+    #[error("The memory could not shrink: current size {} pages, requested size: {} pages", current.0, target.0)]
+    CouldNotShrink {
+        /// The current size in pages.
+        current: Pages,
+        /// The requested size in pages.
+        target: Pages,
+    },
     /// Invalid memory was provided.
     #[error("The memory is invalid because {}", reason)]
     InvalidMemory {
@@ -212,8 +225,13 @@ impl MiddlewareError {
 ///
 /// When a WebAssembly function can't be translated, one of these error codes will be returned
 /// to describe the failure.
+///
+/// It is also the error of the other `wasmer_types` operations, by the
+/// stage that failed: [`WasmError::Parse`], [`WasmError::Compile`],
+/// [`WasmError::Link`] and [`WasmError::Runtime`].
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(Error))]
+#[non_exhaustive]
 pub enum WasmError {
     /// The input WebAssembly code is invalid.
     ///
@@ -247,6 +265,22 @@ pub enum WasmError {
     /// A generic error.
     #[cfg_attr(feature = "std", error("{0}"))]
     Generic(String),
+
+    /// The input could not be parsed, e.g. a malformed binary or text.
+    #[cfg_attr(feature = "std", error("Parse error: {0}"))]
+    Parse(String),
+
+    /// The module could not be compiled.
+    #[cfg_attr(feature = "std", error("Compile error: {0}"))]
+    Compile(String),
+
+    /// The imports of the module could not be linked.
+    #[cfg_attr(feature = "std", error("Link error: {0}"))]
+    Link(String),
+
+    /// An error happened while running the module.
+    #[cfg_attr(feature = "std", error("Runtime error: {0}"))]
+    Runtime(String),
 }
 
 impl From<MiddlewareError> for WasmError {
@@ -265,6 +299,18 @@ pub enum ParseCpuFeatureError {
     Missing(String),
 }
 
+impl From<String> for WasmError {
+    fn from(message: String) -> Self {
+        Self::Generic(message)
+    }
+}
+
+impl From<&str> for WasmError {
+    fn from(message: &str) -> Self {
+        Self::Generic(message.to_string())
+    }
+}
+
 /// A convenient alias for a `Result` that uses `WasmError` as the error type.
 pub type WasmResult<T> = Result<T, WasmError>;
 
@@ -291,4 +337,22 @@ mod tests {
             err => panic!("Unexpected error: {err:?}"),
         }
     }
+
+    #[test]
+    fn strings_convert_to_generic_wasm_error() {
+        let error = WasmError::from(String::from("bad table index"));
+        assert!(matches!(&error, WasmError::Generic(message) if message == "bad table index"));
+        assert_eq!(error.to_string(), "bad table index");
+
+        let error: WasmError = "bad table index".into();
+        assert!(matches!(error, WasmError::Generic(_)));
+    }
+
+    #[test]
+    fn wasm_error_stages() {
+        let error = WasmError::Parse("unexpected end".to_string());
+        assert_eq!(error.to_string(), "Parse error: unexpected end");
+        let error = WasmError::Link("unknown import".to_string());
+        assert_eq!(error.to_string(), "Link error: unknown import");
+    }
 }
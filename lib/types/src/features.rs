@@ -387,6 +387,57 @@ impl Features {
 
         Ok(features)
     }
+
+    #[cfg(feature = "detect-wasm-features")]
+    /// Computes the minimal set of features a module binary requires.
+    ///
+    /// Unlike [`Features::detect_from_wasm`], this does not start from the
+    /// default feature set: every proposal is reported as disabled unless the
+    /// module fails to validate without it. Each proposal is checked by
+    /// validating the module with all proposals enabled except that one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module doesn't validate even with every
+    /// supported proposal enabled.
+    pub fn required_by_wasm(wasm_bytes: &[u8]) -> Result<Self, wasmparser::BinaryReaderError> {
+        let all = WasmFeatures::default()
+            | WasmFeatures::THREADS
+            | WasmFeatures::REFERENCE_TYPES
+            | WasmFeatures::SIMD
+            | WasmFeatures::BULK_MEMORY
+            | WasmFeatures::MULTI_VALUE
+            | WasmFeatures::TAIL_CALL
+            | WasmFeatures::MULTI_MEMORY
+            | WasmFeatures::MEMORY64
+            | WasmFeatures::EXCEPTIONS
+            | WasmFeatures::LEGACY_EXCEPTIONS
+            | WasmFeatures::RELAXED_SIMD
+            | WasmFeatures::EXTENDED_CONST;
+
+        Validator::new_with_features(all).validate_all(wasm_bytes)?;
+
+        let required = |proposal: WasmFeatures| {
+            Validator::new_with_features(all - proposal)
+                .validate_all(wasm_bytes)
+                .is_err()
+        };
+
+        Ok(Self {
+            threads: required(WasmFeatures::THREADS),
+            reference_types: required(WasmFeatures::REFERENCE_TYPES),
+            simd: required(WasmFeatures::SIMD),
+            bulk_memory: required(WasmFeatures::BULK_MEMORY),
+            multi_value: required(WasmFeatures::MULTI_VALUE),
+            tail_call: required(WasmFeatures::TAIL_CALL),
+            module_linking: false,
+            multi_memory: required(WasmFeatures::MULTI_MEMORY),
+            memory64: required(WasmFeatures::MEMORY64),
+            exceptions: required(WasmFeatures::EXCEPTIONS | WasmFeatures::LEGACY_EXCEPTIONS),
+            relaxed_simd: required(WasmFeatures::RELAXED_SIMD),
+            extended_const: required(WasmFeatures::EXTENDED_CONST),
+        })
+    }
 }
 
 impl Default for Features {
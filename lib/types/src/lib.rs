@@ -55,6 +55,7 @@ mod features;
 mod indexes;
 mod initializers;
 mod libcalls;
+mod limits;
 mod memory;
 mod module;
 mod module_hash;
@@ -88,6 +89,7 @@ pub use crate::initializers::{
     DataInitializerLike, DataInitializerLocation, DataInitializerLocationLike,
     OwnedDataInitializer, TableInitializer,
 };
+pub use crate::limits::{InstantiationCost, LimitError, StoreLimits};
 pub use crate::memory::{Memory32, Memory64, MemorySize};
 pub use crate::module::{ExportsIterator, ImportKey, ImportsIterator, ModuleInfo};
 pub use crate::module_hash::{HashAlgorithm, ModuleHash};
@@ -108,7 +110,9 @@ pub use serialize::MetadataHeader;
 pub use crate::stack::{FrameInfo, SourceLoc, TrapInformation};
 pub use crate::store_id::StoreId;
 pub use crate::trapcode::{OnCalledAction, TrapCode};
-pub use crate::utils::is_wasm;
+pub use crate::utils::{
+    is_wasm, strip_custom_sections, wasm_binary_version, StripMode, WASM_BINARY_VERSION,
+};
 pub use crate::vmoffsets::{TargetSharedSignatureIndex, VMBuiltinFunctionIndex, VMOffsets};
 
 /// Offset in bytes from the beginning of the function.
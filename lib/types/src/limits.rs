@@ -0,0 +1,205 @@
+//! Estimating the resources a module allocates when instantiated, so hosts
+//! can reject a module before paying for its instantiation.
+use crate::ModuleInfo;
+use thiserror::Error;
+
+/// The resources instantiating a module allocates, as declared by the
+/// module itself.
+///
+/// Only the memories, tables and globals the module defines are counted:
+/// imported ones are allocated by whoever provides them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InstantiationCost {
+    /// The number of pages the defined memories allocate up front.
+    pub min_memory_pages: u64,
+    /// The number of pages the defined memories may grow to, or `None` if
+    /// at least one of them has no maximum.
+    pub max_memory_pages: Option<u64>,
+    /// The number of elements the defined tables allocate up front.
+    pub table_elements: u64,
+    /// The number of globals the module defines.
+    pub globals: usize,
+    /// The size in bytes of the passive data segments kept alive by every
+    /// instance.
+    pub data_segment_bytes: usize,
+    /// The number of imports the module requires.
+    pub imports: usize,
+}
+
+impl InstantiationCost {
+    /// Computes the instantiation cost of a module from its [`ModuleInfo`].
+    pub fn from_module_info(info: &ModuleInfo) -> Self {
+        let memories = info
+            .memories
+            .values()
+            .skip(info.num_imported_memories)
+            .collect::<Vec<_>>();
+        let min_memory_pages = memories.iter().map(|m| u64::from(m.minimum.0)).sum();
+        let max_memory_pages = memories
+            .iter()
+            .map(|m| m.maximum.map(|max| u64::from(max.0)))
+            .sum();
+        let table_elements = info
+            .tables
+            .values()
+            .skip(info.num_imported_tables)
+            .map(|t| u64::from(t.minimum))
+            .sum();
+
+        Self {
+            min_memory_pages,
+            max_memory_pages,
+            table_elements,
+            globals: info.globals.len() - info.num_imported_globals,
+            data_segment_bytes: info.passive_data.values().map(|data| data.len()).sum(),
+            imports: info.imports.len(),
+        }
+    }
+
+    /// Checks this cost against the given limits.
+    ///
+    /// A module whose memories have no maximum is only rejected if their
+    /// initial size already exceeds the memory limit: growing past it is
+    /// left to the runtime to refuse.
+    pub fn fits_within(&self, limits: &StoreLimits) -> Result<(), LimitError> {
+        fn check<T: PartialOrd + Copy>(
+            required: T,
+            limit: Option<T>,
+            error: impl FnOnce(T, T) -> LimitError,
+        ) -> Result<(), LimitError> {
+            match limit {
+                Some(limit) if required > limit => Err(error(required, limit)),
+                _ => Ok(()),
+            }
+        }
+
+        check(
+            self.min_memory_pages,
+            limits.max_memory_pages,
+            |required, limit| LimitError::MemoryPages { required, limit },
+        )?;
+        check(
+            self.table_elements,
+            limits.max_table_elements,
+            |required, limit| LimitError::TableElements { required, limit },
+        )?;
+        check(self.globals, limits.max_globals, |required, limit| {
+            LimitError::Globals { required, limit }
+        })?;
+        check(
+            self.data_segment_bytes,
+            limits.max_data_segment_bytes,
+            |required, limit| LimitError::DataSegmentBytes { required, limit },
+        )?;
+        check(self.imports, limits.max_imports, |required, limit| {
+            LimitError::Imports { required, limit }
+        })
+    }
+}
+
+/// Upper bounds on the resources a single instance may allocate.
+///
+/// `None` means unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StoreLimits {
+    /// The maximum number of memory pages allocated up front.
+    pub max_memory_pages: Option<u64>,
+    /// The maximum number of table elements allocated up front.
+    pub max_table_elements: Option<u64>,
+    /// The maximum number of defined globals.
+    pub max_globals: Option<usize>,
+    /// The maximum size in bytes of passive data segments.
+    pub max_data_segment_bytes: Option<usize>,
+    /// The maximum number of imports.
+    pub max_imports: Option<usize>,
+}
+
+/// The error returned when an [`InstantiationCost`] exceeds [`StoreLimits`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+    /// The memories need more pages than allowed.
+    #[error("the module requires {required} memory pages, but the limit is {limit}")]
+    MemoryPages {
+        /// The number of pages the module requires.
+        required: u64,
+        /// The limit that was exceeded.
+        limit: u64,
+    },
+    /// The tables need more elements than allowed.
+    #[error("the module requires {required} table elements, but the limit is {limit}")]
+    TableElements {
+        /// The number of elements the module requires.
+        required: u64,
+        /// The limit that was exceeded.
+        limit: u64,
+    },
+    /// The module defines more globals than allowed.
+    #[error("the module defines {required} globals, but the limit is {limit}")]
+    Globals {
+        /// The number of globals the module defines.
+        required: usize,
+        /// The limit that was exceeded.
+        limit: usize,
+    },
+    /// The data segments are larger than allowed.
+    #[error("the module has {required} bytes of data segments, but the limit is {limit}")]
+    DataSegmentBytes {
+        /// The size of the data segments.
+        required: usize,
+        /// The limit that was exceeded.
+        limit: usize,
+    },
+    /// The module requires more imports than allowed.
+    #[error("the module requires {required} imports, but the limit is {limit}")]
+    Imports {
+        /// The number of imports the module requires.
+        required: usize,
+        /// The limit that was exceeded.
+        limit: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MemoryType, Pages, TableType, Type};
+
+    #[test]
+    fn huge_memory_is_rejected() {
+        let mut info = ModuleInfo::new();
+        // 1GiB worth of 64KiB pages.
+        info.memories
+            .push(MemoryType::new(Pages(16384), Some(Pages(16384)), false));
+        info.tables.push(TableType::new(Type::FuncRef, 10, None));
+
+        let cost = InstantiationCost::from_module_info(&info);
+        assert_eq!(cost.min_memory_pages, 16384);
+        assert_eq!(cost.max_memory_pages, Some(16384));
+        assert_eq!(cost.table_elements, 10);
+
+        assert_eq!(cost.fits_within(&StoreLimits::default()), Ok(()));
+        let limits = StoreLimits {
+            max_memory_pages: Some(160),
+            ..Default::default()
+        };
+        assert_eq!(
+            cost.fits_within(&limits),
+            Err(LimitError::MemoryPages {
+                required: 16384,
+                limit: 160
+            })
+        );
+    }
+
+    #[test]
+    fn imported_memories_are_not_counted() {
+        let mut info = ModuleInfo::new();
+        info.memories.push(MemoryType::new(Pages(100), None, false));
+        info.num_imported_memories = 1;
+        info.memories.push(MemoryType::new(Pages(1), None, false));
+
+        let cost = InstantiationCost::from_module_info(&info);
+        assert_eq!(cost.min_memory_pages, 1);
+        assert_eq!(cost.max_memory_pages, None);
+    }
+}
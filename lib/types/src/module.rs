@@ -356,6 +356,20 @@ impl ModuleInfo {
         self.hash
     }
 
+    /// Renames the module part of every import whose module name is a key
+    /// of `remapping`, keeping the imports in their original order.
+    pub fn remap_import_modules(&mut self, remapping: &HashMap<String, String>) {
+        self.imports = std::mem::take(&mut self.imports)
+            .into_iter()
+            .map(|(mut key, index)| {
+                if let Some(module) = remapping.get(&key.module) {
+                    key.module.clone_from(module);
+                }
+                (key, index)
+            })
+            .collect();
+    }
+
     /// Get the given passive element, if it exists.
     pub fn get_passive_element(&self, index: ElemIndex) -> Option<&[FunctionIndex]> {
         self.passive_elements.get(&index).map(|es| &**es)
@@ -707,3 +721,32 @@ impl<I: Iterator<Item = ImportType> + Sized> Iterator for ImportsIterator<I> {
         self.iter.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remap_import_modules() {
+        let mut info = ModuleInfo::new();
+        for (i, (module, field)) in [("env", "a"), ("other", "b"), ("env", "c")]
+            .into_iter()
+            .enumerate()
+        {
+            info.imports.insert(
+                (module.to_string(), field.to_string(), i as u32).into(),
+                ImportIndex::Function(FunctionIndex::new(i)),
+            );
+        }
+
+        let remapping = HashMap::from([("env".to_string(), "wasi".to_string())]);
+        info.remap_import_modules(&remapping);
+
+        let imports = info
+            .imports
+            .keys()
+            .map(|key| (key.module.as_str(), key.field.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(imports, [("wasi", "a"), ("other", "b"), ("wasi", "c")]);
+    }
+}
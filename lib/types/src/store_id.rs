@@ -19,6 +19,13 @@ impl loupe::MemoryUsage for StoreId {
     }
 }
 
+impl StoreId {
+    /// Returns the ID as a number, for use in logs and metrics.
+    pub fn as_u64(&self) -> u64 {
+        self.0.get() as u64
+    }
+}
+
 impl Default for StoreId {
     // Allocates a unique ID for a new context.
     fn default() -> Self {
@@ -39,3 +46,23 @@ impl Display for StoreId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn store_ids_are_map_keys() {
+        let (a, b) = (StoreId::default(), StoreId::default());
+        assert_ne!(a, b);
+        assert_ne!(a.as_u64(), b.as_u64());
+
+        let mut names = HashMap::new();
+        names.insert(a, "a");
+        names.insert(b, "b");
+        assert_eq!(names[&a], "a");
+        assert_eq!(names[&b], "b");
+        assert_eq!(a.to_string(), a.as_u64().to_string());
+    }
+}
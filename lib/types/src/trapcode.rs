@@ -83,6 +83,62 @@ impl TrapCode {
             Self::UncaughtException => "uncaught exception",
         }
     }
+
+    /// Recognizes the trap code from the message of a trap raised by
+    /// another WebAssembly runtime, such as the ones of the `wamr`,
+    /// `wasmi` and `v8` backends, which only report traps as text.
+    ///
+    /// The messages of [`TrapCode::message`] are recognized as well.
+    pub fn from_message(message: &str) -> Option<Self> {
+        // Checked in order: the first matching pattern wins.
+        const PATTERNS: &[(&str, TrapCode)] = &[
+            ("unreachable", TrapCode::UnreachableCodeReached),
+            (
+                "out of bounds memory access",
+                TrapCode::HeapAccessOutOfBounds,
+            ),
+            (
+                "memory access out of bounds",
+                TrapCode::HeapAccessOutOfBounds,
+            ),
+            ("divide by zero", TrapCode::IntegerDivisionByZero),
+            ("remainder by zero", TrapCode::IntegerDivisionByZero),
+            ("integer overflow", TrapCode::IntegerOverflow),
+            ("divide result unrepresentable", TrapCode::IntegerOverflow),
+            (
+                "invalid conversion to integer",
+                TrapCode::BadConversionToInteger,
+            ),
+            (
+                "unrepresentable in integer range",
+                TrapCode::BadConversionToInteger,
+            ),
+            ("call stack exhausted", TrapCode::StackOverflow),
+            ("stack overflow", TrapCode::StackOverflow),
+            ("maximum call stack size exceeded", TrapCode::StackOverflow),
+            ("signature mismatch", TrapCode::BadSignature),
+            ("indirect call type mismatch", TrapCode::BadSignature),
+            ("uninitialized element", TrapCode::IndirectCallToNull),
+            ("indirect call to null", TrapCode::IndirectCallToNull),
+            (
+                "out of bounds table access",
+                TrapCode::TableAccessOutOfBounds,
+            ),
+            (
+                "table index is out of bounds",
+                TrapCode::TableAccessOutOfBounds,
+            ),
+            ("undefined element", TrapCode::TableAccessOutOfBounds),
+            ("unaligned atomic", TrapCode::UnalignedAtomic),
+            ("misaligned", TrapCode::HeapMisaligned),
+            ("uncaught exception", TrapCode::UncaughtException),
+        ];
+        let message = message.to_lowercase();
+        PATTERNS
+            .iter()
+            .find(|(pattern, _)| message.contains(pattern))
+            .map(|&(_, code)| code)
+    }
 }
 
 impl Display for TrapCode {
@@ -172,4 +228,47 @@ mod tests {
         assert_eq!("user-1".parse::<TrapCode>(), Err(()));
         assert_eq!("users".parse::<TrapCode>(), Err(()));
     }
+
+    #[test]
+    fn from_message() {
+        for code in CODES.into_iter().chain([TrapCode::UncaughtException]) {
+            assert_eq!(TrapCode::from_message(code.message()), Some(code));
+        }
+        for (message, code) in [
+            // wamr
+            (
+                "Exception: integer divide by zero",
+                TrapCode::IntegerDivisionByZero,
+            ),
+            (
+                "Exception: wasm operand stack overflow",
+                TrapCode::StackOverflow,
+            ),
+            // wasmi
+            (
+                "wasm `unreachable` instruction executed",
+                TrapCode::UnreachableCodeReached,
+            ),
+            // v8
+            (
+                "RuntimeError: memory access out of bounds",
+                TrapCode::HeapAccessOutOfBounds,
+            ),
+            (
+                "RuntimeError: remainder by zero",
+                TrapCode::IntegerDivisionByZero,
+            ),
+            (
+                "RuntimeError: float unrepresentable in integer range",
+                TrapCode::BadConversionToInteger,
+            ),
+            (
+                "RangeError: Maximum call stack size exceeded",
+                TrapCode::StackOverflow,
+            ),
+        ] {
+            assert_eq!(TrapCode::from_message(message), Some(code), "{message}");
+        }
+        assert_eq!(TrapCode::from_message("host function failed"), None);
+    }
 }
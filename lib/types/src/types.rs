@@ -4,7 +4,7 @@ use crate::lib::std::fmt;
 use crate::lib::std::format;
 use crate::lib::std::string::{String, ToString};
 use crate::lib::std::vec::Vec;
-use crate::units::Pages;
+use crate::units::{Pages, WASM_PAGE_SIZE};
 
 use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 #[cfg(feature = "enable-serde")]
@@ -54,6 +54,31 @@ impl Type {
     pub fn is_ref(self) -> bool {
         matches!(self, Self::ExternRef | Self::FuncRef | Self::ExceptionRef)
     }
+
+    /// Returns true for the numeric types: `I32`, `I64`, `F32`, `F64`
+    /// and `V128`.
+    ///
+    /// Same as [`Type::is_num`].
+    pub fn is_numeric(&self) -> bool {
+        self.is_num()
+    }
+
+    /// Returns true for the reference types: `FuncRef`, `ExternRef` and
+    /// `ExceptionRef`.
+    ///
+    /// Same as [`Type::is_ref`].
+    pub fn is_reference(&self) -> bool {
+        self.is_ref()
+    }
+
+    /// Returns true if a value of this type can be used where a value of
+    /// type `other` is expected.
+    ///
+    /// The reference types supported so far are disjoint hierarchies, so
+    /// a type is only a subtype of itself.
+    pub fn is_subtype_of(&self, other: &Self) -> bool {
+        self == other
+    }
 }
 
 impl fmt::Display for Type {
@@ -144,7 +169,12 @@ fn is_global_compatible(exported: GlobalType, imported: GlobalType) -> bool {
         mutability: imported_mutability,
     } = imported;
 
-    exported_ty == imported_ty && imported_mutability == exported_mutability
+    // Immutable globals are covariant, mutable ones are invariant.
+    imported_mutability == exported_mutability
+        && match exported_mutability {
+            Mutability::Const => exported_ty.is_subtype_of(&imported_ty),
+            Mutability::Var => exported_ty == imported_ty,
+        }
 }
 
 fn is_table_element_type_compatible(exported_type: Type, imported_type: Type) -> bool {
@@ -231,10 +261,30 @@ impl ExternType {
         (Table(TableType) table unwrap_table)
         (Memory(MemoryType) memory unwrap_memory)
     }
-    /// Check if two externs are compatible
+    /// Returns true if an extern of this type can satisfy an import of
+    /// type `other`, following the import matching rules of the
+    /// WebAssembly spec.
+    ///
+    /// Functions must be subtypes of the imported type, globals must have
+    /// the same mutability and a matching type, and tables and memories
+    /// must have limits within the imported ones. `runtime_size` is the
+    /// current size of the table or memory, when it grew past the minimum
+    /// of its type.
+    ///
+    /// This only needs the types, so it can check the imports of a module
+    /// before instantiating it, by passing `None` as `runtime_size`.
+    ///
+    /// # Example
+    /// ```
+    /// # use wasmer_types::{ExternType, MemoryType};
+    /// let exported = ExternType::Memory(MemoryType::new(2, Some(4), false));
+    /// let imported = ExternType::Memory(MemoryType::new(1, Some(8), false));
+    /// assert!(exported.is_compatible_with(&imported, None));
+    /// assert!(!imported.is_compatible_with(&exported, None));
+    /// ```
     pub fn is_compatible_with(&self, other: &Self, runtime_size: Option<u32>) -> bool {
         match (self, other) {
-            (Self::Function(a), Self::Function(b)) => a == b,
+            (Self::Function(a), Self::Function(b)) => a.is_subtype_of(b),
             (Self::Global(a), Self::Global(b)) => is_global_compatible(*a, *b),
             (Self::Table(a), Self::Table(b)) => is_table_compatible(a, b, runtime_size),
             (Self::Memory(a), Self::Memory(b)) => is_memory_compatible(a, b, runtime_size),
@@ -243,6 +293,136 @@ impl ExternType {
             _ => false,
         }
     }
+
+    /// Describes why `self` can't satisfy an import of type `imported`, with
+    /// one entry per difference, such as `param 2: expected i64, got i32`.
+    ///
+    /// Returns an empty list when [`ExternType::is_compatible_with`] holds
+    /// for the same arguments.
+    pub fn incompatibilities(&self, imported: &Self, runtime_size: Option<u32>) -> Vec<String> {
+        let mut diffs = Vec::new();
+        match (self, imported) {
+            (Self::Function(a), Self::Function(b)) => {
+                diff_types("param", a.params(), b.params(), &mut diffs);
+                diff_types("result", a.results(), b.results(), &mut diffs);
+            }
+            (Self::Tag(a), Self::Tag(b)) => {
+                diff_types("param", a.params(), b.params(), &mut diffs);
+            }
+            (Self::Global(a), Self::Global(b)) => {
+                if a.ty != b.ty {
+                    diffs.push(format!(
+                        "type: expected {}, got {}",
+                        type_name(b.ty),
+                        type_name(a.ty)
+                    ));
+                }
+                if a.mutability != b.mutability {
+                    let name = |m: Mutability| {
+                        if m.is_mutable() {
+                            "mutable"
+                        } else {
+                            "immutable"
+                        }
+                    };
+                    diffs.push(format!(
+                        "mutability: expected {}, got {}",
+                        name(b.mutability),
+                        name(a.mutability)
+                    ));
+                }
+            }
+            (Self::Table(a), Self::Table(b)) => {
+                if !is_table_element_type_compatible(a.ty, b.ty) {
+                    diffs.push(format!(
+                        "element type: expected {}, got {}",
+                        type_name(b.ty),
+                        type_name(a.ty)
+                    ));
+                }
+                let size = runtime_size.unwrap_or(a.minimum);
+                diff_limits((size, a.maximum), (b.minimum, b.maximum), &mut diffs);
+            }
+            (Self::Memory(a), Self::Memory(b)) => {
+                let size = runtime_size.unwrap_or(a.minimum.0);
+                let maximum = |m: Option<Pages>| m.map(|pages| pages.0);
+                diff_limits(
+                    (size, maximum(a.maximum)),
+                    (b.minimum.0, maximum(b.maximum)),
+                    &mut diffs,
+                );
+                if a.shared != b.shared {
+                    let name = |shared| if shared { "shared" } else { "not shared" };
+                    diffs.push(format!(
+                        "sharing: expected {}, got {}",
+                        name(b.shared),
+                        name(a.shared)
+                    ));
+                }
+            }
+            _ => diffs.push(format!(
+                "expected a {}, got a {}",
+                imported.kind_name(),
+                self.kind_name()
+            )),
+        }
+        diffs
+    }
+
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Function(_) => "function",
+            Self::Global(_) => "global",
+            Self::Table(_) => "table",
+            Self::Memory(_) => "memory",
+            Self::Tag(_) => "tag",
+        }
+    }
+}
+
+/// The name of `ty` in the WebAssembly text format.
+fn type_name(ty: Type) -> String {
+    ty.to_string().to_lowercase()
+}
+
+fn diff_types(what: &str, provided: &[Type], expected: &[Type], diffs: &mut Vec<String>) {
+    if provided.len() != expected.len() {
+        diffs.push(format!(
+            "expected {} {what}s, got {}",
+            expected.len(),
+            provided.len()
+        ));
+    }
+    for (i, (provided, expected)) in provided.iter().zip(expected).enumerate() {
+        if provided != expected {
+            diffs.push(format!(
+                "{what} {i}: expected {}, got {}",
+                type_name(*expected),
+                type_name(*provided)
+            ));
+        }
+    }
+}
+
+fn diff_limits(
+    (size, provided_maximum): (u32, Option<u32>),
+    (minimum, expected_maximum): (u32, Option<u32>),
+    diffs: &mut Vec<String>,
+) {
+    if size < minimum {
+        diffs.push(format!("minimum: expected at least {minimum}, got {size}"));
+    }
+    if let Some(expected_maximum) = expected_maximum {
+        match provided_maximum {
+            None => diffs.push(format!(
+                "maximum: expected at most {expected_maximum}, got no maximum"
+            )),
+            Some(maximum) if maximum > expected_maximum => diffs.push(format!(
+                "maximum: expected at most {expected_maximum}, got {maximum}"
+            )),
+            _ => {}
+        }
+    }
 }
 
 // TODO: `shrink_to_fit` these or change it to `Box<[Type]>` if not using
@@ -285,6 +465,33 @@ impl FunctionType {
     pub fn results(&self) -> &[Type] {
         &self.results
     }
+
+    /// Returns true if a function of this type can be used where a
+    /// function of type `other` is expected.
+    ///
+    /// Following the function subtyping rule of the GC proposal, parameters
+    /// are contravariant and results are covariant: `self` must accept
+    /// every parameter `other` accepts, and return results that are valid
+    /// results of `other`.
+    pub fn is_subtype_of(&self, other: &Self) -> bool {
+        self.params.len() == other.params.len()
+            && self.results.len() == other.results.len()
+            && self
+                .params
+                .iter()
+                .zip(other.params.iter())
+                .all(|(mine, theirs)| theirs.is_subtype_of(mine))
+            && self
+                .results
+                .iter()
+                .zip(other.results.iter())
+                .all(|(mine, theirs)| mine.is_subtype_of(theirs))
+    }
+
+    /// Returns true if either type is a subtype of the other.
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        self.is_subtype_of(other) || other.is_subtype_of(self)
+    }
 }
 
 impl fmt::Display for FunctionType {
@@ -595,6 +802,17 @@ impl MemoryType {
             shared,
         }
     }
+
+    /// Returns the minimum size of the memory in bytes.
+    pub fn minimum_byte_size(&self) -> u64 {
+        u64::from(self.minimum.0) * WASM_PAGE_SIZE as u64
+    }
+
+    /// Returns the maximum size of the memory in bytes, if it has one.
+    pub fn maximum_byte_size(&self) -> Option<u64> {
+        self.maximum
+            .map(|maximum| u64::from(maximum.0) * WASM_PAGE_SIZE as u64)
+    }
 }
 
 impl fmt::Display for MemoryType {
@@ -700,6 +918,26 @@ mod tests {
     const V128_I64_TO_I32: ([Type; 2], [Type; 1]) = ([Type::V128, Type::I64], [Type::I32]);
     const NINE_V128_TO_NINE_I32: ([Type; 9], [Type; 9]) = ([Type::V128; 9], [Type::I32; 9]);
 
+    #[test]
+    fn memory_byte_sizes() {
+        let ty = MemoryType::new(2, Some(65536), false);
+        assert_eq!(ty.minimum_byte_size(), 2 * 65536);
+        assert_eq!(ty.maximum_byte_size(), Some(1 << 32));
+        assert_eq!(MemoryType::new(0, None, false).maximum_byte_size(), None);
+    }
+
+    #[test]
+    fn type_categories() {
+        for ty in [Type::I32, Type::I64, Type::F32, Type::F64, Type::V128] {
+            assert!(ty.is_numeric());
+            assert!(!ty.is_reference());
+        }
+        for ty in [Type::FuncRef, Type::ExternRef, Type::ExceptionRef] {
+            assert!(!ty.is_numeric());
+            assert!(ty.is_reference());
+        }
+    }
+
     #[test]
     fn convert_tuple_to_functiontype() {
         let ty: FunctionType = VOID_TO_VOID.into();
@@ -723,4 +961,108 @@ mod tests {
         assert_eq!(ty.params().len(), 9);
         assert_eq!(ty.results().len(), 9);
     }
+
+    #[test]
+    fn function_subtyping() {
+        let unary = FunctionType::new([Type::I32], [Type::I64]);
+        assert!(unary.is_subtype_of(&unary));
+        assert!(unary.is_compatible_with(&unary.clone()));
+
+        let other_param = FunctionType::new([Type::I64], [Type::I64]);
+        let other_result = FunctionType::new([Type::I32], [Type::FuncRef]);
+        let binary = FunctionType::new([Type::I32, Type::I32], [Type::I64]);
+        for ty in [&other_param, &other_result, &binary] {
+            assert!(!unary.is_subtype_of(ty));
+            assert!(!ty.is_subtype_of(&unary));
+            assert!(!unary.is_compatible_with(ty));
+        }
+
+        assert!(Type::ExternRef.is_subtype_of(&Type::ExternRef));
+        assert!(!Type::FuncRef.is_subtype_of(&Type::ExternRef));
+    }
+
+    #[test]
+    fn extern_type_compatibility() {
+        let func = ExternType::Function(FunctionType::new([Type::I32], [Type::I64]));
+        assert!(func.is_compatible_with(&func, None));
+        let other = ExternType::Function(FunctionType::new([Type::I64], [Type::I64]));
+        assert!(!func.is_compatible_with(&other, None));
+
+        let global = |ty, mutability| ExternType::Global(GlobalType::new(ty, mutability));
+        for mutability in [Mutability::Const, Mutability::Var] {
+            assert!(global(Type::I32, mutability)
+                .is_compatible_with(&global(Type::I32, mutability), None));
+            assert!(!global(Type::I32, mutability)
+                .is_compatible_with(&global(Type::I64, mutability), None));
+        }
+        assert!(!global(Type::I32, Mutability::Const)
+            .is_compatible_with(&global(Type::I32, Mutability::Var), None));
+        assert!(!global(Type::I32, Mutability::Var)
+            .is_compatible_with(&global(Type::I32, Mutability::Const), None));
+
+        let table = |ty, min, max| ExternType::Table(TableType::new(ty, min, max));
+        assert!(table(Type::ExternRef, 2, Some(4))
+            .is_compatible_with(&table(Type::ExternRef, 1, None), None));
+        assert!(!table(Type::ExternRef, 1, None)
+            .is_compatible_with(&table(Type::ExternRef, 2, None), None));
+        assert!(table(Type::ExternRef, 1, None)
+            .is_compatible_with(&table(Type::ExternRef, 2, None), Some(2)));
+        assert!(!table(Type::ExternRef, 1, None)
+            .is_compatible_with(&table(Type::ExternRef, 1, Some(4)), None));
+        assert!(
+            !table(Type::I32, 1, None).is_compatible_with(&table(Type::ExternRef, 1, None), None)
+        );
+
+        let memory = |min, max, shared| ExternType::Memory(MemoryType::new(min, max, shared));
+        assert!(memory(2, Some(4), false).is_compatible_with(&memory(1, Some(4), false), None));
+        assert!(!memory(2, Some(8), false).is_compatible_with(&memory(1, Some(4), false), None));
+        assert!(!memory(1, Some(4), true).is_compatible_with(&memory(1, Some(4), false), None));
+
+        assert!(!func.is_compatible_with(&memory(1, None, false), None));
+    }
+
+    #[test]
+    fn extern_type_incompatibilities() {
+        let func = |params: &[Type]| ExternType::Function(FunctionType::new(params, []));
+        let expected = func(&[Type::I32, Type::I32, Type::I64]);
+        assert!(func(&[Type::I32, Type::I32, Type::I64])
+            .incompatibilities(&expected, None)
+            .is_empty());
+        assert_eq!(
+            func(&[Type::I32, Type::I32, Type::I32]).incompatibilities(&expected, None),
+            ["param 2: expected i64, got i32"]
+        );
+        assert_eq!(
+            func(&[Type::I32]).incompatibilities(&expected, None),
+            ["expected 3 params, got 1"]
+        );
+
+        let global = |mutability| ExternType::Global(GlobalType::new(Type::I32, mutability));
+        assert_eq!(
+            global(Mutability::Const).incompatibilities(&global(Mutability::Var), None),
+            ["mutability: expected mutable, got immutable"]
+        );
+
+        let memory = |min, max| ExternType::Memory(MemoryType::new(min, max, false));
+        let expected = memory(2, Some(4));
+        assert!(memory(2, Some(4))
+            .incompatibilities(&expected, None)
+            .is_empty());
+        assert_eq!(
+            memory(1, None).incompatibilities(&expected, None),
+            [
+                "minimum: expected at least 2, got 1",
+                "maximum: expected at most 4, got no maximum"
+            ]
+        );
+        // A memory that grew past the minimum satisfies it.
+        assert!(memory(1, Some(3))
+            .incompatibilities(&expected, Some(2))
+            .is_empty());
+
+        assert_eq!(
+            memory(1, None).incompatibilities(&global(Mutability::Var), None),
+            ["expected a global, got a memory"]
+        );
+    }
 }
@@ -48,6 +48,29 @@ impl Pages {
         }
     }
 
+    /// Saturating addition. Computes `self + rhs`, returning
+    /// [`Pages::max_value`] if overflow occurred.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or_else(Self::max_value)
+    }
+
+    /// Checked multiplication. Computes `self * factor`,
+    /// returning `None` if overflow occurred.
+    pub fn checked_mul(self, factor: u32) -> Option<Self> {
+        let multiplied = u64::from(self.0) * u64::from(factor);
+        if multiplied <= u64::from(WASM_MAX_PAGES) {
+            Some(Self(multiplied as u32))
+        } else {
+            None
+        }
+    }
+
+    /// Calculate number of bytes from pages, returning `None` if they
+    /// don't fit in a `usize`.
+    pub fn to_bytes_checked(self) -> Option<usize> {
+        (self.0 as usize).checked_mul(WASM_PAGE_SIZE)
+    }
+
     /// Calculate number of bytes from pages.
     pub fn bytes(self) -> Bytes {
         self.into()
@@ -66,6 +89,12 @@ impl From<u32> for Pages {
     }
 }
 
+impl From<Pages> for u64 {
+    fn from(pages: Pages) -> Self {
+        pages.0.into()
+    }
+}
+
 /// Units of WebAssembly memory in terms of 8-bit bytes.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "enable-serde", derive(Serialize, Deserialize))]
@@ -115,7 +144,7 @@ where
     }
 }
 
-/// The only error that can happen when converting `Bytes` to `Pages`
+/// The only error that can happen when converting `Bytes` or a `u64` to `Pages`
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
 #[error("Number of pages exceeds uint32 range")]
 pub struct PageCountOutOfRange;
@@ -131,6 +160,14 @@ impl TryFrom<Bytes> for Pages {
     }
 }
 
+impl TryFrom<u64> for Pages {
+    type Error = PageCountOutOfRange;
+
+    fn try_from(pages: u64) -> Result<Self, Self::Error> {
+        Ok(Self(pages.try_into().or(Err(PageCountOutOfRange))?))
+    }
+}
+
 impl<T> Sub<T> for Bytes
 where
     T: Into<Self>,
@@ -181,4 +218,46 @@ mod tests {
         let result = Pages::try_from(Bytes(usize::MAX));
         assert_eq!(result.unwrap_err(), PageCountOutOfRange);
     }
+
+    #[test]
+    fn pages_ordering_and_u64() {
+        use std::collections::BTreeSet;
+
+        assert!(Pages(1) < Pages(2));
+        assert_eq!(Pages(3).max(Pages(2)), Pages(3));
+        let sorted: Vec<_> = BTreeSet::from([Pages(5), Pages(0), Pages(2)])
+            .into_iter()
+            .collect();
+        assert_eq!(sorted, [Pages(0), Pages(2), Pages(5)]);
+
+        assert_eq!(u64::from(Pages(u32::MAX)), u32::MAX as u64);
+        assert_eq!(Pages::try_from(7u64), Ok(Pages(7)));
+        assert_eq!(Pages::try_from(u32::MAX as u64), Ok(Pages(u32::MAX)));
+        assert_eq!(
+            Pages::try_from(u32::MAX as u64 + 1),
+            Err(PageCountOutOfRange)
+        );
+    }
+
+    #[test]
+    fn checked_pages_arithmetic() {
+        assert_eq!(Pages(1).checked_add(Pages(2)), Some(Pages(3)));
+        assert_eq!(Pages::max_value().checked_add(Pages(1)), None);
+        assert_eq!(Pages(1).saturating_add(Pages(2)), Pages(3));
+        assert_eq!(Pages(u32::MAX).saturating_add(Pages(1)), Pages::max_value());
+
+        assert_eq!(Pages(2).checked_mul(3), Some(Pages(6)));
+        assert_eq!(Pages(0x100).checked_mul(0x100), Some(Pages::max_value()));
+        assert_eq!(Pages(0x100).checked_mul(0x101), None);
+        assert_eq!(Pages(u32::MAX).checked_mul(u32::MAX), None);
+
+        assert_eq!(Pages(3).to_bytes_checked(), Some(3 * WASM_PAGE_SIZE));
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            Pages(u32::MAX).to_bytes_checked(),
+            Some(u32::MAX as usize * WASM_PAGE_SIZE)
+        );
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(Pages::max_value().to_bytes_checked(), None);
+    }
 }
@@ -1,4 +1,178 @@
+use crate::error::WasmError;
+
+/// The version encoded in the header of every core WebAssembly binary.
+pub const WASM_BINARY_VERSION: u32 = 1;
+
 /// Check if the provided bytes are wasm-like
 pub fn is_wasm(bytes: impl AsRef<[u8]>) -> bool {
     bytes.as_ref().starts_with(b"\0asm")
 }
+
+/// Returns the version stored in bytes 4–7 of a wasm binary (little-endian).
+///
+/// Core modules use [`WASM_BINARY_VERSION`], while other kinds of binaries
+/// (such as components) use a different value. Returns `None` if the bytes
+/// are not wasm-like or are too short to hold a version.
+pub fn wasm_binary_version(bytes: impl AsRef<[u8]>) -> Option<u32> {
+    let bytes = bytes.as_ref();
+    if !is_wasm(bytes) {
+        return None;
+    }
+    let version: [u8; 4] = bytes.get(4..8)?.try_into().ok()?;
+    Some(u32::from_le_bytes(version))
+}
+
+/// Selects which custom sections [`strip_custom_sections`] removes from a
+/// wasm binary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StripMode {
+    /// Keep every custom section.
+    #[default]
+    None,
+    /// Remove every custom section.
+    All,
+    /// Remove every custom section except the ones with the given names.
+    AllExcept(Vec<String>),
+    /// Remove only the custom sections with the given names.
+    Only(Vec<String>),
+}
+
+impl StripMode {
+    /// Returns whether a custom section called `name` should be removed.
+    pub fn strips(&self, name: &str) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::AllExcept(keep) => !keep.iter().any(|k| k == name),
+            Self::Only(strip) => strip.iter().any(|s| s == name),
+        }
+    }
+}
+
+/// Removes the custom sections selected by `mode` from a core wasm binary.
+///
+/// All the other sections are copied over untouched, so the result
+/// compiles to the same code as the input.
+pub fn strip_custom_sections(bytes: &[u8], mode: &StripMode) -> Result<Vec<u8>, WasmError> {
+    fn invalid(message: &str, offset: usize) -> WasmError {
+        WasmError::InvalidWebAssembly {
+            message: message.to_string(),
+            offset,
+        }
+    }
+
+    fn read_u32(bytes: &[u8], offset: &mut usize) -> Result<u32, WasmError> {
+        let start = *offset;
+        let mut result = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = *bytes
+                .get(*offset)
+                .ok_or_else(|| invalid("unexpected end of LEB128 integer", start))?;
+            *offset += 1;
+            result |= u32::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(invalid("LEB128 integer is too long", start))
+    }
+
+    if !is_wasm(bytes) || bytes.len() < 8 {
+        return Err(invalid("expected a wasm binary", 0));
+    }
+
+    let mut stripped = bytes[..8].to_vec();
+    let mut offset = 8;
+    while offset < bytes.len() {
+        let start = offset;
+        let id = bytes[offset];
+        offset += 1;
+        let size = read_u32(bytes, &mut offset)? as usize;
+        let end = offset
+            .checked_add(size)
+            .filter(|end| *end <= bytes.len())
+            .ok_or_else(|| invalid("section extends past the end of the binary", start))?;
+
+        let keep = if id == 0 {
+            let mut name_offset = offset;
+            let name_len = read_u32(bytes, &mut name_offset)? as usize;
+            let name = name_offset
+                .checked_add(name_len)
+                .filter(|name_end| *name_end <= end)
+                .and_then(|name_end| std::str::from_utf8(&bytes[name_offset..name_end]).ok())
+                .ok_or_else(|| invalid("malformed custom section name", offset))?;
+            !mode.strips(name)
+        } else {
+            true
+        };
+        if keep {
+            stripped.extend_from_slice(&bytes[start..end]);
+        }
+        offset = end;
+    }
+
+    Ok(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_version() {
+        assert_eq!(
+            wasm_binary_version(b"\0asm\x01\0\0\0"),
+            Some(WASM_BINARY_VERSION)
+        );
+        // Component-model binaries use version 0x0d and layer 1.
+        assert_eq!(wasm_binary_version(b"\0asm\x0d\0\x01\0"), Some(0x0001_000d));
+        assert_eq!(wasm_binary_version(b"\0asm\x01"), None);
+        assert_eq!(wasm_binary_version(b"(module)"), None);
+    }
+
+    /// Builds a core module holding the given custom sections.
+    fn module_with_custom_sections(names: &[&str]) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // An empty type section, to check non-custom sections are kept.
+        wasm.extend_from_slice(&[1, 1, 0]);
+        for name in names {
+            let payload = b"payload";
+            wasm.push(0);
+            wasm.push((1 + name.len() + payload.len()) as u8);
+            wasm.push(name.len() as u8);
+            wasm.extend_from_slice(name.as_bytes());
+            wasm.extend_from_slice(payload);
+        }
+        wasm
+    }
+
+    #[test]
+    fn strip_sections() {
+        let wasm = module_with_custom_sections(&["name", ".debug_info", "producers"]);
+
+        assert_eq!(
+            strip_custom_sections(&wasm, &StripMode::None).unwrap(),
+            wasm
+        );
+        assert_eq!(
+            strip_custom_sections(&wasm, &StripMode::All).unwrap(),
+            module_with_custom_sections(&[])
+        );
+        assert_eq!(
+            strip_custom_sections(&wasm, &StripMode::AllExcept(vec!["name".into()])).unwrap(),
+            module_with_custom_sections(&["name"])
+        );
+        assert_eq!(
+            strip_custom_sections(&wasm, &StripMode::Only(vec![".debug_info".into()])).unwrap(),
+            module_with_custom_sections(&["name", "producers"])
+        );
+    }
+
+    #[test]
+    fn strip_sections_malformed() {
+        let mut wasm = module_with_custom_sections(&["name"]);
+        wasm.pop();
+        assert!(strip_custom_sections(&wasm, &StripMode::All).is_err());
+        assert!(strip_custom_sections(b"(module)", &StripMode::All).is_err());
+    }
+}
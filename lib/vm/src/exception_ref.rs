@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::sync::Arc;
 use wasmer_types::RawValue;
 
 use crate::store::InternalStoreHandle;
@@ -7,6 +8,8 @@ use crate::store::InternalStoreHandle;
 #[derive(Debug)]
 pub struct VMExceptionObj {
     contents: Box<dyn Any + Send + Sync + 'static>,
+    /// Shared with the host handles of the object, to count them.
+    liveness: Arc<()>,
 }
 
 impl VMExceptionObj {
@@ -14,6 +17,7 @@ impl VMExceptionObj {
     pub fn new(val: impl Any + Send + Sync + 'static) -> Self {
         Self {
             contents: Box::new(val),
+            liveness: Arc::new(()),
         }
     }
 
@@ -22,8 +26,33 @@ impl VMExceptionObj {
     pub fn as_ref(&self) -> &(dyn Any + Send + Sync + 'static) {
         &*self.contents
     }
+
+    /// Returns a mutable reference to the underlying value.
+    pub fn as_mut(&mut self) -> &mut (dyn Any + Send + Sync + 'static) {
+        &mut *self.contents
+    }
+
+    /// Moves the underlying value out if it is a `T`.
+    ///
+    /// The object is left empty: it no longer downcasts to any type.
+    pub fn take<T: Any + Send + Sync + 'static>(&mut self) -> Option<T> {
+        if !self.contents.is::<T>() {
+            return None;
+        }
+        let contents = std::mem::replace(&mut self.contents, Box::new(Taken));
+        contents.downcast::<T>().ok().map(|value| *value)
+    }
+
+    /// Returns the token that host handles to this object hold, to know
+    /// whether one of them is the last.
+    pub fn liveness(&self) -> &Arc<()> {
+        &self.liveness
+    }
 }
 
+/// Placeholder left behind by [`VMExceptionObj::take`].
+struct Taken;
+
 /// Represents an opaque reference to any data within WebAssembly.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy)]
@@ -1,29 +1,145 @@
 use std::any::Any;
+use std::sync::Arc;
 use wasmer_types::RawValue;
 
 use crate::store::InternalStoreHandle;
 
+/// A function called with the value of a [`VMExternObj`] when it is freed.
+pub type VMExternFinalizer = Box<dyn FnOnce(&mut (dyn Any + Send + Sync + 'static)) + Send>;
+
 /// Underlying object referenced by a `VMExternRef`.
-#[derive(Debug)]
 pub struct VMExternObj {
-    contents: Box<dyn Any + Send + Sync + 'static>,
+    /// `None` once the object was released, see
+    /// [`StoreObjects::release_extern_objs`](crate::StoreObjects::release_extern_objs).
+    contents: Option<Box<dyn Any + Send + Sync + 'static>>,
+    /// Shared with the host handles of the object, to count them.
+    liveness: Arc<()>,
+    /// Called when the object is released, or when the store is dropped.
+    finalizer: Option<VMExternFinalizer>,
+    /// Name of the type of the value the object was created with.
+    type_name: &'static str,
 }
 
 impl VMExternObj {
     /// Wraps the given value to expose it to Wasm code as an externref.
     pub fn new(val: impl Any + Send + Sync + 'static) -> Self {
         Self {
-            contents: Box::new(val),
+            type_name: std::any::type_name_of_val(&val),
+            contents: Some(Box::new(val)),
+            liveness: Arc::new(()),
+            finalizer: None,
+        }
+    }
+
+    /// Wraps the given value like [`VMExternObj::new`], calling
+    /// `finalizer` with it when the object is released or dropped with its
+    /// store.
+    ///
+    /// The finalizer is not called if the value was moved out with
+    /// [`VMExternObj::take`].
+    pub fn new_with_finalizer(
+        val: impl Any + Send + Sync + 'static,
+        finalizer: VMExternFinalizer,
+    ) -> Self {
+        Self {
+            type_name: std::any::type_name_of_val(&val),
+            contents: Some(Box::new(val)),
+            liveness: Arc::new(()),
+            finalizer: Some(finalizer),
         }
     }
 
     #[allow(clippy::should_implement_trait)]
     /// Returns a reference to the underlying value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object was released.
     pub fn as_ref(&self) -> &(dyn Any + Send + Sync + 'static) {
-        &*self.contents
+        self.contents
+            .as_deref()
+            .expect("extern object used after it was released")
+    }
+
+    /// Returns a mutable reference to the underlying value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object was released.
+    pub fn as_mut(&mut self) -> &mut (dyn Any + Send + Sync + 'static) {
+        self.contents
+            .as_deref_mut()
+            .expect("extern object used after it was released")
+    }
+
+    /// Moves the underlying value out if it is a `T`.
+    ///
+    /// The object is left empty: it no longer downcasts to any type.
+    pub fn take<T: Any + Send + Sync + 'static>(&mut self) -> Option<T> {
+        if !self.as_ref().is::<T>() {
+            return None;
+        }
+        let contents = self.contents.replace(Box::new(Taken))?;
+        contents.downcast::<T>().ok().map(|value| *value)
+    }
+
+    /// Returns the name of the type of the value the object was created
+    /// with, as given by [`std::any::type_name`].
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the token that host handles to this object hold: the object
+    /// is not released while one of them is alive.
+    ///
+    /// The token of a released object is dropped, so weak handles to it
+    /// can't be upgraded anymore.
+    pub fn liveness(&self) -> &Arc<()> {
+        &self.liveness
+    }
+
+    /// Returns true if the object was released.
+    pub fn is_released(&self) -> bool {
+        self.contents.is_none()
+    }
+
+    /// Finalizes and drops the underlying value, and drops the token of
+    /// the object.
+    pub(crate) fn release(&mut self) {
+        self.finalize();
+        self.contents = None;
+        self.liveness = Arc::new(());
+    }
+
+    fn finalize(&mut self) {
+        if let (Some(finalizer), Some(contents)) = (self.finalizer.take(), &mut self.contents) {
+            if !contents.is::<Taken>() {
+                finalizer(&mut **contents);
+            }
+        }
+    }
+}
+
+impl Drop for VMExternObj {
+    fn drop(&mut self) {
+        self.finalize();
     }
 }
 
+impl std::fmt::Debug for VMExternObj {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VMExternObj")
+            .field("contents", &self.contents)
+            .field("liveness", &self.liveness)
+            .field("finalizer", &self.finalizer.is_some())
+            .field("type_name", &self.type_name)
+            .finish()
+    }
+}
+
+/// Placeholder left behind by [`VMExternObj::take`].
+struct Taken;
+
 /// Represents an opaque reference to any data within WebAssembly.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy)]
@@ -28,7 +28,7 @@ use memoffset::offset_of;
 use more_asserts::assert_lt;
 use std::alloc::Layout;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::mem;
@@ -95,6 +95,14 @@ pub(crate) struct Instance {
     /// will point to elements here for functions imported by this instance.
     imported_funcrefs: BoxedSlice<FunctionIndex, NonNull<VMCallerCheckedAnyfunc>>,
 
+    /// Whether `finish_instantiation` should leave the start function to
+    /// be invoked later by the embedder.
+    defer_start: bool,
+
+    /// Imported functions bound to placeholders, waiting to be linked with
+    /// `VMInstance::link_function_import`.
+    unlinked_functions: HashSet<FunctionIndex>,
+
     /// Additional context used by compiled WebAssembly code. This
     /// field is last, and represents a dynamically-sized array that
     /// extends beyond the nominal end of the struct (similar to a
@@ -1076,6 +1084,8 @@ impl VMInstance {
                 passive_data,
                 funcrefs,
                 imported_funcrefs,
+                defer_start: false,
+                unlinked_functions: HashSet::new(),
                 vmctx: VMContext {},
             };
 
@@ -1175,10 +1185,57 @@ impl VMInstance {
 
         // The WebAssembly spec specifies that the start function is
         // invoked automatically at instantiation time.
-        instance.invoke_start_function(config, trap_handler)?;
+        if !instance.defer_start {
+            instance.invoke_start_function(config, trap_handler)?;
+        }
         Ok(())
     }
 
+    /// Makes [`VMInstance::finish_instantiation`] skip the start function,
+    /// leaving it to the embedder to invoke it later, if at all.
+    pub fn defer_start_function(&mut self) {
+        self.instance_mut().defer_start = true;
+    }
+
+    /// Marks the imported function `index` as bound to a placeholder, to be
+    /// replaced later with [`VMInstance::link_function_import`].
+    pub fn mark_function_import_unlinked(&mut self, index: FunctionIndex) {
+        self.instance_mut().unlinked_functions.insert(index);
+    }
+
+    /// Returns true if the imported function `index` is still bound to a
+    /// placeholder.
+    pub fn is_function_import_unlinked(&self, index: FunctionIndex) -> bool {
+        self.instance().unlinked_functions.contains(&index)
+    }
+
+    /// Replaces the placeholder bound to the imported function `index`.
+    ///
+    /// Calls made by the instance after this go to `import`. References to
+    /// the function taken before, for instance stored in a table, keep
+    /// pointing to the placeholder.
+    ///
+    /// # Safety
+    ///
+    /// `import` and `anyfunc` must describe a function with the signature
+    /// the module expects for `index`, living in the same store as this
+    /// instance.
+    pub unsafe fn link_function_import(
+        &mut self,
+        index: FunctionIndex,
+        import: VMFunctionImport,
+        anyfunc: NonNull<VMCallerCheckedAnyfunc>,
+    ) {
+        let instance = self.instance_mut();
+        assert_lt!(index.index(), instance.module.num_imported_functions);
+        instance
+            .imported_functions_ptr()
+            .add(index.index())
+            .write(import);
+        instance.imported_funcrefs[index] = anyfunc;
+        instance.unlinked_functions.remove(&index);
+    }
+
     /// Return a reference to the vmctx used by compiled wasm code.
     pub fn vmctx(&self) -> &VMContext {
         self.instance().vmctx()
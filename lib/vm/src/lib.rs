@@ -37,7 +37,7 @@ use std::ptr::NonNull;
 
 pub use crate::exception_ref::{VMExceptionObj, VMExceptionRef};
 pub use crate::export::*;
-pub use crate::extern_ref::{VMExternObj, VMExternRef};
+pub use crate::extern_ref::{VMExternFinalizer, VMExternObj, VMExternRef};
 pub use crate::function_env::VMFunctionEnvironment;
 pub use crate::global::*;
 pub use crate::imports::Imports;
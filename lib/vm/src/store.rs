@@ -1,10 +1,11 @@
 use crate::{
-    VMExceptionObj, VMExternObj, VMFunction, VMFunctionEnvironment, VMGlobal, VMInstance, VMMemory,
-    VMTable, VMTag,
+    LinearMemory, TableElement, VMExceptionObj, VMExternObj, VMExternRef, VMFunction,
+    VMFunctionEnvironment, VMGlobal, VMInstance, VMMemory, VMTable, VMTag,
 };
 use core::slice::Iter;
+use std::sync::Arc;
 use std::{cell::UnsafeCell, fmt, marker::PhantomData, num::NonZeroUsize, ptr::NonNull};
-use wasmer_types::StoreId;
+use wasmer_types::{MemoryStyle, StoreId};
 
 /// Trait to represent an object managed by a context. This is implemented on
 /// the VM types managed by the context.
@@ -54,6 +55,8 @@ pub struct StoreObjects {
     exceptions: Vec<VMExceptionObj>,
     tags: Vec<VMTag>,
     function_environments: Vec<VMFunctionEnvironment>,
+    /// Slots of `extern_objs` that were released and can be reused.
+    free_extern_objs: Vec<InternalStoreHandle<VMExternObj>>,
 }
 
 impl StoreObjects {
@@ -82,6 +85,7 @@ impl StoreObjects {
             function_environments,
             exceptions,
             tags,
+            free_extern_objs: Vec::new(),
         }
     }
 
@@ -114,6 +118,105 @@ impl StoreObjects {
         }
     }
 
+    /// Returns the offset of `address` in the linear memory whose
+    /// reservation, guard pages included, contains it.
+    ///
+    /// This turns the [`Trap::fault_address`](crate::Trap::fault_address)
+    /// of an out-of-bounds access into the offset the guest tried to
+    /// access.
+    pub fn memory_offset_of(&self, address: usize) -> Option<u64> {
+        self.memories.iter().find_map(|memory| {
+            let definition = unsafe { memory.vmmemory().as_ref() };
+            let base = definition.base as usize;
+            let style = memory.style();
+            let reserved = match style {
+                MemoryStyle::Static { bound, .. } => bound.bytes().0 as u64,
+                MemoryStyle::Dynamic { .. } => definition.current_length as u64,
+            } + style.offset_guard_size();
+            let offset = address.checked_sub(base)? as u64;
+            (offset < reserved).then_some(offset)
+        })
+    }
+
+    /// Moves the given extern object into the store, reusing the slot of a
+    /// released one if there is any.
+    pub fn insert_extern_obj(&mut self, obj: VMExternObj) -> StoreHandle<VMExternObj> {
+        let internal = match self.free_extern_objs.pop() {
+            Some(internal) => {
+                *internal.get_mut(self) = obj;
+                internal
+            }
+            None => InternalStoreHandle::new(self, obj),
+        };
+        StoreHandle {
+            id: self.id,
+            internal,
+        }
+    }
+
+    /// Releases the extern objects that have no host handle left and are
+    /// not referenced by a table or a global of the store, and returns how
+    /// many were released. Their slots are reused by the next objects.
+    ///
+    /// # Safety
+    ///
+    /// Wasm code of the store must not be running: references held in its
+    /// locals or on its stack are not seen. Raw `VMExternRef`s to a
+    /// released object must not be used anymore.
+    pub unsafe fn release_extern_objs(&mut self) -> usize {
+        let mut reachable = vec![false; self.extern_objs.len()];
+        let mut mark = |externref: Option<VMExternRef>| {
+            if let Some(externref) = externref {
+                reachable[externref.0.index() - 1] = true;
+            }
+        };
+        for table in &self.tables {
+            if table.ty().ty == wasmer_types::Type::ExternRef {
+                for index in 0..table.size() {
+                    if let Some(TableElement::ExternRef(externref)) = table.get(index) {
+                        mark(externref);
+                    }
+                }
+            }
+        }
+        for global in &self.globals {
+            if global.ty().ty == wasmer_types::Type::ExternRef {
+                mark(VMExternRef::from_raw(global.vmglobal().as_ref().val));
+            }
+        }
+
+        let mut released = 0;
+        for (index, obj) in self.extern_objs.iter_mut().enumerate() {
+            if !obj.is_released() && !reachable[index] && Arc::strong_count(obj.liveness()) == 1 {
+                obj.release();
+                self.free_extern_objs
+                    .push(InternalStoreHandle::from_index(index + 1).unwrap());
+                released += 1;
+            }
+        }
+        released
+    }
+
+    /// Returns the number of objects of type `T` in the store.
+    pub fn count<T: StoreObject>(&self) -> usize {
+        T::list(self).len()
+    }
+
+    /// Returns the number of extern objects that were not released by
+    /// [`StoreObjects::release_extern_objs`].
+    pub fn live_extern_objs(&self) -> usize {
+        self.extern_objs.len() - self.free_extern_objs.len()
+    }
+
+    /// Returns true if `index`, the raw value of a [`VMExternRef`],
+    /// designates an extern object of this store that was not released.
+    pub fn is_live_extern_obj(&self, index: usize) -> bool {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.extern_objs.get(index))
+            .is_some_and(|obj| !obj.is_released())
+    }
+
     /// Return an immutable iterator over all globals
     pub fn iter_globals(&self) -> Iter<VMGlobal> {
         self.globals.iter()
@@ -224,6 +224,22 @@ impl VMTable {
         })
     }
 
+    /// Check whether the specified element is a null reference, without
+    /// cloning it out of the table.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    pub fn is_null(&self, index: u32) -> Option<bool> {
+        let raw_data = self.vec.get(index as usize)?;
+        Some(match self.table.ty {
+            ValType::ExternRef => unsafe { raw_data.extern_ref }.is_none(),
+            ValType::FuncRef => unsafe { raw_data.func_ref }
+                .map_or(true, |f| unsafe { f.0.as_ref() }.func_ptr.is_null()),
+            // Tables only hold references of the two types above, see
+            // `new_inner`.
+            _ => false,
+        })
+    }
+
     /// Set reference to the specified element.
     ///
     /// # Errors
@@ -19,6 +19,9 @@ pub enum Trap {
         backtrace: Backtrace,
         /// Optional trapcode associated to the signal that caused the trap
         signal_trap: Option<TrapCode>,
+        /// The faulting address, for out-of-bounds memory accesses caught
+        /// by a guard page
+        fault_address: Option<usize>,
     },
 
     /// A trap raised from a wasm libcall
@@ -60,6 +63,20 @@ impl Trap {
             pc,
             backtrace,
             signal_trap,
+            fault_address: None,
+        }
+    }
+
+    /// Returns the faulting address of an out-of-bounds memory access
+    /// caught by a guard page.
+    ///
+    /// The address is in the host address space; use
+    /// [`StoreObjects::memory_offset_of`](crate::StoreObjects::memory_offset_of)
+    /// to turn it into an offset in a linear memory.
+    pub fn fault_address(&self) -> Option<usize> {
+        match self {
+            Self::Wasm { fault_address, .. } => *fault_address,
+            _ => None,
         }
     }
 
@@ -80,6 +80,11 @@ pub fn set_stack_size(size: usize) {
     DEFAULT_STACK_SIZE.store(size.clamp(8 * 1024, 100 * 1024 * 1024), Ordering::Relaxed);
 }
 
+/// Returns the stack size used when the [`VMConfig`] doesn't set one.
+pub fn stack_size() -> usize {
+    DEFAULT_STACK_SIZE.load(Ordering::Relaxed)
+}
+
 cfg_if::cfg_if! {
     if #[cfg(unix)] {
         /// Function which may handle custom signals while processing traps.
@@ -713,9 +718,7 @@ where
 {
     // Ensure that per-thread initialization is done.
     lazy_per_thread_init()?;
-    let stack_size = config
-        .wasm_stack_size
-        .unwrap_or_else(|| DEFAULT_STACK_SIZE.load(Ordering::Relaxed));
+    let stack_size = config.wasm_stack_size.unwrap_or_else(stack_size);
     on_wasm_stack(stack_size, trap_handler, closure).map_err(UnwindReason::into_trap)
 }
 
@@ -875,10 +878,15 @@ impl<T> TrapHandlerContextInner<T> {
 
         // Set up the register state for exception return to force the
         // coroutine to return to its caller with UnwindReason::WasmTrap.
+        let fault_address = match signal_trap {
+            Some(TrapCode::HeapAccessOutOfBounds) => maybe_fault_address,
+            _ => None,
+        };
         let unwind = UnwindReason::WasmTrap {
             backtrace,
             signal_trap,
             pc,
+            fault_address,
         };
         let regs = self
             .coro_trap_handler
@@ -900,6 +908,7 @@ enum UnwindReason {
         backtrace: Backtrace,
         pc: usize,
         signal_trap: Option<TrapCode>,
+        fault_address: Option<usize>,
     },
 }
 
@@ -912,7 +921,13 @@ impl UnwindReason {
                 backtrace,
                 pc,
                 signal_trap,
-            } => Trap::wasm(pc, backtrace, signal_trap),
+                fault_address,
+            } => Trap::Wasm {
+                pc,
+                backtrace,
+                signal_trap,
+                fault_address,
+            },
             Self::Panic(panic) => std::panic::resume_unwind(panic),
         }
     }
@@ -941,13 +956,17 @@ fn on_wasm_stack<F: FnOnce() -> T + 'static, T: 'static>(
     // system calls. We therefore keep a cache of pre-allocated stacks which
     // allows them to be reused multiple times.
     // FIXME(Amanieu): We should refactor this to avoid the lock.
-    static STACK_POOL: LazyLock<crossbeam_queue::SegQueue<DefaultStack>> =
+    //
+    // Stacks are pooled with the size they were requested with, so that code
+    // runs on a stack of the size it was configured with.
+    static STACK_POOL: LazyLock<crossbeam_queue::SegQueue<(usize, DefaultStack)>> =
         LazyLock::new(crossbeam_queue::SegQueue::new);
 
-    let stack = STACK_POOL
-        .pop()
-        .unwrap_or_else(|| DefaultStack::new(stack_size).unwrap());
-    let mut stack = scopeguard::guard(stack, |stack| STACK_POOL.push(stack));
+    let stack = match STACK_POOL.pop() {
+        Some((size, stack)) if size == stack_size => stack,
+        _ => DefaultStack::new(stack_size).unwrap(),
+    };
+    let mut stack = scopeguard::guard(stack, |stack| STACK_POOL.push((stack_size, stack)));
 
     // Create a coroutine with a new stack to run the function on.
     let mut coro = Coroutine::with_stack(&mut *stack, move |yielder, ()| {
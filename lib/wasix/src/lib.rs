@@ -67,8 +67,8 @@ pub use wasmer;
 pub use wasmer_wasix_types;
 
 use wasmer::{
-    imports, namespace, AsStoreMut, Exports, FunctionEnv, Imports, Memory32, MemoryAccessError,
-    MemorySize, RuntimeError,
+    imports, namespace, AsStoreMut, Exports, FunctionEnv, Imports, Linker, LinkerError, Memory32,
+    MemoryAccessError, MemorySize, RuntimeError,
 };
 
 pub use virtual_fs;
@@ -375,6 +375,72 @@ pub fn generate_import_object_from_env(
     imports
 }
 
+/// Registers WASI host functions in a [`Linker`].
+pub trait WasiLinkerExt {
+    /// Defines all the host functions of the WASI `version` for `env`, like
+    /// [`generate_import_object_from_env`].
+    ///
+    /// Several versions can be defined in the same linker, a module then
+    /// imports the one it was compiled for. The functions of the `wasi`
+    /// namespace, common to all versions, are only defined once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LinkerError::NamespaceDefined`] if `version` was already
+    /// defined with this method, and [`LinkerError::Shadowing`] if one of
+    /// its functions was defined by something else than this method.
+    fn define_wasi(
+        &mut self,
+        store: &mut impl AsStoreMut,
+        env: &FunctionEnv<WasiEnv>,
+        version: WasiVersion,
+    ) -> Result<&mut Self, LinkerError>;
+
+    /// Returns the WASI versions defined with
+    /// [`WasiLinkerExt::define_wasi`].
+    fn wasi_versions(&self) -> Vec<WasiVersion>;
+}
+
+impl WasiLinkerExt for Linker {
+    fn define_wasi(
+        &mut self,
+        store: &mut impl AsStoreMut,
+        env: &FunctionEnv<WasiEnv>,
+        version: WasiVersion,
+    ) -> Result<&mut Self, LinkerError> {
+        // The versions are recorded under their namespace, so that
+        // `Latest` and `Snapshot1` are the same version.
+        let namespace = version.get_namespace_str();
+        if self.has_api(namespace) {
+            return Err(LinkerError::NamespaceDefined(namespace.to_string()));
+        }
+        // The generic namespace comes with every version, and was defined
+        // with the first one.
+        let generic_defined = !self.wasi_versions().is_empty();
+        let mut imports = Imports::new();
+        for ((module, name), ext) in &generate_import_object_from_env(store, env, version) {
+            if !(generic_defined && module == "wasi") {
+                imports.define(&module, &name, ext);
+            }
+        }
+        self.define_imports(&imports)?;
+        self.record_api(namespace);
+        Ok(self)
+    }
+
+    fn wasi_versions(&self) -> Vec<WasiVersion> {
+        [
+            WasiVersion::Snapshot0,
+            WasiVersion::Snapshot1,
+            WasiVersion::Wasix32v1,
+            WasiVersion::Wasix64v1,
+        ]
+        .into_iter()
+        .filter(|version| self.has_api(version.get_namespace_str()))
+        .collect()
+    }
+}
+
 fn wasi_exports_generic(mut store: &mut impl AsStoreMut, env: &FunctionEnv<WasiEnv>) -> Exports {
     use syscalls::*;
     let namespace = namespace! {
@@ -883,3 +949,90 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use wasmer::{Function, Store};
+
+    use super::*;
+
+    fn with_wasi_env(f: impl FnOnce(&mut Store, &FunctionEnv<WasiEnv>)) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        #[cfg(not(target_arch = "wasm32"))]
+        let _guard = runtime.enter();
+
+        let mut store = Store::default();
+        let env = WasiEnv::builder("test_prog")
+            .finalize(&mut store)
+            .unwrap()
+            .env;
+        f(&mut store, &env);
+    }
+
+    #[test]
+    fn define_wasi() {
+        with_wasi_env(|store, env| {
+            let mut linker = Linker::new();
+            linker
+                .define_wasi(store, env, WasiVersion::Snapshot1)
+                .unwrap()
+                .define_wasi(store, env, WasiVersion::Wasix32v1)
+                .unwrap();
+            assert_eq!(
+                linker.wasi_versions(),
+                [WasiVersion::Snapshot1, WasiVersion::Wasix32v1]
+            );
+            assert!(linker.contains("wasi_snapshot_preview1", "fd_write"));
+            assert!(linker.contains("wasi", "thread-spawn"));
+
+            // `Latest` is the same version as `Snapshot1`.
+            assert!(matches!(
+                linker.define_wasi(store, env, WasiVersion::Latest),
+                Err(LinkerError::NamespaceDefined(namespace))
+                    if namespace == "wasi_snapshot_preview1"
+            ));
+            assert!(matches!(
+                linker.define_wasi(store, env, WasiVersion::Wasix32v1),
+                Err(LinkerError::NamespaceDefined(namespace)) if namespace == "wasix_32v1"
+            ));
+        });
+    }
+
+    #[test]
+    fn define_wasi_in_user_namespace() {
+        with_wasi_env(|store, env| {
+            let mut linker = Linker::new();
+            linker
+                .define("wasi_unstable", "extra", Function::new_typed(store, || {}))
+                .unwrap();
+            // Defining a function in the namespace doesn't define the version.
+            assert!(linker.wasi_versions().is_empty());
+            linker
+                .define_wasi(store, env, WasiVersion::Snapshot0)
+                .unwrap();
+            assert_eq!(linker.wasi_versions(), [WasiVersion::Snapshot0]);
+            assert!(linker.contains("wasi_unstable", "extra"));
+            assert!(linker.contains("wasi_unstable", "fd_write"));
+
+            // A version whose functions are already defined is not recorded.
+            linker
+                .define(
+                    "wasi_snapshot_preview1",
+                    "fd_write",
+                    Function::new_typed(store, || {}),
+                )
+                .unwrap();
+            assert!(matches!(
+                linker.define_wasi(store, env, WasiVersion::Snapshot1),
+                Err(LinkerError::Shadowing(module, name))
+                    if module == "wasi_snapshot_preview1" && name == "fd_write"
+            ));
+            assert_eq!(linker.wasi_versions(), [WasiVersion::Snapshot0]);
+            assert!(!linker.contains("wasi_snapshot_preview1", "fd_read"));
+        });
+    }
+}